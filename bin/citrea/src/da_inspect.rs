@@ -0,0 +1,36 @@
+//! Decodes the Citrea inscriptions in a raw bitcoin block or transaction and prints them as JSON.
+
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use bitcoin_da::inspect::inspect_hex;
+use bitcoin_da::network::NetworkProfile;
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Path to a file with a hex encoded block or transaction.
+    input: PathBuf,
+
+    /// Network profile whose reveal txid prefixes are used, in the format of `DA_NETWORK_PROFILE`.
+    /// The named networks can be given without quotes, e.g. `regtest`.
+    #[arg(long, default_value = "mainnet", value_parser = parse_network_profile)]
+    network_profile: NetworkProfile,
+}
+
+fn parse_network_profile(profile: &str) -> Result<NetworkProfile, serde_json::Error> {
+    serde_json::from_str(profile)
+        .or_else(|_| serde_json::from_value(serde_json::Value::String(profile.to_string())))
+}
+
+fn main() -> Result<(), anyhow::Error> {
+    let args = Args::parse();
+
+    let input = std::fs::read_to_string(&args.input)
+        .with_context(|| format!("Failed to read {:?}", args.input))?;
+    let inscriptions = inspect_hex(&input, &args.network_profile.rollup_params())?;
+
+    println!("{}", serde_json::to_string_pretty(&inscriptions)?);
+    Ok(())
+}
@@ -4,12 +4,11 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use bitcoin_da::rpc::create_rpc_module as create_da_rpc_module;
 use bitcoin_da::service::{BitcoinService, BitcoinServiceConfig, TxidWrapper};
-use bitcoin_da::spec::{BitcoinSpec, RollupParams};
+use bitcoin_da::spec::BitcoinSpec;
 use bitcoin_da::verifier::BitcoinVerifier;
 use citrea_common::rpc::register_healthcheck_rpc;
 use citrea_common::tasks::manager::TaskManager;
 use citrea_common::FullNodeConfig;
-use citrea_primitives::{TO_BATCH_PROOF_PREFIX, TO_LIGHT_CLIENT_PREFIX};
 use citrea_risc0_adapter::host::Risc0BonsaiHost;
 // use citrea_sp1::host::SP1Host;
 use citrea_stf::genesis_config::StorageConfig;
@@ -123,27 +122,14 @@ impl RollupBlueprint for BitcoinRollup {
         task_manager: &mut TaskManager<()>,
     ) -> Result<Arc<Self::DaService>, anyhow::Error> {
         let (tx, rx) = unbounded_channel::<SenderWithNotifier<TxidWrapper>>();
+        let rollup_params = rollup_config.da.rollup_params()?;
 
         let bitcoin_service = if require_wallet_check {
-            BitcoinService::new_with_wallet_check(
-                rollup_config.da.clone(),
-                RollupParams {
-                    to_light_client_prefix: TO_LIGHT_CLIENT_PREFIX.to_vec(),
-                    to_batch_proof_prefix: TO_BATCH_PROOF_PREFIX.to_vec(),
-                },
-                tx,
-            )
-            .await?
+            BitcoinService::new_with_wallet_check(rollup_config.da.clone(), rollup_params, tx)
+                .await?
         } else {
-            BitcoinService::new_without_wallet_check(
-                rollup_config.da.clone(),
-                RollupParams {
-                    to_light_client_prefix: TO_LIGHT_CLIENT_PREFIX.to_vec(),
-                    to_batch_proof_prefix: TO_BATCH_PROOF_PREFIX.to_vec(),
-                },
-                tx,
-            )
-            .await?
+            BitcoinService::new_without_wallet_check(rollup_config.da.clone(), rollup_params, tx)
+                .await?
         };
         let service = Arc::new(bitcoin_service);
         // until forced transactions are implemented,
@@ -154,16 +140,18 @@ impl RollupBlueprint for BitcoinRollup {
 
             task_manager.spawn(|tk| Arc::clone(&service).run_da_queue(rx, tk));
             task_manager.spawn(|tk| Arc::clone(&service.monitoring).run(tk));
+            task_manager.spawn(|tk| Arc::clone(&service).run_fee_bumper(tk));
+            task_manager.spawn(|tk| Arc::clone(&service).run_utxo_consolidation(tk));
         }
 
         Ok(service)
     }
 
-    fn create_da_verifier(&self) -> Self::DaVerifier {
-        BitcoinVerifier::new(RollupParams {
-            to_light_client_prefix: TO_LIGHT_CLIENT_PREFIX.to_vec(),
-            to_batch_proof_prefix: TO_BATCH_PROOF_PREFIX.to_vec(),
-        })
+    fn create_da_verifier(
+        &self,
+        rollup_config: &FullNodeConfig<Self::DaConfig>,
+    ) -> Result<Self::DaVerifier, anyhow::Error> {
+        Ok(BitcoinVerifier::new(rollup_config.da.rollup_params()?))
     }
 
     fn get_batch_proof_elfs(&self) -> HashMap<SpecId, Vec<u8>> {
@@ -96,8 +96,11 @@ impl RollupBlueprint for MockDemoRollup {
         )))
     }
 
-    fn create_da_verifier(&self) -> Self::DaVerifier {
-        Default::default()
+    fn create_da_verifier(
+        &self,
+        _rollup_config: &FullNodeConfig<Self::DaConfig>,
+    ) -> Result<Self::DaVerifier, anyhow::Error> {
+        Ok(Default::default())
     }
 
     fn get_batch_proof_elfs(&self) -> HashMap<SpecId, Vec<u8>> {
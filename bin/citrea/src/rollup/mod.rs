@@ -309,7 +309,7 @@ pub trait CitreaRollupBlueprint: RollupBlueprint {
             .create_da_service(&rollup_config, true, &mut task_manager)
             .await?;
 
-        let da_verifier = self.create_da_verifier();
+        let da_verifier = self.create_da_verifier(&rollup_config)?;
 
         // Migrate before constructing ledger_db instance so that no lock is present.
         let migrator = LedgerDBMigrator::new(
@@ -439,7 +439,7 @@ pub trait CitreaRollupBlueprint: RollupBlueprint {
         let da_service = self
             .create_da_service(&rollup_config, true, &mut task_manager)
             .await?;
-        let da_verifier = self.create_da_verifier();
+        let da_verifier = self.create_da_verifier(&rollup_config)?;
 
         let rocksdb_config = RocksdbConfig::new(
             rollup_config.storage.path.as_path(),
@@ -17,6 +17,7 @@ use citrea_e2e::node::{Config, NodeKind};
 use citrea_e2e::test_case::{TestCase, TestCaseRunner};
 use citrea_e2e::traits::NodeT;
 use citrea_e2e::Result;
+use citrea_primitives::forks::FORK2_DA_ACTIVATION_HEIGHT;
 use citrea_primitives::{TO_BATCH_PROOF_PREFIX, TO_LIGHT_CLIENT_PREFIX};
 use reth_primitives::{Address, U64};
 use sov_ledger_rpc::LedgerRpcClient;
@@ -131,8 +132,10 @@ impl TestCase for BasicProverTest {
                 .map(|(k, v)| k.len() + v.as_ref().map(|v| v.len()).unwrap_or_default())
                 .sum();
             let borshed_state_diff = borsh::to_vec(state_diff).unwrap();
-            let compressed_state_diff =
-                citrea_primitives::compression::compress_blob(&borshed_state_diff);
+            let compressed_state_diff = citrea_primitives::compression::compress_blob(
+                &borshed_state_diff,
+                citrea_primitives::compression::BlobCompression::Legacy,
+            );
             println!(
                 "StateDiff: size {}, compressed {}",
                 state_diff_size,
@@ -205,12 +208,18 @@ impl TestCase for SkipPreprovenCommitmentsTest {
                 // somehow resubmitted the same commitment.
                 "045FFC81A3C1FDB3AF1359DBF2D114B0B3EFBF7F29CC9C5DA01267AA39D2C78D".to_owned(),
             ),
+            signer: None,
             tx_backup_dir: Self::test_config()
                 .dir
                 .join("tx_backup_dir")
                 .display()
                 .to_string(),
             monitoring: Default::default(),
+            fee_bump: None,
+            fee_estimator: None,
+            coin_selection: None,
+            finality_depth: None,
+            network_profile: None,
         };
         let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
 
@@ -220,6 +229,7 @@ impl TestCase for SkipPreprovenCommitmentsTest {
                 RollupParams {
                     to_light_client_prefix: TO_LIGHT_CLIENT_PREFIX.to_vec(),
                     to_batch_proof_prefix: TO_BATCH_PROOF_PREFIX.to_vec(),
+                    fork2_da_activation_height: FORK2_DA_ACTIVATION_HEIGHT,
                 },
                 tx,
             )
@@ -3,13 +3,16 @@ use std::str::FromStr;
 use citrea_common::SequencerConfig;
 // use citrea::initialize_logging;
 use citrea_evm::smart_contracts::{CallerContract, SimpleStorageContract};
+use citrea_evm::FLAT_CALL_TRACER;
 use citrea_stf::genesis_config::GenesisPaths;
-use reth_primitives::{Address, BlockNumberOrTag};
+use reth_primitives::{Address, BlockNumberOrTag, B256};
+use reth_rpc_types::trace::filter::TraceFilter;
 use reth_rpc_types::trace::geth::GethTrace::{self, CallTracer, FourByteTracer};
 use reth_rpc_types::trace::geth::{
-    CallConfig, CallFrame, FourByteFrame, GethDebugBuiltInTracerType, GethDebugTracerType,
-    GethDebugTracingOptions,
+    CallConfig, CallFrame, FourByteFrame, GethDebugBuiltInTracerType, GethDebugTracerConfig,
+    GethDebugTracerType, GethDebugTracingOptions, GethDefaultTracingOptions, PreStateFrame,
 };
+use reth_rpc_types::trace::parity::{Action, LocalizedTransactionTrace, TraceType};
 use serde_json::{self, json};
 
 use crate::evm::make_test_client;
@@ -326,3 +329,330 @@ async fn tracing_tests() -> Result<(), Box<dyn std::error::Error>> {
     rollup_task.abort();
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn parity_tracing_tests() -> Result<(), Box<dyn std::error::Error>> {
+    let storage_dir = tempdir_with_children(&["DA", "sequencer", "full-node"]);
+    let da_db_dir = storage_dir.path().join("DA").to_path_buf();
+    let sequencer_db_dir = storage_dir.path().join("sequencer").to_path_buf();
+
+    let (port_tx, port_rx) = tokio::sync::oneshot::channel();
+
+    let rollup_config =
+        create_default_rollup_config(true, &sequencer_db_dir, &da_db_dir, NodeMode::SequencerNode);
+    let sequencer_config = SequencerConfig::default();
+
+    let rollup_task = tokio::spawn(async {
+        start_rollup(
+            port_tx,
+            GenesisPaths::from_dir(TEST_DATA_GENESIS_PATH),
+            None,
+            None,
+            rollup_config,
+            Some(sequencer_config),
+        )
+        .await;
+    });
+
+    let port = port_rx.await.unwrap();
+
+    let test_client = make_test_client(port).await?;
+
+    let ss_contract = SimpleStorageContract::default();
+    let deploy_ss_contract_req = test_client
+        .deploy_contract(ss_contract.byte_code(), None)
+        .await?;
+    let caller_contract = CallerContract::default();
+    let deploy_caller_contract_req = test_client
+        .deploy_contract(caller_contract.byte_code(), None)
+        .await?;
+
+    test_client.send_publish_batch_request().await;
+
+    let ss_contract_address = deploy_ss_contract_req
+        .get_receipt()
+        .await?
+        .contract_address
+        .unwrap();
+    let caller_contract_address = deploy_caller_contract_req
+        .get_receipt()
+        .await?
+        .contract_address
+        .unwrap();
+
+    let call_set_value_req = test_client
+        .contract_transaction(
+            caller_contract_address,
+            caller_contract.call_set_call_data(ss_contract_address, 3),
+            None,
+        )
+        .await;
+    test_client.send_publish_batch_request().await;
+    let receipt = call_set_value_req.get_receipt().await?;
+    let tx_hash = receipt.transaction_hash;
+    let block_number = receipt.block_number.unwrap();
+
+    // the caller contract calls the simple storage contract
+    let tx_traces = test_client.trace_transaction(tx_hash).await.unwrap();
+    assert_eq!(tx_traces.len(), 2);
+    assert_eq!(tx_traces[0].trace.subtraces, 1);
+    assert!(tx_traces[0].trace.trace_address.is_empty());
+    assert_eq!(tx_traces[1].trace.trace_address, vec![0]);
+    let Action::Call(top_call) = &tx_traces[0].trace.action else {
+        panic!("Expected a call action");
+    };
+    assert_eq!(top_call.from, test_client.from_addr);
+    assert_eq!(top_call.to, caller_contract_address);
+    let Action::Call(inner_call) = &tx_traces[1].trace.action else {
+        panic!("Expected a call action");
+    };
+    assert_eq!(inner_call.from, caller_contract_address);
+    assert_eq!(inner_call.to, ss_contract_address);
+    assert!(tx_traces
+        .iter()
+        .all(|trace| trace.transaction_hash == Some(tx_hash)
+            && trace.block_number == Some(block_number)));
+
+    assert!(test_client
+        .trace_transaction(B256::repeat_byte(1))
+        .await
+        .is_none());
+
+    let block_traces = test_client
+        .trace_block(BlockNumberOrTag::Number(block_number))
+        .await;
+    let block_traces = block_traces
+        .into_iter()
+        .filter(|trace| trace.transaction_hash == Some(tx_hash))
+        .collect::<Vec<_>>();
+    assert_eq!(block_traces, tx_traces);
+
+    // both deployments are in the previous block
+    let deploy_traces = test_client
+        .trace_block(BlockNumberOrTag::Number(block_number - 1))
+        .await;
+    let deploy_traces = deploy_traces
+        .into_iter()
+        .filter(|trace| match &trace.trace.action {
+            Action::Create(create) => create.from == test_client.from_addr,
+            _ => false,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(deploy_traces.len(), 2);
+
+    // only the inner call goes to the simple storage contract
+    let filtered_traces = test_client
+        .trace_filter(TraceFilter {
+            from_block: Some(0),
+            to_block: Some(block_number),
+            to_address: vec![ss_contract_address],
+            ..Default::default()
+        })
+        .await;
+    assert_eq!(filtered_traces, vec![tx_traces[1].clone()]);
+
+    // the two deployments and the call are sent by the test client
+    let sender_filter = TraceFilter {
+        from_block: Some(0),
+        to_block: Some(block_number),
+        from_address: vec![test_client.from_addr],
+        ..Default::default()
+    };
+    let sender_traces = test_client.trace_filter(sender_filter.clone()).await;
+    assert_eq!(
+        sender_traces,
+        vec![
+            deploy_traces[0].clone(),
+            deploy_traces[1].clone(),
+            tx_traces[0].clone()
+        ]
+    );
+    let paginated_traces = test_client
+        .trace_filter(TraceFilter {
+            after: Some(1),
+            count: Some(1),
+            ..sender_filter
+        })
+        .await;
+    assert_eq!(paginated_traces, vec![deploy_traces[1].clone()]);
+
+    let replayed = test_client
+        .trace_replay_block_transactions(
+            BlockNumberOrTag::Number(block_number),
+            vec![TraceType::Trace, TraceType::StateDiff],
+        )
+        .await;
+    let replayed = replayed
+        .into_iter()
+        .find(|result| result.transaction_hash == tx_hash)
+        .unwrap();
+    assert_eq!(replayed.full_trace.trace.len(), 2);
+    let state_diff = replayed.full_trace.state_diff.unwrap();
+    // the stored value changes from 0 to 3
+    let ss_diff = state_diff.get(&ss_contract_address).unwrap();
+    assert_eq!(ss_diff.storage.len(), 1);
+    // the sender pays for the transaction and its nonce is increased
+    assert!(state_diff.contains_key(&test_client.from_addr));
+
+    rollup_task.abort();
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn prestate_flat_call_and_struct_log_tracing_tests() -> Result<(), Box<dyn std::error::Error>>
+{
+    let storage_dir = tempdir_with_children(&["DA", "sequencer", "full-node"]);
+    let da_db_dir = storage_dir.path().join("DA").to_path_buf();
+    let sequencer_db_dir = storage_dir.path().join("sequencer").to_path_buf();
+
+    let (port_tx, port_rx) = tokio::sync::oneshot::channel();
+
+    let rollup_config =
+        create_default_rollup_config(true, &sequencer_db_dir, &da_db_dir, NodeMode::SequencerNode);
+    let sequencer_config = SequencerConfig::default();
+
+    let rollup_task = tokio::spawn(async {
+        start_rollup(
+            port_tx,
+            GenesisPaths::from_dir(TEST_DATA_GENESIS_PATH),
+            None,
+            None,
+            rollup_config,
+            Some(sequencer_config),
+        )
+        .await;
+    });
+
+    let port = port_rx.await.unwrap();
+
+    let test_client = make_test_client(port).await?;
+
+    let ss_contract = SimpleStorageContract::default();
+    let deploy_ss_contract_req = test_client
+        .deploy_contract(ss_contract.byte_code(), None)
+        .await?;
+    let caller_contract = CallerContract::default();
+    let deploy_caller_contract_req = test_client
+        .deploy_contract(caller_contract.byte_code(), None)
+        .await?;
+
+    test_client.send_publish_batch_request().await;
+
+    let ss_contract_address = deploy_ss_contract_req
+        .get_receipt()
+        .await?
+        .contract_address
+        .unwrap();
+    let caller_contract_address = deploy_caller_contract_req
+        .get_receipt()
+        .await?
+        .contract_address
+        .unwrap();
+
+    let call_set_value_req = test_client
+        .contract_transaction(
+            caller_contract_address,
+            caller_contract.call_set_call_data(ss_contract_address, 3),
+            None,
+        )
+        .await;
+    test_client.send_publish_batch_request().await;
+    let receipt = call_set_value_req.get_receipt().await?;
+    let tx_hash = receipt.transaction_hash;
+    let block_number = receipt.block_number.unwrap();
+
+    // prestate of every account touched by the transaction
+    let prestate_opts = GethDebugTracingOptions::default().with_tracer(
+        GethDebugTracerType::BuiltInTracer(GethDebugBuiltInTracerType::PreStateTracer),
+    );
+    let GethTrace::PreStateTracer(PreStateFrame::Default(prestate)) = test_client
+        .debug_trace_transaction(tx_hash, Some(prestate_opts.clone()))
+        .await
+    else {
+        panic!("Expected a prestate frame");
+    };
+    assert!(prestate.0.contains_key(&test_client.from_addr));
+    assert!(prestate.0.contains_key(&caller_contract_address));
+    // the stored value is still unset before the transaction
+    let ss_prestate = prestate.0.get(&ss_contract_address).unwrap();
+    assert!(ss_prestate.code.is_some());
+    assert!(ss_prestate
+        .storage
+        .values()
+        .all(|value| *value == B256::ZERO));
+
+    let diff_opts = GethDebugTracingOptions {
+        tracer: Some(GethDebugTracerType::BuiltInTracer(
+            GethDebugBuiltInTracerType::PreStateTracer,
+        )),
+        tracer_config: GethDebugTracerConfig(json!({ "diffMode": true })),
+        ..Default::default()
+    };
+    let GethTrace::PreStateTracer(PreStateFrame::Diff(diff)) = test_client
+        .debug_trace_transaction(tx_hash, Some(diff_opts))
+        .await
+    else {
+        panic!("Expected a prestate diff frame");
+    };
+    let ss_post = diff.post.get(&ss_contract_address).unwrap();
+    assert_eq!(
+        ss_post.storage.values().collect::<Vec<_>>(),
+        vec![&B256::with_last_byte(3)]
+    );
+    let sender_pre = diff.pre.get(&test_client.from_addr).unwrap();
+    let sender_post = diff.post.get(&test_client.from_addr).unwrap();
+    assert_eq!(sender_post.nonce, sender_pre.nonce.map(|nonce| nonce + 1));
+
+    // the prestate tracer works for whole blocks as well
+    let block_traces = test_client
+        .debug_trace_block_by_number(BlockNumberOrTag::Number(block_number), Some(prestate_opts))
+        .await;
+    assert!(block_traces
+        .iter()
+        .all(|trace| matches!(trace, GethTrace::PreStateTracer(_))));
+
+    // flat call traces match the parity traces of the transaction
+    let flat_call_opts = GethDebugTracingOptions::default()
+        .with_tracer(GethDebugTracerType::JsTracer(FLAT_CALL_TRACER.to_string()));
+    let GethTrace::JS(flat_calls) = test_client
+        .debug_trace_transaction(tx_hash, Some(flat_call_opts))
+        .await
+    else {
+        panic!("Expected flat call traces");
+    };
+    let flat_calls = serde_json::from_value::<Vec<LocalizedTransactionTrace>>(flat_calls)?;
+    assert_eq!(flat_calls.len(), 2);
+    assert_eq!(
+        Some(flat_calls),
+        test_client.trace_transaction(tx_hash).await
+    );
+
+    // struct logger without stack but with memory
+    let struct_log_opts = GethDebugTracingOptions {
+        config: GethDefaultTracingOptions {
+            disable_stack: Some(true),
+            enable_memory: Some(true),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let GethTrace::Default(frame) = test_client
+        .debug_trace_transaction(tx_hash, Some(struct_log_opts))
+        .await
+    else {
+        panic!("Expected struct logs");
+    };
+    assert!(!frame.failed);
+    assert!(!frame.struct_logs.is_empty());
+    assert!(frame.struct_logs.iter().all(|log| log.stack.is_none()));
+    assert!(frame.struct_logs.iter().any(|log| log.memory.is_some()));
+
+    let GethTrace::Default(frame) = test_client.debug_trace_transaction(tx_hash, None).await else {
+        panic!("Expected struct logs");
+    };
+    assert!(frame.struct_logs.iter().all(|log| log.stack.is_some()));
+    assert!(frame.struct_logs.iter().all(|log| log.memory.is_none()));
+
+    rollup_task.abort();
+    Ok(())
+}
@@ -467,3 +467,58 @@ async fn test_same_nonce_tx_replacement() {
 
     seq_task.abort();
 }
+
+/// txpool_* methods should reflect the pending and queued transactions of the mempool.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_txpool_status_and_content() {
+    // citrea::initialize_logging(tracing::Level::INFO);
+
+    let db_dir = tempdir_with_children(&["DA", "sequencer", "full-node"]);
+    let da_db_dir = db_dir.path().join("DA").to_path_buf();
+    let sequencer_db_dir = db_dir.path().join("sequencer").to_path_buf();
+    let (seq_task, test_client) = initialize_test(sequencer_db_dir, da_db_dir).await;
+
+    let addr = Address::from_str("0x70997970C51812dc3A010C7d01b50e0d17dc79C8").unwrap();
+
+    let status = test_client.txpool_status().await;
+    assert_eq!(status.pending, 0);
+    assert_eq!(status.queued, 0);
+
+    // nonce 0 and 1 are executable, nonce 3 has a gap
+    let tx_hash = test_client
+        .send_eth(addr, None, None, Some(0), 0u128)
+        .await
+        .unwrap();
+    let _tx_hash1 = test_client
+        .send_eth(addr, None, None, Some(1), 0u128)
+        .await
+        .unwrap();
+    let tx_hash3 = test_client
+        .send_eth(addr, None, None, Some(3), 0u128)
+        .await
+        .unwrap();
+
+    let status = test_client.txpool_status().await;
+    assert_eq!(status.pending, 2);
+    assert_eq!(status.queued, 1);
+
+    let content = test_client.txpool_content_from(test_client.from_addr).await;
+    assert_eq!(content.pending.len(), 2);
+    assert_eq!(content.pending["0"].hash, *tx_hash.tx_hash());
+    assert_eq!(content.queued.len(), 1);
+    assert_eq!(content.queued["3"].hash, *tx_hash3.tx_hash());
+
+    // the recipient has not sent anything
+    let content = test_client.txpool_content_from(addr).await;
+    assert!(content.pending.is_empty());
+    assert!(content.queued.is_empty());
+
+    test_client.send_publish_batch_request().await;
+    wait_for_l2_block(&test_client, 1, None).await;
+
+    let status = test_client.txpool_status().await;
+    assert_eq!(status.pending, 0);
+    assert_eq!(status.queued, 1);
+
+    seq_task.abort();
+}
@@ -16,7 +16,12 @@ use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
 use jsonrpsee::rpc_params;
 use jsonrpsee::ws_client::{PingConfig, WsClient, WsClientBuilder};
 use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, TxHash, TxKind, B256, U256, U64};
+use reth_rpc_types::trace::filter::TraceFilter;
 use reth_rpc_types::trace::geth::{GethDebugTracingOptions, GethTrace};
+use reth_rpc_types::trace::parity::{
+    LocalizedTransactionTrace, TraceResultsWithTransactionHash, TraceType,
+};
+use reth_rpc_types::txpool::{TxpoolContentFrom, TxpoolStatus};
 use reth_rpc_types::RichBlock;
 use sov_ledger_rpc::{HexHash, LedgerRpcClient};
 use sov_rollup_interface::rpc::{
@@ -424,6 +429,20 @@ impl TestClient {
             .unwrap()
     }
 
+    pub(crate) async fn txpool_status(&self) -> TxpoolStatus {
+        self.http_client
+            .request("txpool_status", rpc_params![])
+            .await
+            .unwrap()
+    }
+
+    pub(crate) async fn txpool_content_from(&self, from: Address) -> TxpoolContentFrom {
+        self.http_client
+            .request("txpool_contentFrom", rpc_params![from])
+            .await
+            .unwrap()
+    }
+
     pub(crate) async fn eth_get_block_receipts(
         &self,
         block_number_or_hash: BlockId,
@@ -657,6 +676,47 @@ impl TestClient {
         traces.into_iter().flatten().collect()
     }
 
+    pub(crate) async fn trace_block(
+        &self,
+        block_number: BlockNumberOrTag,
+    ) -> Vec<LocalizedTransactionTrace> {
+        self.http_client
+            .request("trace_block", rpc_params![block_number])
+            .await
+            .unwrap()
+    }
+
+    pub(crate) async fn trace_transaction(
+        &self,
+        tx_hash: TxHash,
+    ) -> Option<Vec<LocalizedTransactionTrace>> {
+        self.http_client
+            .request("trace_transaction", rpc_params![tx_hash])
+            .await
+            .unwrap()
+    }
+
+    pub(crate) async fn trace_filter(&self, filter: TraceFilter) -> Vec<LocalizedTransactionTrace> {
+        self.http_client
+            .request("trace_filter", rpc_params![filter])
+            .await
+            .unwrap()
+    }
+
+    pub(crate) async fn trace_replay_block_transactions(
+        &self,
+        block_number: BlockNumberOrTag,
+        trace_types: Vec<TraceType>,
+    ) -> Vec<TraceResultsWithTransactionHash> {
+        self.http_client
+            .request(
+                "trace_replayBlockTransactions",
+                rpc_params![block_number, trace_types],
+            )
+            .await
+            .unwrap()
+    }
+
     pub(crate) async fn subscribe_new_heads(&self) -> mpsc::Receiver<RichBlock> {
         let (tx, rx) = mpsc::channel();
         let mut subscription = self
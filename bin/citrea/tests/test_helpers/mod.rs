@@ -347,7 +347,7 @@ fn extract_da_data(
             let data = DaData::try_from_slice(tx.full_data());
             if let Ok(DaData::SequencerCommitment(seq_com)) = data {
                 sequencer_commitments.push(seq_com);
-            } else if let Ok(DaData::ZKProof(proof)) = data {
+            } else if let Ok(DaData::ZKProof(proof, _)) = data {
                 zk_proofs.push(proof);
             } else {
                 tracing::warn!(
@@ -7,11 +7,11 @@ use std::sync::Arc;
 use anyhow::{anyhow, Context as _};
 use borsh::{BorshDeserialize, BorshSerialize};
 use citrea_common::cache::L1BlockCache;
-use citrea_common::da::get_da_block_at_height;
+use citrea_common::da::{expect_l1_rollback, get_da_block_at_height, sync_l1, L1SyncEvent};
 use citrea_common::utils::merge_state_diffs;
 use citrea_common::BatchProverConfig;
 use citrea_primitives::compression::compress_blob;
-use citrea_primitives::forks::FORKS;
+use citrea_primitives::forks::{blob_compression, FORKS};
 use citrea_primitives::MAX_TXBODY_SIZE;
 use rand::Rng;
 use serde::de::DeserializeOwned;
@@ -28,7 +28,7 @@ use sov_rollup_interface::zk::ZkvmHost;
 use sov_stf_runner::ProverService;
 use tokio::select;
 use tokio::sync::{mpsc, Mutex};
-use tokio::time::{sleep, Duration};
+use tokio::time::Duration;
 use tokio_util::sync::CancellationToken;
 use tracing::{error, info, warn};
 
@@ -149,8 +149,12 @@ where
                     return;
                 }
                 _ = &mut l1_sync_worker => {},
-                Some(l1_block) = l1_rx.recv() => {
-                    self.pending_l1_blocks.push_back(l1_block);
+                Some(event) = l1_rx.recv() => match event {
+                    L1SyncEvent::Block(l1_block) => self.pending_l1_blocks.push_back(l1_block),
+                    L1SyncEvent::Reorg { fork_height, orphaned_hashes } => expect_l1_rollback(
+                        fork_height,
+                        self.handle_l1_reorg(fork_height, &orphaned_hashes),
+                    ),
                 },
                 _ = interval.tick() => {
                     if let Err(e) = self.process_l1_block().await {
@@ -161,6 +165,21 @@ where
         }
     }
 
+    /// Forgets the pending L1 blocks and the batch proofs of the L1 blocks orphaned by a reorg.
+    /// Commitments of the new canonical chain are proven once its blocks are processed.
+    fn handle_l1_reorg(
+        &mut self,
+        fork_height: u64,
+        orphaned_hashes: &[[u8; 32]],
+    ) -> anyhow::Result<()> {
+        self.pending_l1_blocks
+            .retain(|l1_block| l1_block.header().height() <= fork_height);
+
+        self.ledger_db
+            .rollback_l1_blocks(fork_height, orphaned_hashes)?;
+        Ok(())
+    }
+
     async fn process_l1_block(&mut self) -> Result<(), anyhow::Error> {
         while !self.pending_l1_blocks.is_empty() {
             let l1_block = self
@@ -277,7 +296,15 @@ where
 
     async fn check_and_recover_ongoing_proving_sessions(&self) -> Result<(), anyhow::Error> {
         let prover_service = self.prover_service.as_ref();
-        let txs_and_proofs = prover_service.recover_and_submit_proving_sessions().await?;
+        // Recovered sessions don't tell which commitments they prove, assume the fork of the head
+        let head_l2_height = self
+            .ledger_db
+            .get_head_soft_confirmation()?
+            .map_or(0, |(l2_height, _)| l2_height.0);
+        let current_spec = fork_from_block_number(FORKS, head_l2_height).spec_id;
+        let txs_and_proofs = prover_service
+            .recover_and_submit_proving_sessions(current_spec)
+            .await?;
 
         extract_and_store_proof::<DB, Da, Vm, StateRoot>(
             self.ledger_db.clone(),
@@ -290,57 +317,6 @@ where
     }
 }
 
-async fn sync_l1<Da>(
-    start_l1_height: u64,
-    da_service: Arc<Da>,
-    sender: mpsc::Sender<Da::FilteredBlock>,
-    l1_block_cache: Arc<Mutex<L1BlockCache<Da>>>,
-) where
-    Da: DaService,
-{
-    let mut l1_height = start_l1_height;
-    info!("Starting to sync from L1 height {}", l1_height);
-
-    'block_sync: loop {
-        // TODO: for a node, the da block at slot_height might not have been finalized yet
-        // should wait for it to be finalized
-        let last_finalized_l1_block_header =
-            match da_service.get_last_finalized_block_header().await {
-                Ok(header) => header,
-                Err(e) => {
-                    error!("Could not fetch last finalized L1 block header: {}", e);
-                    sleep(Duration::from_secs(2)).await;
-                    continue;
-                }
-            };
-
-        let new_l1_height = last_finalized_l1_block_header.height();
-
-        for block_number in l1_height + 1..=new_l1_height {
-            let l1_block =
-                match get_da_block_at_height(&da_service, block_number, l1_block_cache.clone())
-                    .await
-                {
-                    Ok(block) => block,
-                    Err(e) => {
-                        error!("Could not fetch last finalized L1 block: {}", e);
-                        sleep(Duration::from_secs(2)).await;
-                        continue 'block_sync;
-                    }
-                };
-            if block_number > l1_height {
-                l1_height = block_number;
-                if let Err(e) = sender.send(l1_block).await {
-                    error!("Could not notify about L1 block: {}", e);
-                    continue 'block_sync;
-                }
-            }
-        }
-
-        sleep(Duration::from_secs(2)).await;
-    }
-}
-
 pub(crate) async fn get_batch_proof_circuit_input_from_commitments<
     'txs,
     Da: DaService,
@@ -464,14 +440,17 @@ pub(crate) fn break_sequencer_commitments_into_groups<DB: BatchProverLedgerOps>(
             sequencer_commitment_state_diff.clone(),
         );
 
-        let compressed_state_diff = compress_blob(&borsh::to_vec(&cumulative_state_diff)?);
+        let commitment_spec =
+            fork_from_block_number(FORKS, sequencer_commitment.l2_end_block_number).spec_id;
+
+        let compressed_state_diff = compress_blob(
+            &borsh::to_vec(&cumulative_state_diff)?,
+            blob_compression(commitment_spec),
+        );
 
         // Threshold is checked by comparing compressed state diff size as the data will be compressed before it is written on DA
         let state_diff_threshold_reached = compressed_state_diff.len() > MAX_TXBODY_SIZE;
 
-        let commitment_spec =
-            fork_from_block_number(FORKS, sequencer_commitment.l2_end_block_number).spec_id;
-
         if commitment_spec != current_spec || state_diff_threshold_reached {
             result_range.push(range);
             // Reset the cumulative state diff to be equal to the current commitment state diff
@@ -254,7 +254,7 @@ where
     // Prove all proofs in parallel
     let proofs = prover_service.prove(elf).await?;
 
-    let txs_and_proofs = prover_service.submit_proofs(proofs).await?;
+    let txs_and_proofs = prover_service.submit_proofs(proofs, current_spec).await?;
 
     extract_and_store_proof::<DB, Da, Vm, StateRoot>(
         ledger.clone(),
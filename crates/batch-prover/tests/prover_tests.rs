@@ -8,6 +8,7 @@ use sov_db::rocks_db_config::RocksdbConfig;
 use sov_mock_da::{MockAddress, MockBlockHeader, MockDaService, MockDaSpec, MockHash};
 use sov_mock_zkvm::MockZkvm;
 use sov_rollup_interface::da::Time;
+use sov_rollup_interface::spec::SpecId;
 use sov_rollup_interface::zk::{BatchProofCircuitInput, Proof, ZkvmHost};
 use sov_stf_runner::mock::MockStf;
 use sov_stf_runner::ProverService;
@@ -46,7 +47,10 @@ async fn test_successful_prover_execution() {
     let header = extract_output_header(&proofs[0]);
     assert_eq!(header.hash, header_hash);
 
-    let txs = prover_service.submit_proofs(proofs).await.unwrap();
+    let txs = prover_service
+        .submit_proofs(proofs, SpecId::Genesis)
+        .await
+        .unwrap();
     assert_eq!(txs.len(), 1);
 }
 
@@ -98,7 +102,10 @@ async fn test_parallel_proofs_equal_to_limit() {
     let header_2 = extract_output_header(&proofs[1]);
     assert_eq!(header_2.hash, header_hash_2);
 
-    let txs_and_proofs = prover_service.submit_proofs(proofs).await.unwrap();
+    let txs_and_proofs = prover_service
+        .submit_proofs(proofs, SpecId::Genesis)
+        .await
+        .unwrap();
     assert_eq!(txs_and_proofs.len(), 2);
 }
 
@@ -196,7 +203,10 @@ async fn test_parallel_proofs_higher_than_limit() {
     let header_5 = extract_output_header(&proofs[4]);
     assert_eq!(header_5.hash, header_hash_5);
 
-    let txs_and_proofs = prover_service.submit_proofs(proofs).await.unwrap();
+    let txs_and_proofs = prover_service
+        .submit_proofs(proofs, SpecId::Genesis)
+        .await
+        .unwrap();
     assert_eq!(txs_and_proofs.len(), 5);
 }
 
@@ -242,7 +252,10 @@ async fn test_multiple_parallel_proof_run() {
     let proofs = rx.await.unwrap();
     assert_eq!(proofs.len(), 2);
 
-    let txs_and_proofs = prover_service.submit_proofs(proofs).await.unwrap();
+    let txs_and_proofs = prover_service
+        .submit_proofs(proofs, SpecId::Genesis)
+        .await
+        .unwrap();
     assert_eq!(txs_and_proofs.len(), 2);
 
     // 1st proof
@@ -284,7 +297,10 @@ async fn test_multiple_parallel_proof_run() {
     let proofs = rx.await.unwrap();
     assert_eq!(proofs.len(), 3);
 
-    let txs_and_proofs = prover_service.submit_proofs(proofs).await.unwrap();
+    let txs_and_proofs = prover_service
+        .submit_proofs(proofs, SpecId::Genesis)
+        .await
+        .unwrap();
     assert_eq!(txs_and_proofs.len(), 3);
 }
 
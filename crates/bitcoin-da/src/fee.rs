@@ -6,35 +6,381 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
 use bitcoin::{Amount, Network, Sequence, Txid};
 use bitcoincore_rpc::json::{
     BumpFeeResult, CreateRawTransactionInput, WalletCreateFundedPsbtOptions,
 };
 use bitcoincore_rpc::{Client, RpcApi};
-use tracing::{debug, instrument, trace, warn};
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument, warn};
 
 use crate::monitoring::{MonitoredTx, MonitoredTxKind};
+use crate::network::NetworkProfile;
 use crate::spec::utxo::UTXO;
 
-const MEMPOOL_SPACE_URL: &str = "https://mempool.space/";
 const MEMPOOL_SPACE_RECOMMENDED_FEE_ENDPOINT: &str = "api/v1/fees/recommended";
 
 pub type Psbt = String;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum BumpFeeMethod {
     Cpfp,
     Rbf,
 }
 
+/// Policy of automatic fee bumps for DA txs which are pending for too long.
+///
+/// Only the last tx of the monitored chain is bumped, which is enough for CPFP to pay for
+/// all of its pending ancestors. RBF can only replace CPFP txs, so a pending reveal tx is
+/// always bumped via CPFP first.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct FeeBumpPolicy {
+    /// Bump a tx once it is pending for this many blocks.
+    pub max_pending_blocks: Option<u64>,
+    /// Bump a tx once it is pending for this many seconds.
+    pub max_pending_secs: Option<u64>,
+    /// Multiplier applied to the estimated fee rate to get the bump fee rate.
+    pub fee_rate_multiplier: f64,
+    /// Fee rate ceiling in sat/vB. Txs already paying this much are not bumped.
+    pub max_fee_rate: u64,
+    pub method: BumpFeeMethod,
+}
+
+impl FeeBumpPolicy {
+    /// Whether a tx first seen at `initial_height` and `initial_broadcast`
+    /// waited long enough to be bumped.
+    pub fn is_stuck(
+        &self,
+        initial_height: u64,
+        initial_broadcast: u64,
+        current_height: u64,
+        now: u64,
+    ) -> bool {
+        let blocks_exceeded = self
+            .max_pending_blocks
+            .is_some_and(|max| current_height.saturating_sub(initial_height) >= max);
+        let secs_exceeded = self
+            .max_pending_secs
+            .is_some_and(|max| now.saturating_sub(initial_broadcast) >= max);
+        blocks_exceeded || secs_exceeded
+    }
+
+    /// Fee rate in sat/vB to bump a tx paying `current_fee_rate` with,
+    /// given the estimated fee rate. Returns `None` if the tx pays enough already.
+    pub fn bump_fee_rate(&self, estimated_fee_rate: u64, current_fee_rate: f64) -> Option<f64> {
+        let fee_rate =
+            (estimated_fee_rate as f64 * self.fee_rate_multiplier).min(self.max_fee_rate as f64);
+        (fee_rate > current_fee_rate).then_some(fee_rate)
+    }
+}
+
+/// Source of fee rate estimates in sat/vB.
+#[async_trait]
+pub trait FeeEstimator: std::fmt::Debug + Send + Sync {
+    async fn estimate_fee_rate(&self) -> Result<u64>;
+}
+
+/// Estimates fee rates with the `estimatesmartfee` RPC of bitcoind.
+#[derive(Debug)]
+pub struct BitcoindFeeEstimator {
+    client: Arc<Client>,
+    conf_target: u16,
+}
+
+impl BitcoindFeeEstimator {
+    pub fn new(client: Arc<Client>, conf_target: u16) -> Self {
+        Self {
+            client,
+            conf_target,
+        }
+    }
+}
+
+#[async_trait]
+impl FeeEstimator for BitcoindFeeEstimator {
+    async fn estimate_fee_rate(&self) -> Result<u64> {
+        let fee_rate = self
+            .client
+            .estimate_smart_fee(self.conf_target, None)
+            .await?
+            .fee_rate
+            .context("bitcoind has no fee rate estimate")?;
+        // sat/kvB to sat/vB
+        Ok(fee_rate.to_sat() / 1000)
+    }
+}
+
+/// Fee rate field of the mempool.space recommended fees endpoint.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MempoolSpaceFeePriority {
+    #[default]
+    Fastest,
+    HalfHour,
+    Hour,
+    Economy,
+    Minimum,
+}
+
+impl MempoolSpaceFeePriority {
+    fn field(&self) -> &'static str {
+        match self {
+            Self::Fastest => "fastestFee",
+            Self::HalfHour => "halfHourFee",
+            Self::Hour => "hourFee",
+            Self::Economy => "economyFee",
+            Self::Minimum => "minimumFee",
+        }
+    }
+}
+
+/// Estimates fee rates with the recommended fees endpoint of a mempool.space compatible API.
+#[derive(Debug)]
+pub struct MempoolSpaceFeeEstimator {
+    base_url: String,
+    priority: MempoolSpaceFeePriority,
+}
+
+impl MempoolSpaceFeeEstimator {
+    /// `base_url` is the URL the `api/v1/fees/recommended` path is appended to,
+    /// e.g. `https://mempool.space/testnet4/`.
+    pub fn new(base_url: String, priority: MempoolSpaceFeePriority) -> Self {
+        let base_url = if base_url.ends_with('/') {
+            base_url
+        } else {
+            format!("{}/", base_url)
+        };
+        Self { base_url, priority }
+    }
+}
+
+#[async_trait]
+impl FeeEstimator for MempoolSpaceFeeEstimator {
+    async fn estimate_fee_rate(&self) -> Result<u64> {
+        let url = format!(
+            "{}{}",
+            self.base_url, MEMPOOL_SPACE_RECOMMENDED_FEE_ENDPOINT
+        );
+        reqwest::get(url)
+            .await?
+            .json::<serde_json::Value>()
+            .await?
+            .get(self.priority.field())
+            .and_then(|fee| fee.as_u64())
+            .context("Failed to get fee rate from mempool space")
+    }
+}
+
+/// Always returns the same fee rate.
+#[derive(Debug)]
+pub struct FixedFeeEstimator {
+    fee_rate: u64,
+}
+
+impl FixedFeeEstimator {
+    pub fn new(fee_rate: u64) -> Self {
+        Self { fee_rate }
+    }
+}
+
+#[async_trait]
+impl FeeEstimator for FixedFeeEstimator {
+    async fn estimate_fee_rate(&self) -> Result<u64> {
+        Ok(self.fee_rate)
+    }
+}
+
+/// Returns the median of the sources which returned an estimate.
+/// For an even number of estimates, the average of the two middle ones, rounded up, is returned.
+#[derive(Debug)]
+pub struct MedianFeeEstimator {
+    sources: Vec<Box<dyn FeeEstimator>>,
+}
+
+impl MedianFeeEstimator {
+    pub fn new(sources: Vec<Box<dyn FeeEstimator>>) -> Self {
+        Self { sources }
+    }
+}
+
+#[async_trait]
+impl FeeEstimator for MedianFeeEstimator {
+    async fn estimate_fee_rate(&self) -> Result<u64> {
+        let estimates =
+            join_all(self.sources.iter().map(|source| source.estimate_fee_rate())).await;
+        let mut fee_rates = Vec::with_capacity(estimates.len());
+        for (source, estimate) in self.sources.iter().zip(estimates) {
+            match estimate {
+                Ok(fee_rate) => fee_rates.push(fee_rate),
+                Err(e) => warn!(?e, ?source, "Fee estimator failed"),
+            }
+        }
+        if fee_rates.is_empty() {
+            bail!("All fee estimators failed");
+        }
+
+        fee_rates.sort_unstable();
+        let mid = fee_rates.len() / 2;
+        if fee_rates.len() % 2 == 0 {
+            Ok((fee_rates[mid - 1] + fee_rates[mid]).div_ceil(2))
+        } else {
+            Ok(fee_rates[mid])
+        }
+    }
+}
+
+/// Clamps the estimates of a source between a floor and a ceiling.
+#[derive(Debug)]
+pub struct ClampedFeeEstimator {
+    inner: Box<dyn FeeEstimator>,
+    min_fee_rate: Option<u64>,
+    max_fee_rate: Option<u64>,
+}
+
+#[async_trait]
+impl FeeEstimator for ClampedFeeEstimator {
+    async fn estimate_fee_rate(&self) -> Result<u64> {
+        let fee_rate = self.inner.estimate_fee_rate().await?;
+        let fee_rate = self.min_fee_rate.map_or(fee_rate, |min| fee_rate.max(min));
+        Ok(self.max_fee_rate.map_or(fee_rate, |max| fee_rate.min(max)))
+    }
+}
+
+/// Kind of a fee estimate source.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FeeEstimatorKind {
+    /// `estimatesmartfee` of the connected bitcoind.
+    Bitcoind { conf_target: u16 },
+    /// A mempool.space compatible API, defaults to the public instance of the network.
+    MempoolSpace {
+        base_url: Option<String>,
+        #[serde(default)]
+        priority: MempoolSpaceFeePriority,
+    },
+    /// A fixed fee rate in sat/vB.
+    Fixed { fee_rate: u64 },
+    /// The median of the sources.
+    Median { sources: Vec<FeeEstimatorConfig> },
+}
+
+/// Configuration of a fee estimate source.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeEstimatorConfig {
+    #[serde(flatten)]
+    pub kind: FeeEstimatorKind,
+    /// Floor of the estimates of this source in sat/vB.
+    pub min_fee_rate: Option<u64>,
+    /// Ceiling of the estimates of this source in sat/vB.
+    pub max_fee_rate: Option<u64>,
+}
+
+impl FeeEstimatorConfig {
+    fn unclamped(kind: FeeEstimatorKind) -> Self {
+        Self {
+            kind,
+            min_fee_rate: None,
+            max_fee_rate: None,
+        }
+    }
+
+    /// Fee estimation used when none is configured.
+    ///
+    /// Networks with a mempool.space instance take the median of it and bitcoind,
+    /// others only use bitcoind. Regtest uses a fixed fee rate of 1 sat/vB.
+    pub fn default_for_profile(network_profile: &NetworkProfile) -> Self {
+        let bitcoind = Self::unclamped(FeeEstimatorKind::Bitcoind { conf_target: 1 });
+        let kind = match network_profile {
+            NetworkProfile::Regtest => {
+                return Self::unclamped(FeeEstimatorKind::Fixed { fee_rate: 1 })
+            }
+            _ if network_profile.mempool_space_url().is_some() => FeeEstimatorKind::Median {
+                sources: vec![
+                    Self::unclamped(FeeEstimatorKind::MempoolSpace {
+                        base_url: None,
+                        priority: MempoolSpaceFeePriority::Fastest,
+                    }),
+                    bitcoind,
+                ],
+            },
+            _ => bitcoind.kind,
+        };
+        Self {
+            kind,
+            min_fee_rate: Some(1),
+            max_fee_rate: None,
+        }
+    }
+
+    /// Builds the estimator described by the config.
+    pub fn build(
+        &self,
+        client: &Arc<Client>,
+        network_profile: &NetworkProfile,
+    ) -> Result<Box<dyn FeeEstimator>> {
+        if let (Some(min), Some(max)) = (self.min_fee_rate, self.max_fee_rate) {
+            if min > max {
+                bail!("Fee rate floor {min} is above the ceiling {max}");
+            }
+        }
+
+        let estimator: Box<dyn FeeEstimator> = match &self.kind {
+            FeeEstimatorKind::Bitcoind { conf_target } => {
+                Box::new(BitcoindFeeEstimator::new(client.clone(), *conf_target))
+            }
+            FeeEstimatorKind::MempoolSpace { base_url, priority } => {
+                let base_url = base_url
+                    .clone()
+                    .or_else(|| network_profile.mempool_space_url())
+                    .with_context(|| {
+                        format!("No mempool.space URL for network profile {network_profile:?}")
+                    })?;
+                Box::new(MempoolSpaceFeeEstimator::new(base_url, *priority))
+            }
+            FeeEstimatorKind::Fixed { fee_rate } => Box::new(FixedFeeEstimator::new(*fee_rate)),
+            FeeEstimatorKind::Median { sources } => Box::new(MedianFeeEstimator::new(
+                sources
+                    .iter()
+                    .map(|source| source.build(client, network_profile))
+                    .collect::<Result<_>>()?,
+            )),
+        };
+
+        if self.min_fee_rate.is_none() && self.max_fee_rate.is_none() {
+            return Ok(estimator);
+        }
+        Ok(Box::new(ClampedFeeEstimator {
+            inner: estimator,
+            min_fee_rate: self.min_fee_rate,
+            max_fee_rate: self.max_fee_rate,
+        }))
+    }
+}
+
 #[derive(Debug)]
 pub struct FeeService {
     client: Arc<Client>,
     network: Network,
+    estimator: Box<dyn FeeEstimator>,
 }
 
 impl FeeService {
-    pub fn new(client: Arc<Client>, network: bitcoin::Network) -> Self {
-        Self { client, network }
+    pub fn new(
+        client: Arc<Client>,
+        network_profile: &NetworkProfile,
+        estimator: Option<FeeEstimatorConfig>,
+    ) -> Result<Self> {
+        let estimator = estimator
+            .unwrap_or_else(|| FeeEstimatorConfig::default_for_profile(network_profile))
+            .build(&client, network_profile)?;
+        Ok(Self {
+            client,
+            network: network_profile.network(),
+            estimator,
+        })
     }
 
     #[instrument(level = "trace", skip_all, ret)]
@@ -42,9 +388,10 @@ impl FeeService {
         match self.get_fee_rate_as_sat_vb().await {
             Ok(fee) => Ok(fee),
             Err(e) => {
-                if self.network == bitcoin::Network::Regtest
-                    || self.network == bitcoin::Network::Testnet
-                {
+                if matches!(
+                    self.network,
+                    Network::Regtest | Network::Testnet | Network::Signet
+                ) {
                     Ok(1)
                 } else {
                     Err(e)
@@ -55,18 +402,9 @@ impl FeeService {
 
     #[instrument(level = "trace", skip_all, ret)]
     pub async fn get_fee_rate_as_sat_vb(&self) -> Result<u64> {
-        // If network is regtest or signet, mempool space is not available
-        let smart_fee = match get_fee_rate_from_mempool_space(self.network).await {
-            Ok(fee_rate) => fee_rate,
-            Err(e) => {
-                tracing::error!(?e, "Failed to get fee rate from mempool.space");
-                self.client.estimate_smart_fee(1, None).await?.fee_rate
-            }
-        };
-        let sat_vkb = smart_fee.map_or(1000, |rate| rate.to_sat());
-
-        tracing::debug!("Fee rate: {} sat/vb", sat_vkb / 1000);
-        Ok(sat_vkb / 1000)
+        let fee_rate = self.estimator.estimate_fee_rate().await?;
+        tracing::debug!("Fee rate: {} sat/vb", fee_rate);
+        Ok(fee_rate)
     }
 
     /// Bump TX fee via cpfp.
@@ -143,60 +481,205 @@ impl FeeService {
     }
 }
 
-pub(crate) async fn get_fee_rate_from_mempool_space(
-    network: bitcoin::Network,
-) -> Result<Option<Amount>> {
-    let url = match network {
-        bitcoin::Network::Bitcoin => format!(
-            // Mainnet
-            "{}{}",
-            MEMPOOL_SPACE_URL, MEMPOOL_SPACE_RECOMMENDED_FEE_ENDPOINT
-        ),
-        bitcoin::Network::Testnet => format!(
-            "{}testnet4/{}",
-            MEMPOOL_SPACE_URL, MEMPOOL_SPACE_RECOMMENDED_FEE_ENDPOINT
-        ),
-        _ => {
-            trace!("Unsupported network for mempool space fee estimation");
-            return Ok(None);
-        }
+#[cfg(test)]
+mod tests {
+    use anyhow::{bail, Result};
+    use async_trait::async_trait;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio::task::JoinHandle;
+
+    use super::{
+        BumpFeeMethod, ClampedFeeEstimator, FeeBumpPolicy, FeeEstimator, FeeEstimatorConfig,
+        FeeEstimatorKind, FixedFeeEstimator, MedianFeeEstimator, MempoolSpaceFeeEstimator,
+        MempoolSpaceFeePriority, MEMPOOL_SPACE_RECOMMENDED_FEE_ENDPOINT,
     };
-    let fee_rate = reqwest::get(url)
-        .await?
-        .json::<serde_json::Value>()
-        .await?
-        .get("fastestFee")
-        .and_then(|fee| fee.as_u64())
-        .map(|fee| Amount::from_sat(fee * 1000)) // multiply by 1000 to convert to sat/vkb
-        .context("Failed to get fee rate from mempool space")?;
+    use crate::network::NetworkProfile;
 
-    Ok(Some(fee_rate))
-}
+    #[derive(Debug)]
+    struct FailingFeeEstimator;
 
-#[cfg(test)]
-mod tests {
+    #[async_trait]
+    impl FeeEstimator for FailingFeeEstimator {
+        async fn estimate_fee_rate(&self) -> Result<u64> {
+            bail!("Estimator is down")
+        }
+    }
 
-    use super::get_fee_rate_from_mempool_space;
+    fn policy() -> FeeBumpPolicy {
+        FeeBumpPolicy {
+            max_pending_blocks: Some(3),
+            max_pending_secs: Some(3600),
+            fee_rate_multiplier: 1.5,
+            max_fee_rate: 20,
+            method: BumpFeeMethod::Cpfp,
+        }
+    }
+
+    #[test]
+    fn test_fee_bump_policy_is_stuck() {
+        let policy = policy();
+        assert!(!policy.is_stuck(100, 1000, 102, 1000 + 3599));
+        assert!(policy.is_stuck(100, 1000, 103, 1000));
+        assert!(policy.is_stuck(100, 1000, 100, 1000 + 3600));
+
+        let blocks_only = FeeBumpPolicy {
+            max_pending_secs: None,
+            ..policy
+        };
+        assert!(!blocks_only.is_stuck(100, 1000, 100, u64::MAX));
+    }
+
+    #[test]
+    fn test_fee_bump_policy_fee_rate() {
+        let policy = policy();
+        assert_eq!(policy.bump_fee_rate(10, 2.0), Some(15.0));
+        // Capped at the ceiling
+        assert_eq!(policy.bump_fee_rate(100, 2.0), Some(20.0));
+        // Already paying the ceiling
+        assert_eq!(policy.bump_fee_rate(100, 20.0), None);
+    }
 
     #[tokio::test]
-    async fn test_mempool_space_fee_rate() {
-        let _fee_rate = get_fee_rate_from_mempool_space(bitcoin::Network::Bitcoin)
-            .await
-            .unwrap();
-        let _fee_rate = get_fee_rate_from_mempool_space(bitcoin::Network::Testnet)
-            .await
-            .unwrap();
+    async fn test_median_fee_estimator() {
+        let median = MedianFeeEstimator::new(vec![
+            Box::new(FixedFeeEstimator::new(5)),
+            Box::new(FailingFeeEstimator),
+            Box::new(FixedFeeEstimator::new(1)),
+            Box::new(FixedFeeEstimator::new(30)),
+        ]);
+        assert_eq!(median.estimate_fee_rate().await.unwrap(), 5);
+
+        let median = MedianFeeEstimator::new(vec![
+            Box::new(FixedFeeEstimator::new(5)),
+            Box::new(FixedFeeEstimator::new(8)),
+        ]);
+        assert_eq!(median.estimate_fee_rate().await.unwrap(), 7);
+
+        let median = MedianFeeEstimator::new(vec![
+            Box::new(FixedFeeEstimator::new(30)),
+            Box::new(FixedFeeEstimator::new(4)),
+            Box::new(FixedFeeEstimator::new(1)),
+            Box::new(FixedFeeEstimator::new(6)),
+        ]);
+        assert_eq!(median.estimate_fee_rate().await.unwrap(), 5);
+
+        let median = MedianFeeEstimator::new(vec![Box::new(FailingFeeEstimator)]);
+        assert!(median.estimate_fee_rate().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_clamped_fee_estimator() {
+        let clamped = |fee_rate| ClampedFeeEstimator {
+            inner: Box::new(FixedFeeEstimator::new(fee_rate)),
+            min_fee_rate: Some(2),
+            max_fee_rate: Some(50),
+        };
+        assert_eq!(clamped(1).estimate_fee_rate().await.unwrap(), 2);
+        assert_eq!(clamped(10).estimate_fee_rate().await.unwrap(), 10);
+        assert_eq!(clamped(100).estimate_fee_rate().await.unwrap(), 50);
+    }
+
+    #[test]
+    fn test_fee_estimator_config() {
+        let config: FeeEstimatorConfig = serde_json::from_str(
+            r#"{
+                "type": "median",
+                "max_fee_rate": 200,
+                "sources": [
+                    { "type": "bitcoind", "conf_target": 2, "min_fee_rate": 1 },
+                    { "type": "mempool_space", "base_url": "http://localhost:8999/", "priority": "half_hour" },
+                    { "type": "fixed", "fee_rate": 10 }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let FeeEstimatorKind::Median { sources } = &config.kind else {
+            panic!("Expected median estimator");
+        };
+        assert_eq!(config.max_fee_rate, Some(200));
         assert_eq!(
-            None,
-            get_fee_rate_from_mempool_space(bitcoin::Network::Regtest)
-                .await
-                .unwrap()
+            sources[0].kind,
+            FeeEstimatorKind::Bitcoind { conf_target: 2 }
         );
+        assert_eq!(sources[0].min_fee_rate, Some(1));
         assert_eq!(
-            None,
-            get_fee_rate_from_mempool_space(bitcoin::Network::Signet)
+            sources[1].kind,
+            FeeEstimatorKind::MempoolSpace {
+                base_url: Some("http://localhost:8999/".to_string()),
+                priority: MempoolSpaceFeePriority::HalfHour,
+            }
+        );
+        assert_eq!(sources[2].kind, FeeEstimatorKind::Fixed { fee_rate: 10 });
+    }
+
+    /// Answers a single HTTP request with `body`, returning the URL to request and the request.
+    async fn serve_once(body: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let request = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = vec![0u8; 1024];
+            let len = stream.read(&mut request).await.unwrap();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&request[..len]).into_owned()
+        });
+        (url, request)
+    }
+
+    #[tokio::test]
+    async fn test_mempool_space_fee_rate() {
+        const FEES: &str =
+            r#"{"fastestFee":12,"halfHourFee":8,"hourFee":5,"economyFee":2,"minimumFee":1}"#;
+        for (priority, expected) in [
+            (MempoolSpaceFeePriority::Fastest, 12),
+            (MempoolSpaceFeePriority::HalfHour, 8),
+            (MempoolSpaceFeePriority::Hour, 5),
+            (MempoolSpaceFeePriority::Economy, 2),
+            (MempoolSpaceFeePriority::Minimum, 1),
+        ] {
+            let (url, request) = serve_once(FEES).await;
+            let fee_rate = MempoolSpaceFeeEstimator::new(url, priority)
+                .estimate_fee_rate()
+                .await
+                .unwrap();
+            assert_eq!(fee_rate, expected);
+            assert!(request
                 .await
                 .unwrap()
+                .starts_with(&format!("GET /{} ", MEMPOOL_SPACE_RECOMMENDED_FEE_ENDPOINT)));
+        }
+
+        // a base URL without a trailing slash
+        let (url, _) = serve_once(FEES).await;
+        let url = url.trim_end_matches('/').to_string();
+        let fee_rate = MempoolSpaceFeeEstimator::new(url, MempoolSpaceFeePriority::Fastest)
+            .estimate_fee_rate()
+            .await
+            .unwrap();
+        assert_eq!(fee_rate, 12);
+
+        let (url, _) = serve_once("{}").await;
+        assert!(
+            MempoolSpaceFeeEstimator::new(url, MempoolSpaceFeePriority::Fastest)
+                .estimate_fee_rate()
+                .await
+                .is_err()
         );
+
+        for network_profile in [
+            NetworkProfile::Mainnet,
+            NetworkProfile::Testnet4,
+            NetworkProfile::Signet,
+        ] {
+            assert!(network_profile.mempool_space_url().is_some());
+        }
+        assert_eq!(None, NetworkProfile::Regtest.mempool_space_url());
     }
 }
@@ -11,39 +11,82 @@ use bitcoin::hashes::Hash;
 use bitcoin::key::{TapTweak, TweakedPublicKey, UntweakedKeypair};
 use bitcoin::opcodes::all::{OP_CHECKSIGVERIFY, OP_NIP};
 use bitcoin::script::PushBytesBuf;
-use bitcoin::secp256k1::{Secp256k1, SecretKey, XOnlyPublicKey};
+use bitcoin::secp256k1::{Secp256k1, XOnlyPublicKey};
 use bitcoin::{Address, Amount, Network, Transaction};
 use serde::Serialize;
 use tracing::{instrument, trace, warn};
 
 use super::{
     build_commit_transaction, build_reveal_transaction, build_taproot, build_witness,
-    get_size_reveal, sign_blob_with_private_key, update_witness, TransactionKindBatchProof,
-    TxListWithReveal, TxWithId,
+    get_size_reveal, update_witness, TransactionKindBatchProof, TxListWithReveal, TxWithId,
 };
+use crate::helpers::calculate_sha256;
+use crate::helpers::parsers::BatchProofAggregate;
+use crate::signer::{BlobSignature, DaSigner};
 use crate::spec::utxo::UTXO;
 use crate::{REVEAL_OUTPUT_AMOUNT, REVEAL_OUTPUT_THRESHOLD};
 
-/// This is a list of batch proof tx we need to send to DA (only SequencerCommitment for now)
+/// Max size of a body sent in a single reveal, which pushes it as one script element.
+/// Larger bodies must be sent with [`create_chunked_batchproof_transactions`].
+const MAX_SEQCOMMITMENT_BODY_SIZE: usize = 520;
+
+/// Max number of chunks of an aggregate. The commits and reveals of the chunks and the aggregate
+/// are chained unconfirmed txs, so 2 * chunks + 2 of them and the pending txs they spend from must
+/// stay within Bitcoin Core's default limit of 25 ancestors.
+pub const MAX_BATCH_PROOF_CHUNKS: usize = 10;
+
+/// This is a list of batch proof tx we need to send to DA
 #[derive(Serialize)]
-pub(crate) struct BatchProvingTxs {
-    pub(crate) commit: Transaction, // unsigned
-    pub(crate) reveal: TxWithId,
+pub(crate) enum BatchProvingTxs {
+    SequencerCommitment {
+        commit: Transaction, // unsigned
+        reveal: TxWithId,
+    },
+    Chunked {
+        commit_chunks: Vec<Transaction>, // unsigned
+        reveal_chunks: Vec<Transaction>,
+        commit: Transaction, // unsigned
+        reveal: TxWithId,
+    },
 }
 
 impl TxListWithReveal for BatchProvingTxs {
     fn write_to_file(&self, mut path: PathBuf) -> Result<(), anyhow::Error> {
-        path.push(format!(
-            "batch_proof_inscription_with_reveal_id_{}.txs",
-            self.reveal.id
-        ));
-
-        let file = File::create(path)?;
-        let mut writer = BufWriter::new(&file);
-        writer.write_all(&serialize(&self.commit))?;
-        writer.write_all(&serialize(&self.reveal.tx))?;
-        writer.flush()?;
-        Ok(())
+        match self {
+            Self::SequencerCommitment { commit, reveal } => {
+                path.push(format!(
+                    "batch_proof_inscription_with_reveal_id_{}.txs",
+                    reveal.id
+                ));
+                let file = File::create(path)?;
+                let mut writer = BufWriter::new(&file);
+                writer.write_all(&serialize(commit))?;
+                writer.write_all(&serialize(&reveal.tx))?;
+                writer.flush()?;
+                Ok(())
+            }
+            Self::Chunked {
+                commit_chunks,
+                reveal_chunks,
+                commit,
+                reveal,
+            } => {
+                path.push(format!(
+                    "chunked_batch_proof_inscription_with_reveal_id_{}.txs",
+                    reveal.id
+                ));
+                let file = File::create(path)?;
+                let mut writer = BufWriter::new(&file);
+                for (commit_chunk, reveal_chunk) in commit_chunks.iter().zip(reveal_chunks.iter()) {
+                    writer.write_all(&serialize(commit_chunk))?;
+                    writer.write_all(&serialize(reveal_chunk))?;
+                }
+                writer.write_all(&serialize(commit))?;
+                writer.write_all(&serialize(&reveal.tx))?;
+                writer.flush()?;
+                Ok(())
+            }
+        }
     }
 }
 
@@ -54,7 +97,7 @@ impl TxListWithReveal for BatchProvingTxs {
 #[instrument(level = "trace", skip_all, err)]
 pub fn create_seqcommitment_transactions(
     body: Vec<u8>,
-    da_private_key: SecretKey,
+    signer: &dyn DaSigner,
     prev_utxo: Option<UTXO>,
     utxos: Vec<UTXO>,
     change_address: Address,
@@ -65,7 +108,34 @@ pub fn create_seqcommitment_transactions(
 ) -> Result<BatchProvingTxs, anyhow::Error> {
     create_batchproof_type_0(
         body,
-        &da_private_key,
+        signer,
+        prev_utxo,
+        utxos,
+        change_address,
+        commit_fee_rate,
+        reveal_fee_rate,
+        network,
+        &reveal_tx_prefix,
+    )
+}
+
+// Creates the batch proof transactions for a blob too large for a single reveal (chunks and aggregate)
+#[allow(clippy::too_many_arguments)]
+#[instrument(level = "trace", skip_all, err)]
+pub fn create_chunked_batchproof_transactions(
+    chunks: Vec<Vec<u8>>,
+    signer: &dyn DaSigner,
+    prev_utxo: Option<UTXO>,
+    utxos: Vec<UTXO>,
+    change_address: Address,
+    commit_fee_rate: u64,
+    reveal_fee_rate: u64,
+    network: Network,
+    reveal_tx_prefix: Vec<u8>,
+) -> Result<BatchProvingTxs, anyhow::Error> {
+    create_batchproof_type_1(
+        chunks,
+        signer,
         prev_utxo,
         utxos,
         change_address,
@@ -81,7 +151,7 @@ pub fn create_seqcommitment_transactions(
 #[instrument(level = "trace", skip_all, err)]
 pub fn create_batchproof_type_0(
     body: Vec<u8>,
-    da_private_key: &SecretKey,
+    signer: &dyn DaSigner,
     prev_utxo: Option<UTXO>,
     utxos: Vec<UTXO>,
     change_address: Address,
@@ -91,7 +161,7 @@ pub fn create_batchproof_type_0(
     reveal_tx_prefix: &[u8],
 ) -> Result<BatchProvingTxs, anyhow::Error> {
     debug_assert!(
-        body.len() < 520,
+        body.len() <= MAX_SEQCOMMITMENT_BODY_SIZE,
         "The body of a serialized sequencer commitment exceeds 520 bytes"
     );
     // Create reveal key
@@ -103,7 +173,10 @@ pub fn create_batchproof_type_0(
     let kind_bytes = kind.to_bytes();
 
     // sign the body for authentication of the sequencer
-    let (signature, signer_public_key) = sign_blob_with_private_key(&body, da_private_key);
+    let BlobSignature {
+        signature,
+        public_key: signer_public_key,
+    } = signer.sign_blob(&body)?;
 
     // start creating inscription content
     let reveal_script_builder = script::Builder::new()
@@ -120,6 +193,204 @@ pub fn create_batchproof_type_0(
         .push_opcode(OP_ENDIF);
 
     println!("reveal_script_builder: {:?}", reveal_script_builder);
+    // we don't need leftover_utxos because they will be requested from bitcoind next call
+    let (unsigned_commit_tx, reveal_tx, _leftover_utxos) = build_prefixed_commit_and_reveal(
+        reveal_script_builder,
+        &key_pair,
+        prev_utxo,
+        utxos,
+        &change_address,
+        commit_fee_rate,
+        reveal_fee_rate,
+        network,
+        reveal_tx_prefix,
+    )?;
+
+    Ok(BatchProvingTxs::SequencerCommitment {
+        commit: unsigned_commit_tx,
+        reveal: TxWithId {
+            id: reveal_tx.compute_txid(),
+            tx: reveal_tx,
+        },
+    })
+}
+
+// Creates the batch proof transactions Type 1 - BatchProvingTxs::Chunked
+// Unlike in the light client namespace, chunk reveals also start with the prefix,
+// so readers find them among the relevant txs of the blocks they are mined in.
+#[allow(clippy::too_many_arguments)]
+#[instrument(level = "trace", skip_all, err)]
+pub fn create_batchproof_type_1(
+    chunks: Vec<Vec<u8>>,
+    signer: &dyn DaSigner,
+    mut prev_utxo: Option<UTXO>,
+    mut utxos: Vec<UTXO>,
+    change_address: Address,
+    commit_fee_rate: u64,
+    reveal_fee_rate: u64,
+    network: Network,
+    reveal_tx_prefix: &[u8],
+) -> Result<BatchProvingTxs, anyhow::Error> {
+    anyhow::ensure!(
+        chunks.len() <= MAX_BATCH_PROOF_CHUNKS,
+        "Batch proof blob needs {} chunks, at most {} are allowed",
+        chunks.len(),
+        MAX_BATCH_PROOF_CHUNKS
+    );
+
+    // Create reveal key
+    let secp256k1 = Secp256k1::new();
+    let key_pair = UntweakedKeypair::new(&secp256k1, &mut rand::thread_rng());
+    let (public_key, _parity) = XOnlyPublicKey::from_keypair(&key_pair);
+
+    let body_hash = calculate_sha256(&chunks.concat());
+
+    let mut commit_chunks: Vec<Transaction> = vec![];
+    let mut reveal_chunks: Vec<Transaction> = vec![];
+
+    for body in chunks {
+        let kind = TransactionKindBatchProof::ChunkedPart;
+        let kind_bytes = kind.to_bytes();
+
+        // start creating inscription content
+        let mut reveal_script_builder = script::Builder::new()
+            .push_x_only_key(&public_key)
+            .push_opcode(OP_CHECKSIGVERIFY)
+            .push_slice(PushBytesBuf::try_from(kind_bytes).expect("Cannot push header"))
+            .push_opcode(OP_FALSE)
+            .push_opcode(OP_IF);
+        // push body in chunks of 520 bytes
+        for chunk in body.chunks(520) {
+            reveal_script_builder = reveal_script_builder.push_slice(
+                PushBytesBuf::try_from(chunk.to_vec()).expect("Cannot push body chunk"),
+            );
+        }
+        // push end if
+        reveal_script_builder = reveal_script_builder.push_opcode(OP_ENDIF);
+
+        let (unsigned_commit_tx, reveal_tx, leftover_utxos) = build_prefixed_commit_and_reveal(
+            reveal_script_builder,
+            &key_pair,
+            prev_utxo.clone(),
+            utxos,
+            &change_address,
+            commit_fee_rate,
+            reveal_fee_rate,
+            network,
+            reveal_tx_prefix,
+        )?;
+
+        // set prev utxo to last reveal tx[0] to chain txs in order
+        prev_utxo = Some(UTXO {
+            tx_id: reveal_tx.compute_txid(),
+            vout: 0,
+            script_pubkey: reveal_tx.output[0].script_pubkey.to_hex_string(),
+            address: None,
+            amount: reveal_tx.output[0].value.to_sat(),
+            confirmations: 0,
+            spendable: true,
+            solvable: true,
+        });
+
+        // Replace utxos with leftovers so we don't use prev utxos in next chunks
+        utxos = leftover_utxos;
+        if unsigned_commit_tx.output.len() > 1 {
+            utxos.push(UTXO {
+                tx_id: unsigned_commit_tx.compute_txid(),
+                vout: 1,
+                address: None,
+                script_pubkey: unsigned_commit_tx.output[1].script_pubkey.to_hex_string(),
+                amount: unsigned_commit_tx.output[1].value.to_sat(),
+                confirmations: 0,
+                spendable: true,
+                solvable: true,
+            });
+        }
+
+        commit_chunks.push(unsigned_commit_tx);
+        reveal_chunks.push(reveal_tx);
+    }
+
+    let aggregate = BatchProofAggregate {
+        chunk_ids: reveal_chunks
+            .iter()
+            .map(|tx| tx.compute_txid().to_byte_array())
+            .collect(),
+        body_hash,
+    };
+
+    let reveal_body: Vec<u8> =
+        borsh::to_vec(&aggregate).expect("Aggregate serialize must not fail");
+    // sign the body for authentication of the sequencer
+    let BlobSignature {
+        signature,
+        public_key: signer_public_key,
+    } = signer.sign_blob(&reveal_body)?;
+
+    let kind = TransactionKindBatchProof::Chunked;
+    let kind_bytes = kind.to_bytes();
+
+    // start creating inscription content
+    let mut reveal_script_builder = script::Builder::new()
+        .push_x_only_key(&public_key)
+        .push_opcode(OP_CHECKSIGVERIFY)
+        .push_slice(PushBytesBuf::try_from(kind_bytes).expect("Cannot push header"))
+        .push_opcode(OP_FALSE)
+        .push_opcode(OP_IF)
+        .push_slice(PushBytesBuf::try_from(signature).expect("Cannot push signature"))
+        .push_slice(
+            PushBytesBuf::try_from(signer_public_key).expect("Cannot push sequencer public key"),
+        );
+    // push body in chunks of 520 bytes
+    for chunk in reveal_body.chunks(520) {
+        reveal_script_builder = reveal_script_builder
+            .push_slice(PushBytesBuf::try_from(chunk.to_vec()).expect("Cannot push body chunk"));
+    }
+    // push end if
+    reveal_script_builder = reveal_script_builder.push_opcode(OP_ENDIF);
+
+    let (unsigned_commit_tx, reveal_tx, _leftover_utxos) = build_prefixed_commit_and_reveal(
+        reveal_script_builder,
+        &key_pair,
+        prev_utxo,
+        utxos,
+        &change_address,
+        commit_fee_rate,
+        reveal_fee_rate,
+        network,
+        reveal_tx_prefix,
+    )?;
+
+    Ok(BatchProvingTxs::Chunked {
+        commit_chunks,
+        reveal_chunks,
+        commit: unsigned_commit_tx,
+        reveal: TxWithId {
+            id: reveal_tx.compute_txid(),
+            tx: reveal_tx,
+        },
+    })
+}
+
+/// Finishes the envelope of `reveal_script_builder` with a nonce and builds the commit and
+/// the reveal tx spending it, such that the reveal wtxid starts with `reveal_tx_prefix`.
+///
+/// Return (unsigned_commit_tx, reveal_tx, leftover_utxos)
+#[allow(clippy::too_many_arguments)]
+fn build_prefixed_commit_and_reveal(
+    reveal_script_builder: script::Builder,
+    key_pair: &UntweakedKeypair,
+    prev_utxo: Option<UTXO>,
+    utxos: Vec<UTXO>,
+    change_address: &Address,
+    commit_fee_rate: u64,
+    reveal_fee_rate: u64,
+    network: Network,
+    reveal_tx_prefix: &[u8],
+) -> Result<(Transaction, Transaction, Vec<UTXO>), anyhow::Error> {
+    let secp256k1 = Secp256k1::new();
+    let (public_key, _parity) = XOnlyPublicKey::from_keypair(key_pair);
+
     // Start loop to find a 'nonce' i.e. random number that makes the reveal tx hash starting with zeros given length
     let mut nonce: i64 = 16; // skip the first digits to avoid OP_PUSHNUM_X
     loop {
@@ -129,19 +400,13 @@ pub fn create_batchproof_type_0(
                 warn!("Too many iterations finding nonce");
             }
         }
-        let utxos = utxos.clone();
-        let change_address = change_address.clone();
-        // ownerships are moved to the loop
-        let mut reveal_script_builder = reveal_script_builder.clone();
-
         // push nonce
-        reveal_script_builder = reveal_script_builder
+        let reveal_script = reveal_script_builder
+            .clone()
             .push_slice(nonce.to_le_bytes())
             // drop the second item, bc there is a big chance it's 0 (tx kind) and nonce is >= 16
-            .push_opcode(OP_NIP);
-
-        // finalize reveal script
-        let reveal_script = reveal_script_builder.into_script();
+            .push_opcode(OP_NIP)
+            .into_script();
 
         let (control_block, merkle_root, tapscript_hash) =
             build_taproot(&reveal_script, public_key, &secp256k1);
@@ -160,10 +425,9 @@ pub fn create_batchproof_type_0(
         let reveal_input_value = fee + reveal_value + REVEAL_OUTPUT_THRESHOLD;
 
         // build commit tx
-        // we don't need leftover_utxos because they will be requested from bitcoind next call
-        let (mut unsigned_commit_tx, _leftover_utxos) = build_commit_transaction(
+        let (mut unsigned_commit_tx, leftover_utxos) = build_commit_transaction(
             prev_utxo.clone(),
-            utxos,
+            utxos.clone(),
             commit_tx_address.clone(),
             change_address.clone(),
             reveal_input_value,
@@ -176,7 +440,7 @@ pub fn create_batchproof_type_0(
             output_to_reveal.clone(),
             unsigned_commit_tx.compute_txid(),
             0,
-            change_address,
+            change_address.clone(),
             reveal_value + REVEAL_OUTPUT_THRESHOLD,
             reveal_fee_rate,
             &reveal_script,
@@ -189,13 +453,12 @@ pub fn create_batchproof_type_0(
             tapscript_hash,
             reveal_script,
             control_block,
-            &key_pair,
+            key_pair,
             &secp256k1,
         );
 
         let min_commit_value = Amount::from_sat(fee + reveal_value);
         while unsigned_commit_tx.output[0].value >= min_commit_value {
-            // tracing::info!("reveal output: {}", reveal_tx.output[0].value);
             let reveal_wtxid = reveal_tx.compute_wtxid();
             let reveal_hash = reveal_wtxid.as_raw_hash().to_byte_array();
             // check if first N bytes equal to the given prefix
@@ -211,13 +474,7 @@ pub fn create_batchproof_type_0(
                     commit_tx_address
                 );
 
-                return Ok(BatchProvingTxs {
-                    commit: unsigned_commit_tx,
-                    reveal: TxWithId {
-                        id: reveal_tx.compute_txid(),
-                        tx: reveal_tx,
-                    },
-                });
+                return Ok((unsigned_commit_tx, reveal_tx, leftover_utxos));
             } else {
                 unsigned_commit_tx.output[0].value -= Amount::ONE_SAT;
                 unsigned_commit_tx.output[1].value += Amount::ONE_SAT;
@@ -227,7 +484,7 @@ pub fn create_batchproof_type_0(
                     &unsigned_commit_tx,
                     &mut reveal_tx,
                     tapscript_hash,
-                    &key_pair,
+                    key_pair,
                     &secp256k1,
                 );
             }
@@ -11,7 +11,7 @@ use bitcoin::hashes::Hash;
 use bitcoin::key::{TapTweak, TweakedPublicKey, UntweakedKeypair};
 use bitcoin::opcodes::all::{OP_CHECKSIGVERIFY, OP_NIP};
 use bitcoin::script::PushBytesBuf;
-use bitcoin::secp256k1::{Secp256k1, SecretKey, XOnlyPublicKey};
+use bitcoin::secp256k1::{Secp256k1, XOnlyPublicKey};
 use bitcoin::{Address, Amount, Network, Transaction};
 use serde::Serialize;
 use sov_rollup_interface::da::DaDataLightClient;
@@ -19,9 +19,9 @@ use tracing::{instrument, trace, warn};
 
 use super::{
     build_commit_transaction, build_reveal_transaction, build_taproot, build_witness,
-    get_size_reveal, sign_blob_with_private_key, update_witness, TransactionKindLightClient,
-    TxListWithReveal, TxWithId,
+    get_size_reveal, update_witness, TransactionKindLightClient, TxListWithReveal, TxWithId,
 };
+use crate::signer::{BlobSignature, DaSigner};
 use crate::spec::utxo::UTXO;
 use crate::{REVEAL_OUTPUT_AMOUNT, REVEAL_OUTPUT_THRESHOLD};
 
@@ -96,7 +96,7 @@ impl TxListWithReveal for LightClientTxs {
 #[instrument(level = "trace", skip_all, err)]
 pub fn create_zkproof_transactions(
     data: RawLightClientData,
-    da_private_key: SecretKey,
+    signer: &dyn DaSigner,
     prev_utxo: Option<UTXO>,
     utxos: Vec<UTXO>,
     change_address: Address,
@@ -108,7 +108,7 @@ pub fn create_zkproof_transactions(
     match data {
         RawLightClientData::Complete(body) => create_inscription_type_0(
             body,
-            &da_private_key,
+            signer,
             prev_utxo,
             utxos,
             change_address,
@@ -119,7 +119,7 @@ pub fn create_zkproof_transactions(
         ),
         RawLightClientData::Chunks(body) => create_inscription_type_1(
             body,
-            &da_private_key,
+            signer,
             prev_utxo,
             utxos,
             change_address,
@@ -138,7 +138,7 @@ pub fn create_zkproof_transactions(
 #[instrument(level = "trace", skip_all, err)]
 pub fn create_inscription_type_0(
     body: Vec<u8>,
-    da_private_key: &SecretKey,
+    signer: &dyn DaSigner,
     prev_utxo: Option<UTXO>,
     utxos: Vec<UTXO>,
     change_address: Address,
@@ -156,7 +156,10 @@ pub fn create_inscription_type_0(
     let kind_bytes = kind.to_bytes();
 
     // sign the body for authentication of the sequencer
-    let (signature, signer_public_key) = sign_blob_with_private_key(&body, da_private_key);
+    let BlobSignature {
+        signature,
+        public_key: signer_public_key,
+    } = signer.sign_blob(&body)?;
 
     // start creating inscription content
     let mut reveal_script_builder = script::Builder::new()
@@ -302,7 +305,7 @@ pub fn create_inscription_type_0(
 #[instrument(level = "trace", skip_all, err)]
 pub fn create_inscription_type_1(
     chunks: Vec<Vec<u8>>,
-    da_private_key: &SecretKey,
+    signer: &dyn DaSigner,
     mut prev_utxo: Option<UTXO>,
     mut utxos: Vec<UTXO>,
     change_address: Address,
@@ -448,7 +451,10 @@ pub fn create_inscription_type_1(
     let reveal_body: Vec<u8> =
         borsh::to_vec(&aggregate).expect("Aggregate serialize must not fail");
     // sign the body for authentication of the sequencer
-    let (signature, signer_public_key) = sign_blob_with_private_key(&reveal_body, da_private_key);
+    let BlobSignature {
+        signature,
+        public_key: signer_public_key,
+    } = signer.sign_blob(&reveal_body)?;
 
     let kind = TransactionKindLightClient::Chunked;
     let kind_bytes = kind.to_bytes();
@@ -6,10 +6,8 @@ mod tests;
 
 use core::fmt;
 use core::result::Result::Ok;
-use std::collections::HashSet;
 use std::path::PathBuf;
 
-use anyhow::anyhow;
 use bitcoin::absolute::LockTime;
 use bitcoin::blockdata::script;
 use bitcoin::hashes::Hash;
@@ -24,6 +22,7 @@ use bitcoin::{
 use serde::Serialize;
 use tracing::{instrument, trace, warn};
 
+use super::coin_selection::select_coins;
 use super::{calculate_sha256, TransactionKindBatchProof, TransactionKindLightClient};
 use crate::spec::utxo::UTXO;
 use crate::REVEAL_OUTPUT_AMOUNT;
@@ -400,64 +399,14 @@ fn get_size_reveal(
 }
 
 /// Return (chosen_utxos, sum(chosen.amount), leftover_utxos)
+/// Selections exceeding `amount` by less than a non-dust change are preferred,
+/// the excess goes to fees instead of a change output.
 fn choose_utxos(
     required_utxo: Option<UTXO>,
     utxos: &[UTXO],
-    mut amount: u64,
+    amount: u64,
 ) -> Result<(Vec<UTXO>, u64, Vec<UTXO>), anyhow::Error> {
-    let mut chosen_utxos = vec![];
-    let mut sum = 0;
-
-    // First include a required utxo
-    if let Some(required) = required_utxo {
-        let req_amount = required.amount;
-        chosen_utxos.push(required);
-        sum += req_amount;
-    }
-    if sum >= amount {
-        return Ok((chosen_utxos, sum, utxos.to_vec()));
-    } else {
-        amount -= sum;
-    }
-
-    let mut bigger_utxos: Vec<&UTXO> = utxos.iter().filter(|utxo| utxo.amount >= amount).collect();
-
-    if !bigger_utxos.is_empty() {
-        // sort vec by amount (small first)
-        bigger_utxos.sort_by(|a, b| a.amount.cmp(&b.amount));
-
-        // single utxo will be enough
-        // so return the transaction
-        let utxo = bigger_utxos[0];
-        sum += utxo.amount;
-        chosen_utxos.push(utxo.clone());
-    } else {
-        let mut smaller_utxos: Vec<&UTXO> =
-            utxos.iter().filter(|utxo| utxo.amount < amount).collect();
-
-        // sort vec by amount (large first)
-        smaller_utxos.sort_by(|a, b| b.amount.cmp(&a.amount));
-
-        for utxo in smaller_utxos {
-            sum += utxo.amount;
-            chosen_utxos.push(utxo.clone());
-
-            if sum >= amount {
-                break;
-            }
-        }
-
-        if sum < amount {
-            return Err(anyhow!("not enough UTXOs"));
-        }
-    }
-
-    let input_set: HashSet<_> = utxos.iter().collect();
-    let chosen_set: HashSet<_> = chosen_utxos.iter().collect();
-    let leftovers_set = input_set.difference(&chosen_set);
-    let leftovers: Vec<_> = leftovers_set.copied().cloned().collect();
-
-    Ok((chosen_utxos, sum, leftovers))
+    select_coins(required_utxo, utxos, amount, REVEAL_OUTPUT_AMOUNT)
 }
 
 /// Signs a message with a private key
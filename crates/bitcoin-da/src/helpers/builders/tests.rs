@@ -6,11 +6,20 @@ use bitcoin::secp256k1::schnorr::Signature;
 use bitcoin::secp256k1::SecretKey;
 use bitcoin::taproot::ControlBlock;
 use bitcoin::{Address, Amount, ScriptBuf, TxOut, Txid};
-use citrea_primitives::compression::{compress_blob, decompress_blob};
+use citrea_primitives::compression::{
+    compress_blob, decompress_blob, decompress_legacy_blob, BlobCompression, CompressionCodec,
+    DecompressionError, ENVELOPE_V1,
+};
 
+use super::batch_proof_namespace::{BatchProvingTxs, MAX_BATCH_PROOF_CHUNKS};
 use super::light_client_proof_namespace::{LightClientTxs, RawLightClientData};
 use crate::helpers::builders::sign_blob_with_private_key;
-use crate::helpers::parsers::{parse_light_client_transaction, ParsedLightClientTransaction};
+use crate::helpers::parsers::{
+    collect_batch_proof_chunks, parse_batch_proof_transaction, parse_light_client_transaction,
+    reassemble_batch_proof_aggregate, ParsedBatchProofTransaction, ParsedLightClientTransaction,
+    VerifyParsed,
+};
+use crate::signer::InProcessSigner;
 use crate::spec::utxo::UTXO;
 use crate::REVEAL_OUTPUT_AMOUNT;
 
@@ -20,12 +29,12 @@ fn compression_decompression() {
 
     // compress and measure time
     let time = std::time::Instant::now();
-    let compressed_blob = compress_blob(&blob);
+    let compressed_blob = compress_blob(&blob, BlobCompression::Legacy);
     println!("compression time: {:?}", time.elapsed());
 
     // decompress and measure time
     let time = std::time::Instant::now();
-    let decompressed_blob = decompress_blob(&compressed_blob);
+    let decompressed_blob = decompress_blob(&compressed_blob).unwrap();
     println!("decompression time: {:?}", time.elapsed());
 
     assert_eq!(blob, decompressed_blob);
@@ -39,6 +48,60 @@ fn compression_decompression() {
     );
 }
 
+#[test]
+fn compression_envelope_roundtrip() {
+    let blob = std::fs::read("test_data/blob.txt").unwrap();
+
+    for codec in [
+        CompressionCodec::None,
+        CompressionCodec::Brotli,
+        CompressionCodec::ZstdStateDiff,
+    ] {
+        let compressed_blob = compress_blob(&blob, BlobCompression::Envelope(codec));
+        assert_eq!(compressed_blob[..2], [ENVELOPE_V1, codec as u8]);
+        assert_eq!(decompress_blob(&compressed_blob).unwrap(), blob);
+    }
+}
+
+#[test]
+fn decompression_of_invalid_blobs_fails() {
+    assert_eq!(decompress_blob(&[]), Err(DecompressionError::Empty));
+    assert_eq!(
+        decompress_blob(&[ENVELOPE_V1, 42, 1, 2, 3]),
+        Err(DecompressionError::UnknownCodec(42))
+    );
+    assert!(matches!(
+        decompress_blob(&[ENVELOPE_V1, CompressionCodec::ZstdStateDiff as u8, 1, 2, 3]),
+        Err(DecompressionError::Malformed(
+            CompressionCodec::ZstdStateDiff,
+            _
+        ))
+    ));
+
+    let mut truncated = compress_blob(&[1; 1000], BlobCompression::Legacy);
+    truncated.truncate(truncated.len() / 2);
+    assert!(matches!(
+        decompress_blob(&truncated),
+        Err(DecompressionError::Malformed(CompressionCodec::Brotli, _))
+    ));
+}
+
+#[test]
+fn legacy_decompression_only_reads_bare_brotli() {
+    let blob = vec![7; 1000];
+    assert_eq!(
+        decompress_legacy_blob(&compress_blob(&blob, BlobCompression::Legacy)),
+        blob
+    );
+
+    // envelopes are only read from Fork2 on
+    let compressed_blob = compress_blob(
+        &blob,
+        BlobCompression::Envelope(CompressionCodec::ZstdStateDiff),
+    );
+    assert!(std::panic::catch_unwind(|| decompress_legacy_blob(&compressed_blob)).is_err());
+}
+
 #[allow(clippy::type_complexity)]
 fn get_mock_data() -> (Vec<u8>, Address, Vec<UTXO>) {
     let body = vec![100; 1000];
@@ -470,7 +533,7 @@ fn create_inscription_transactions() {
     let LightClientTxs::Complete { commit, reveal } =
         super::light_client_proof_namespace::create_zkproof_transactions(
             RawLightClientData::Complete(body.clone()),
-            da_private_key,
+            &InProcessSigner::new(da_private_key),
             None,
             utxos.clone(),
             address.clone(),
@@ -538,3 +601,101 @@ fn create_inscription_transactions() {
         "sequencer public key should be correct"
     );
 }
+
+#[test]
+fn create_chunked_batch_proof_transactions() {
+    let (_, address, utxos) = get_mock_data();
+    let body: Vec<u8> = (0..3000).map(|i| i as u8).collect();
+
+    let da_private_key = SecretKey::from_slice(&[0xcd; 32]).expect("32 bytes, within curve order");
+
+    let tx_prefix = &[0u8];
+    let BatchProvingTxs::Chunked {
+        commit_chunks,
+        reveal_chunks,
+        commit,
+        reveal,
+    } = super::batch_proof_namespace::create_chunked_batchproof_transactions(
+        body.chunks(1200).map(|chunk| chunk.to_vec()).collect(),
+        &InProcessSigner::new(da_private_key),
+        None,
+        utxos,
+        address,
+        12,
+        10,
+        bitcoin::Network::Bitcoin,
+        tx_prefix.to_vec(),
+    )
+    .unwrap()
+    else {
+        panic!("Unexpected tx kind was produced");
+    };
+
+    assert_eq!(commit_chunks.len(), 3);
+    assert_eq!(reveal_chunks.len(), 3);
+
+    // chunks and the aggregate are chained
+    for (commit, prev_reveal) in commit_chunks[1..]
+        .iter()
+        .chain([&commit])
+        .zip(&reveal_chunks)
+    {
+        assert_eq!(
+            commit.input[0].previous_output.txid,
+            prev_reveal.compute_txid(),
+            "commit should spend the previous reveal"
+        );
+    }
+
+    // chunks need the prefix too, so they are in the completeness proof
+    for reveal in reveal_chunks.iter().chain([&reveal.tx]) {
+        assert!(reveal
+            .compute_wtxid()
+            .as_byte_array()
+            .starts_with(tx_prefix));
+    }
+
+    let ParsedBatchProofTransaction::Aggregate(aggregate) =
+        parse_batch_proof_transaction(&reveal.tx).unwrap()
+    else {
+        panic!("Unexpected tx kind");
+    };
+    assert!(aggregate.get_sig_verified_hash().is_some());
+
+    let chunks = collect_batch_proof_chunks(&reveal_chunks);
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(
+        reassemble_batch_proof_aggregate(&aggregate, &chunks),
+        Some(body)
+    );
+
+    // all chunks must be present
+    let chunks = collect_batch_proof_chunks(&reveal_chunks[1..]);
+    assert_eq!(reassemble_batch_proof_aggregate(&aggregate, &chunks), None);
+
+    // and match the signed body hash
+    let mut chunks = collect_batch_proof_chunks(&reveal_chunks);
+    chunks.values_mut().next().unwrap().body[0] ^= 1;
+    assert_eq!(reassemble_batch_proof_aggregate(&aggregate, &chunks), None);
+}
+
+#[test]
+fn create_chunked_batch_proof_transactions_over_chunk_limit() {
+    let (_, address, utxos) = get_mock_data();
+
+    let da_private_key = SecretKey::from_slice(&[0xcd; 32]).expect("32 bytes, within curve order");
+
+    // the chained chunk txs would exceed the mempool ancestor limit
+    let res = super::batch_proof_namespace::create_chunked_batchproof_transactions(
+        vec![vec![0u8; 100]; MAX_BATCH_PROOF_CHUNKS + 1],
+        &InProcessSigner::new(da_private_key),
+        None,
+        utxos,
+        address,
+        12,
+        10,
+        bitcoin::Network::Bitcoin,
+        vec![0u8],
+    );
+    assert!(res.is_err());
+}
@@ -0,0 +1,253 @@
+//! Selection of wallet UTXOs funding DA transactions.
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+
+use crate::spec::utxo::UTXO;
+
+/// Maximum number of branches branch and bound explores before giving up.
+const BNB_MAX_TRIES: usize = 100_000;
+
+/// Coin selection rules of the DA wallet.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CoinSelectionConfig {
+    /// Wallet UTXOs with fewer confirmations are not spent.
+    /// Outputs of the monitored DA tx chain are always spendable.
+    #[serde(default)]
+    pub min_confirmations: u32,
+    /// Consolidation of small UTXOs while fees are low, disabled if not set.
+    pub consolidation: Option<ConsolidationConfig>,
+}
+
+/// Merges small wallet UTXOs into a single one while fee rates are low,
+/// so later commit transactions need fewer inputs.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ConsolidationConfig {
+    /// Only consolidate while the estimated fee rate in sat/vB is at most this.
+    pub max_fee_rate: u64,
+    /// Only consolidate once the wallet has at least this many spendable UTXOs.
+    pub min_utxos: usize,
+    /// Maximum number of UTXOs spent by a consolidation transaction.
+    pub max_inputs: usize,
+    /// Seconds between consolidation checks.
+    pub check_interval: u64,
+}
+
+/// Chooses UTXOs worth at least `amount`, always including `required_utxo`.
+///
+/// Branch and bound looks for a selection without change first, i.e. one exceeding `amount`
+/// by at most `cost_of_change`, preferring the one wasting the least. If there is none, the
+/// smallest single UTXO covering the amount is chosen, or the largest UTXOs until it is covered.
+///
+/// Return (chosen_utxos, sum(chosen.amount), leftover_utxos)
+pub fn select_coins(
+    required_utxo: Option<UTXO>,
+    utxos: &[UTXO],
+    amount: u64,
+    cost_of_change: u64,
+) -> Result<(Vec<UTXO>, u64, Vec<UTXO>), anyhow::Error> {
+    let mut chosen_utxos = vec![];
+    let mut sum = 0;
+
+    // First include a required utxo
+    if let Some(required) = required_utxo {
+        sum += required.amount;
+        chosen_utxos.push(required);
+    }
+    if sum >= amount {
+        return Ok((chosen_utxos, sum, utxos.to_vec()));
+    }
+    let remaining = amount - sum;
+
+    let selected = branch_and_bound(utxos, remaining, cost_of_change)
+        .or_else(|| knapsack(utxos, remaining))
+        .ok_or(anyhow!("not enough UTXOs"))?;
+
+    for index in &selected {
+        sum += utxos[*index].amount;
+        chosen_utxos.push(utxos[*index].clone());
+    }
+    let leftovers = utxos
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !selected.contains(index))
+        .map(|(_, utxo)| utxo.clone())
+        .collect();
+
+    Ok((chosen_utxos, sum, leftovers))
+}
+
+/// Depth first search for the selection in `[target, target + cost_of_change]` with the
+/// least waste, as in Bitcoin Core. Returns the indices of the selected utxos.
+fn branch_and_bound(utxos: &[UTXO], target: u64, cost_of_change: u64) -> Option<Vec<usize>> {
+    // Largest first, so the search reaches the target quickly
+    let mut pool: Vec<usize> = (0..utxos.len()).collect();
+    pool.sort_by(|a, b| utxos[*b].amount.cmp(&utxos[*a].amount));
+    let value = |pool_index: usize| utxos[pool[pool_index]].amount;
+
+    let mut curr_value = 0u64;
+    let mut curr_available: u64 = utxos.iter().map(|utxo| utxo.amount).sum();
+    let mut curr_selection: Vec<usize> = vec![];
+    let mut best: Option<(Vec<usize>, u64)> = None;
+
+    let mut pool_index = 0;
+    for _ in 0..BNB_MAX_TRIES {
+        let backtrack =
+            if curr_value + curr_available < target || curr_value > target + cost_of_change {
+                true
+            } else if curr_value >= target {
+                let waste = curr_value - target;
+                if best
+                    .as_ref()
+                    .map_or(true, |(_, best_waste)| waste < *best_waste)
+                {
+                    best = Some((curr_selection.clone(), waste));
+                }
+                if waste == 0 {
+                    break;
+                }
+                true
+            } else {
+                false
+            };
+
+        if backtrack {
+            let Some(&last_selected) = curr_selection.last() else {
+                // Every branch has been explored
+                break;
+            };
+            // Add the utxos skipped since the last selected one back to the lookahead
+            pool_index -= 1;
+            while pool_index > last_selected {
+                curr_available += value(pool_index);
+                pool_index -= 1;
+            }
+            // Continue with the branch excluding the last selected utxo
+            curr_value -= value(pool_index);
+            curr_selection.pop();
+        } else {
+            curr_available -= value(pool_index);
+            // Excluding a utxo and including an equal one next is the same selection
+            let equivalent_to_excluded = pool_index > 0
+                && curr_selection.last() != Some(&(pool_index - 1))
+                && value(pool_index) == value(pool_index - 1);
+            if !equivalent_to_excluded {
+                curr_selection.push(pool_index);
+                curr_value += value(pool_index);
+            }
+        }
+        pool_index += 1;
+    }
+
+    best.map(|(selection, _)| selection.into_iter().map(|i| pool[i]).collect())
+}
+
+/// Returns the smallest utxo covering `target` on its own, or else the largest utxos
+/// until `target` is covered. Returns the indices of the selected utxos.
+fn knapsack(utxos: &[UTXO], target: u64) -> Option<Vec<usize>> {
+    if let Some(index) = (0..utxos.len())
+        .filter(|index| utxos[*index].amount >= target)
+        .min_by_key(|index| utxos[*index].amount)
+    {
+        // single utxo will be enough
+        return Some(vec![index]);
+    }
+
+    let mut smaller: Vec<usize> = (0..utxos.len()).collect();
+    // sort by amount (large first)
+    smaller.sort_by(|a, b| utxos[*b].amount.cmp(&utxos[*a].amount));
+
+    let mut sum = 0;
+    let mut selected = vec![];
+    for index in smaller {
+        sum += utxos[index].amount;
+        selected.push(index);
+        if sum >= target {
+            return Some(selected);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    use bitcoin::Txid;
+
+    use super::{select_coins, UTXO};
+
+    fn utxo(vout: u32, amount: u64) -> UTXO {
+        UTXO {
+            tx_id: Txid::from_str(
+                "4cfbec13cf1510545f285cceceb6229bd7b6a918a8f6eba1dbee64d26226a3b7",
+            )
+            .unwrap(),
+            vout,
+            address: None,
+            script_pubkey: String::new(),
+            amount,
+            confirmations: 1,
+            spendable: true,
+            solvable: true,
+        }
+    }
+
+    #[test]
+    fn branch_and_bound_avoids_change() {
+        let utxos = vec![
+            utxo(0, 1_000_000),
+            utxo(1, 60_000),
+            utxo(2, 50_000),
+            utxo(3, 40_000),
+            utxo(4, 10_000),
+        ];
+
+        // 60k + 40k is exact, while the smallest covering utxo is 1M
+        let (chosen, sum, leftovers) = select_coins(None, &utxos, 100_000, 546).unwrap();
+        assert_eq!(sum, 100_000);
+        assert_eq!(chosen, vec![utxos[1].clone(), utxos[3].clone()]);
+        assert_eq!(leftovers.len(), 3);
+
+        // Within the cost of change
+        let (chosen, sum, _) = select_coins(None, &utxos, 109_800, 546).unwrap();
+        assert_eq!(sum, 110_000);
+        assert_eq!(chosen.len(), 2);
+    }
+
+    #[test]
+    fn knapsack_fallback() {
+        let utxos = vec![utxo(0, 1_000_000), utxo(1, 60_000), utxo(2, 50_000)];
+
+        // No selection close enough, the smallest covering utxo is used
+        let (chosen, sum, _) = select_coins(None, &utxos, 105_000, 546).unwrap();
+        assert_eq!(sum, 1_000_000);
+        assert_eq!(chosen, vec![utxos[0].clone()]);
+
+        // No single utxo covers the amount, the largest ones are used
+        let (chosen, sum, leftovers) = select_coins(None, &utxos, 1_055_000, 546).unwrap();
+        assert_eq!(sum, 1_060_000);
+        assert_eq!(chosen, vec![utxos[0].clone(), utxos[1].clone()]);
+        assert_eq!(leftovers, vec![utxos[2].clone()]);
+
+        assert!(select_coins(None, &utxos, 2_000_000, 546).is_err());
+    }
+
+    #[test]
+    fn required_utxo_is_always_chosen() {
+        let utxos = vec![utxo(0, 30_000), utxo(1, 20_000)];
+
+        let (chosen, sum, leftovers) =
+            select_coins(Some(utxo(9, 50_000)), &utxos, 70_000, 546).unwrap();
+        assert_eq!(sum, 70_000);
+        assert_eq!(chosen[0].vout, 9);
+        assert_eq!(chosen[1], utxos[1]);
+        assert_eq!(leftovers, vec![utxos[0].clone()]);
+
+        let (chosen, sum, leftovers) =
+            select_coins(Some(utxo(9, 50_000)), &utxos, 40_000, 546).unwrap();
+        assert_eq!(sum, 50_000);
+        assert_eq!(chosen.len(), 1);
+        assert_eq!(leftovers, utxos);
+    }
+}
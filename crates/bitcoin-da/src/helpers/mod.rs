@@ -4,6 +4,8 @@ use sha2::{Digest, Sha256};
 
 #[cfg(feature = "native")]
 pub mod builders;
+#[cfg(feature = "native")]
+pub mod coin_selection;
 pub mod merkle_tree;
 pub mod parsers;
 
@@ -51,8 +53,11 @@ impl TransactionKindLightClient {
 enum TransactionKindBatchProof {
     /// SequencerCommitment
     SequencerCommitment = 0,
-    // /// ForcedTransaction
-    // ForcedTransaction = 1,
+    /// This type of transaction includes the signed txids and hash of the chunks of a body
+    /// too large for a single reveal (> MAX_TXBODY_SIZE)
+    Chunked = 1,
+    /// This type of transaction includes one chunk of such a body, at most MAX_TXBODY_SIZE
+    ChunkedPart = 2,
     Unknown(NonZeroU16),
 }
 
@@ -61,7 +66,8 @@ impl TransactionKindBatchProof {
     fn to_bytes(&self) -> Vec<u8> {
         match self {
             TransactionKindBatchProof::SequencerCommitment => 0u16.to_le_bytes().to_vec(),
-            // TransactionKindBatchProof::ForcedTransaction => 1u16.to_le_bytes(),
+            TransactionKindBatchProof::Chunked => 1u16.to_le_bytes().to_vec(),
+            TransactionKindBatchProof::ChunkedPart => 2u16.to_le_bytes().to_vec(),
             TransactionKindBatchProof::Unknown(v) => v.get().to_le_bytes().to_vec(),
         }
     }
@@ -73,7 +79,8 @@ impl TransactionKindBatchProof {
         kind_bytes.copy_from_slice(bytes);
         match u16::from_le_bytes(kind_bytes) {
             0 => Some(TransactionKindBatchProof::SequencerCommitment),
-            // 1 => TransactionKindBatchProof::ForcedTransaction,
+            1 => Some(TransactionKindBatchProof::Chunked),
+            2 => Some(TransactionKindBatchProof::ChunkedPart),
             n => Some(TransactionKindBatchProof::Unknown(
                 NonZeroU16::new(n).expect("Is not zero"),
             )),
@@ -1,11 +1,14 @@
 use core::num::NonZeroU16;
+use std::collections::HashMap;
 
 use bitcoin::blockdata::script::Instruction;
+use bitcoin::hashes::Hash;
 use bitcoin::opcodes::all::OP_CHECKSIGVERIFY;
 use bitcoin::script::Instruction::{Op, PushBytes};
 use bitcoin::script::{Error as ScriptError, PushBytes as StructPushBytes};
 use bitcoin::secp256k1::{ecdsa, Message, Secp256k1};
 use bitcoin::{secp256k1, Opcode, Script, Transaction};
+use borsh::{BorshDeserialize, BorshSerialize};
 use thiserror::Error;
 
 use super::calculate_sha256;
@@ -24,8 +27,10 @@ pub enum ParsedLightClientTransaction {
 pub enum ParsedBatchProofTransaction {
     /// Kind 0
     SequencerCommitment(ParsedSequencerCommitment),
-    // /// Kind 1
-    // ForcedTransaction(ForcedTransaction),
+    /// Kind 1
+    Aggregate(ParsedAggregate),
+    /// Kind 2
+    Chunk(ParsedChunk),
 }
 
 #[derive(Debug, Clone)]
@@ -47,6 +52,15 @@ pub struct ParsedChunk {
     pub body: Vec<u8>,
 }
 
+/// Body of a batch proof aggregate.
+#[derive(Debug, Clone, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct BatchProofAggregate {
+    /// Txids of the chunks, in order.
+    pub chunk_ids: Vec<[u8; 32]>,
+    /// Sha256 of the concatenated chunk bodies.
+    pub body_hash: [u8; 32],
+}
+
 #[derive(Debug, Clone)]
 pub struct ParsedSequencerCommitment {
     pub body: Vec<u8>,
@@ -223,6 +237,12 @@ fn parse_relevant_batchproof(
             batch_proof::parse_type_0_body(instructions)
                 .map(ParsedBatchProofTransaction::SequencerCommitment)
         }
+        TransactionKindBatchProof::Chunked => {
+            batch_proof::parse_type_1_body(instructions).map(ParsedBatchProofTransaction::Aggregate)
+        }
+        TransactionKindBatchProof::ChunkedPart => {
+            batch_proof::parse_type_2_body(instructions).map(ParsedBatchProofTransaction::Chunk)
+        }
         TransactionKindBatchProof::Unknown(n) => Err(ParserError::InvalidHeaderType(n)),
     }
 }
@@ -423,6 +443,8 @@ mod batch_proof {
     use bitcoin::opcodes::all::{OP_ENDIF, OP_IF, OP_NIP};
     use bitcoin::script::Instruction;
 
+    // Chunked bodies use the same envelopes as in the light client namespace
+    pub(super) use super::light_client::{parse_type_1_body, parse_type_2_body};
     use super::{read_opcode, read_push_bytes, ParsedSequencerCommitment, ParserError};
 
     // Parse transaction body of Type0
@@ -469,6 +491,49 @@ mod batch_proof {
     }
 }
 
+/// Collects the chunks of the batch proof transactions in `txs` by their txid,
+/// so [`reassemble_batch_proof_aggregate`] can look them up.
+pub fn collect_batch_proof_chunks<'a>(
+    txs: impl IntoIterator<Item = &'a Transaction>,
+) -> HashMap<[u8; 32], ParsedChunk> {
+    txs.into_iter()
+        .filter_map(|tx| match parse_batch_proof_transaction(tx) {
+            Ok(ParsedBatchProofTransaction::Chunk(chunk)) => {
+                Some((tx.compute_txid().to_byte_array(), chunk))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Decodes the body of a batch proof aggregate, `None` if it is malformed or lists no chunks.
+pub fn parse_batch_proof_aggregate(aggregate: &ParsedAggregate) -> Option<BatchProofAggregate> {
+    let aggregate: BatchProofAggregate = borsh::from_slice(&aggregate.body).ok()?;
+    if aggregate.chunk_ids.is_empty() {
+        return None;
+    }
+    Some(aggregate)
+}
+
+/// Concatenates the chunks listed by a batch proof aggregate.
+///
+/// Chunks may be mined in earlier blocks than their aggregate, so readers look them up by
+/// txid. The verifier only sees the block of the aggregate, and checks the reassembled body
+/// against the signed `body_hash` instead. Returns `None` if the aggregate is malformed, a
+/// listed chunk is not in `chunks`, or the reassembled body doesn't match `body_hash`.
+pub fn reassemble_batch_proof_aggregate(
+    aggregate: &ParsedAggregate,
+    chunks: &HashMap<[u8; 32], ParsedChunk>,
+) -> Option<Vec<u8>> {
+    let aggregate = parse_batch_proof_aggregate(aggregate)?;
+
+    let mut body = Vec::new();
+    for chunk_id in aggregate.chunk_ids {
+        body.extend_from_slice(&chunks.get(&chunk_id)?.body);
+    }
+    (calculate_sha256(&body) == aggregate.body_hash).then_some(body)
+}
+
 #[cfg(feature = "native")]
 pub fn parse_hex_transaction(
     tx_hex: &str,
@@ -0,0 +1,494 @@
+//! Offline decoding of the Citrea inscriptions in a raw bitcoin block or transaction.
+//!
+//! Decodes what the DA service would read from the same transactions, without a bitcoin node.
+//! Chunks of aggregates are only looked up in the given transactions. Their wtxids are not
+//! ground for a namespace prefix, so they are resolved against all of them.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, bail, Context, Result};
+use bitcoin::consensus::deserialize;
+use bitcoin::hashes::Hash;
+use bitcoin::{Block, Transaction, Txid, Wtxid};
+use borsh::BorshDeserialize;
+use citrea_primitives::compression::decompress_blob;
+use serde::Serialize;
+use sov_rollup_interface::da::{DaDataBatchProof, DaDataLightClient};
+use sov_rollup_interface::zk::Proof;
+
+use crate::helpers::calculate_sha256;
+use crate::helpers::parsers::{
+    collect_batch_proof_chunks, parse_batch_proof_aggregate, parse_batch_proof_transaction,
+    parse_light_client_transaction, reassemble_batch_proof_aggregate, ParsedBatchProofTransaction,
+    ParsedChunk, ParsedLightClientTransaction, VerifyParsed,
+};
+use crate::spec::RollupParams;
+
+/// DA namespace of an inscription, by the prefix of its reveal wtxid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Namespace {
+    BatchProof,
+    LightClient,
+}
+
+/// Kind of an inscription, from its header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InscriptionKind {
+    SequencerCommitment,
+    Complete,
+    Aggregate,
+    Chunk,
+}
+
+/// Decoded body of an inscription. Aggregates are decoded from their reassembled chunks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InscriptionContent {
+    SequencerCommitment {
+        #[serde(with = "hex::serde")]
+        merkle_root: [u8; 32],
+        l2_start_block_number: u64,
+        l2_end_block_number: u64,
+    },
+    Proof {
+        size: usize,
+        #[serde(with = "hex::serde")]
+        sha256: [u8; 32],
+        #[serde(with = "hex::serde")]
+        proof: Proof,
+    },
+    /// Part of the body of an aggregate.
+    Chunk { size: usize },
+}
+
+/// A transaction in one of the DA namespaces, and what could be decoded from it.
+#[derive(Debug, Clone, Serialize)]
+pub struct Inscription {
+    pub txid: Txid,
+    pub wtxid: Wtxid,
+    pub namespace: Namespace,
+    /// `None` if the inscription could not be parsed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<InscriptionKind>,
+    /// Public key of the sender, hex encoded. Chunks are not signed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature_valid: Option<bool>,
+    /// Chunks listed by an aggregate, in order.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub chunk_txids: Vec<Txid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<InscriptionContent>,
+    /// Why decoding stopped, if it did.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Decodes the inscriptions in a hex encoded block or transaction.
+pub fn inspect_hex(input: &str, params: &RollupParams) -> Result<Vec<Inscription>> {
+    let bytes = hex::decode(input.trim()).context("Input is not hex encoded")?;
+    if let Ok(block) = deserialize::<Block>(&bytes) {
+        return Ok(inspect_transactions(&block.txdata, params));
+    }
+    let tx: Transaction =
+        deserialize(&bytes).context("Input is neither a block nor a transaction")?;
+    Ok(inspect_transactions(&[tx], params))
+}
+
+/// Decodes the inscriptions in `txs`, in order. Transactions outside the DA namespaces are skipped.
+pub fn inspect_transactions(txs: &[Transaction], params: &RollupParams) -> Vec<Inscription> {
+    let in_namespace = |tx: &Transaction, prefix: &[u8]| {
+        tx.compute_wtxid()
+            .to_byte_array()
+            .as_slice()
+            .starts_with(prefix)
+    };
+
+    let batch_proof_chunks = collect_batch_proof_chunks(txs);
+    let light_client_chunks = collect_light_client_chunks(txs);
+
+    let mut inscriptions = Vec::new();
+    for tx in txs {
+        let namespace = if in_namespace(tx, &params.to_batch_proof_prefix) {
+            Namespace::BatchProof
+        } else if in_namespace(tx, &params.to_light_client_prefix) {
+            Namespace::LightClient
+        } else {
+            continue;
+        };
+
+        let mut inscription = Inscription {
+            txid: tx.compute_txid(),
+            wtxid: tx.compute_wtxid(),
+            namespace,
+            kind: None,
+            public_key: None,
+            signature_valid: None,
+            chunk_txids: vec![],
+            content: None,
+            error: None,
+        };
+        let result = match namespace {
+            Namespace::BatchProof => {
+                inspect_batch_proof_transaction(tx, &batch_proof_chunks, &mut inscription)
+            }
+            Namespace::LightClient => {
+                inspect_light_client_transaction(tx, &light_client_chunks, &mut inscription)
+            }
+        };
+        match result {
+            Ok(content) => inscription.content = Some(content),
+            Err(e) => inscription.error = Some(format!("{:#}", e)),
+        }
+        inscriptions.push(inscription);
+    }
+    inscriptions
+}
+
+fn inspect_batch_proof_transaction(
+    tx: &Transaction,
+    chunks: &HashMap<[u8; 32], ParsedChunk>,
+    inscription: &mut Inscription,
+) -> Result<InscriptionContent> {
+    let body = match parse_batch_proof_transaction(tx)? {
+        ParsedBatchProofTransaction::SequencerCommitment(seq_comm) => {
+            inscription.kind = Some(InscriptionKind::SequencerCommitment);
+            set_sender(inscription, &seq_comm);
+            seq_comm.body
+        }
+        ParsedBatchProofTransaction::Aggregate(aggregate) => {
+            inscription.kind = Some(InscriptionKind::Aggregate);
+            set_sender(inscription, &aggregate);
+            let chunk_ids = parse_batch_proof_aggregate(&aggregate)
+                .context("Aggregate body is not a chunk list")?
+                .chunk_ids;
+            inscription.chunk_txids = chunk_ids.into_iter().map(Txid::from_byte_array).collect();
+            reassemble_batch_proof_aggregate(&aggregate, chunks).ok_or_else(|| {
+                anyhow!("Chunks of the aggregate are missing from the input or don't match it")
+            })?
+        }
+        ParsedBatchProofTransaction::Chunk(chunk) => {
+            inscription.kind = Some(InscriptionKind::Chunk);
+            return Ok(InscriptionContent::Chunk {
+                size: chunk.body.len(),
+            });
+        }
+    };
+
+    match DaDataBatchProof::try_from_slice(&body).context("Failed to decode body")? {
+        DaDataBatchProof::SequencerCommitment(commitment) => {
+            Ok(InscriptionContent::SequencerCommitment {
+                merkle_root: commitment.merkle_root,
+                l2_start_block_number: commitment.l2_start_block_number,
+                l2_end_block_number: commitment.l2_end_block_number,
+            })
+        }
+    }
+}
+
+fn inspect_light_client_transaction(
+    tx: &Transaction,
+    chunks: &HashMap<[u8; 32], Vec<u8>>,
+    inscription: &mut Inscription,
+) -> Result<InscriptionContent> {
+    let proof = match parse_light_client_transaction(tx)? {
+        ParsedLightClientTransaction::Complete(complete) => {
+            inscription.kind = Some(InscriptionKind::Complete);
+            set_sender(inscription, &complete);
+            let body = decompress_blob(&complete.body).context("Failed to decompress body")?;
+            let DaDataLightClient::Complete(proof) =
+                DaDataLightClient::try_from_slice(&body).context("Failed to decode body")?
+            else {
+                bail!("Complete body is not a proof");
+            };
+            proof
+        }
+        ParsedLightClientTransaction::Aggregate(aggregate) => {
+            inscription.kind = Some(InscriptionKind::Aggregate);
+            set_sender(inscription, &aggregate);
+            let DaDataLightClient::Aggregate(chunk_ids) =
+                DaDataLightClient::try_from_slice(&aggregate.body)
+                    .context("Failed to decode body")?
+            else {
+                bail!("Aggregate body is not a txid list");
+            };
+            inscription.chunk_txids = chunk_ids
+                .iter()
+                .copied()
+                .map(Txid::from_byte_array)
+                .collect();
+
+            let mut body = Vec::new();
+            for chunk_id in chunk_ids {
+                let chunk = chunks.get(&chunk_id).ok_or_else(|| {
+                    anyhow!(
+                        "Chunk {} is missing from the input",
+                        Txid::from_byte_array(chunk_id)
+                    )
+                })?;
+                body.extend_from_slice(chunk);
+            }
+            let body = decompress_blob(&body).context("Failed to decompress body")?;
+            borsh::from_slice(&body).context("Failed to decode proof")?
+        }
+        ParsedLightClientTransaction::Chunk(chunk) => {
+            inscription.kind = Some(InscriptionKind::Chunk);
+            let DaDataLightClient::Chunk(chunk) =
+                DaDataLightClient::try_from_slice(&chunk.body).context("Failed to decode body")?
+            else {
+                bail!("Chunk body is not a chunk");
+            };
+            return Ok(InscriptionContent::Chunk { size: chunk.len() });
+        }
+    };
+
+    Ok(InscriptionContent::Proof {
+        size: proof.len(),
+        sha256: calculate_sha256(&proof),
+        proof,
+    })
+}
+
+/// Collects the decoded chunks of the light client transactions in `txs` by their txid.
+fn collect_light_client_chunks<'a>(
+    txs: impl IntoIterator<Item = &'a Transaction>,
+) -> HashMap<[u8; 32], Vec<u8>> {
+    txs.into_iter()
+        .filter_map(|tx| match parse_light_client_transaction(tx) {
+            Ok(ParsedLightClientTransaction::Chunk(chunk)) => {
+                match DaDataLightClient::try_from_slice(&chunk.body) {
+                    Ok(DaDataLightClient::Chunk(chunk)) => {
+                        Some((tx.compute_txid().to_byte_array(), chunk))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn set_sender(inscription: &mut Inscription, parsed: &impl VerifyParsed) {
+    inscription.public_key = Some(hex::encode(parsed.public_key()));
+    inscription.signature_valid = Some(parsed.get_sig_verified_hash().is_some());
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::absolute::LockTime;
+    use bitcoin::hashes::Hash;
+    use bitcoin::key::XOnlyPublicKey;
+    use bitcoin::opcodes::all::{OP_CHECKSIGVERIFY, OP_ENDIF, OP_IF, OP_NIP};
+    use bitcoin::opcodes::OP_FALSE;
+    use bitcoin::script::{self, PushBytesBuf};
+    use bitcoin::secp256k1::SecretKey;
+    use bitcoin::{Transaction, TxIn, Witness};
+    use citrea_primitives::compression::{compress_blob, BlobCompression, CompressionCodec};
+    use sov_rollup_interface::da::{DaDataBatchProof, DaDataLightClient, SequencerCommitment};
+
+    use super::{
+        inspect_hex, inspect_transactions, InscriptionContent, InscriptionKind, Namespace,
+    };
+    use crate::helpers::builders::sign_blob_with_private_key;
+    use crate::helpers::calculate_sha256;
+    use crate::helpers::parsers::BatchProofAggregate;
+    use crate::spec::RollupParams;
+
+    fn commitment_transaction(body: &[u8], signature: Vec<u8>, public_key: Vec<u8>) -> Transaction {
+        reveal_transaction(0, Some((signature, public_key)), body)
+    }
+
+    /// Reveal transaction of an inscription of `kind`, signed ones carry a signature and public key.
+    fn reveal_transaction(
+        kind: u16,
+        signed: Option<(Vec<u8>, Vec<u8>)>,
+        body: &[u8],
+    ) -> Transaction {
+        let mut builder = script::Builder::new()
+            .push_x_only_key(&XOnlyPublicKey::from_slice(&[1; 32]).unwrap())
+            .push_opcode(OP_CHECKSIGVERIFY)
+            .push_slice(PushBytesBuf::try_from(kind.to_le_bytes().to_vec()).unwrap())
+            .push_opcode(OP_FALSE)
+            .push_opcode(OP_IF);
+        if let Some((signature, public_key)) = signed {
+            builder = builder
+                .push_slice(PushBytesBuf::try_from(signature).unwrap())
+                .push_slice(PushBytesBuf::try_from(public_key).unwrap());
+        }
+        let reveal_script = builder
+            .push_slice(PushBytesBuf::try_from(body.to_vec()).unwrap())
+            .push_opcode(OP_ENDIF)
+            .push_slice(42i64.to_le_bytes())
+            .push_opcode(OP_NIP)
+            .into_script();
+        let mut control_block = vec![0xc0];
+        control_block.extend_from_slice(&[2; 32]);
+
+        Transaction {
+            version: bitcoin::transaction::Version(2),
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                witness: Witness::from_slice(&[
+                    vec![3; 64],
+                    reveal_script.into_bytes(),
+                    control_block,
+                ]),
+                ..Default::default()
+            }],
+            output: vec![],
+        }
+    }
+
+    #[test]
+    fn decodes_sequencer_commitment() {
+        let commitment = SequencerCommitment {
+            merkle_root: [7; 32],
+            l2_start_block_number: 10,
+            l2_end_block_number: 20,
+        };
+        let body = borsh::to_vec(&DaDataBatchProof::SequencerCommitment(commitment)).unwrap();
+        let da_private_key = SecretKey::from_slice(&[0xcd; 32]).unwrap();
+        let (signature, public_key) = sign_blob_with_private_key(&body, &da_private_key);
+        let tx = commitment_transaction(&body, signature.clone(), public_key.clone());
+        let params = RollupParams {
+            to_batch_proof_prefix: vec![],
+            to_light_client_prefix: vec![0xff; 32],
+            fork2_da_activation_height: 0,
+        };
+
+        let inscriptions =
+            inspect_hex(&bitcoin::consensus::encode::serialize_hex(&tx), &params).unwrap();
+        assert_eq!(inscriptions.len(), 1);
+        let inscription = &inscriptions[0];
+        assert_eq!(inscription.namespace, Namespace::BatchProof);
+        assert_eq!(inscription.kind, Some(InscriptionKind::SequencerCommitment));
+        assert_eq!(inscription.public_key, Some(hex::encode(&public_key)));
+        assert_eq!(inscription.signature_valid, Some(true));
+        assert_eq!(
+            inscription.content,
+            Some(InscriptionContent::SequencerCommitment {
+                merkle_root: [7; 32],
+                l2_start_block_number: 10,
+                l2_end_block_number: 20,
+            })
+        );
+
+        // a tampered body is still decoded, but fails the signature check
+        let mut tampered = body.clone();
+        tampered[1] = 8;
+        let tx = commitment_transaction(&tampered, signature, public_key);
+        let inscriptions =
+            inspect_hex(&bitcoin::consensus::encode::serialize_hex(&tx), &params).unwrap();
+        assert_eq!(inscriptions[0].signature_valid, Some(false));
+        assert!(inscriptions[0].content.is_some());
+
+        assert!(inspect_hex("not hex", &params).is_err());
+    }
+
+    /// Chunks, then the aggregate listing them, with the prefix of only the aggregate's wtxid.
+    fn chunked_transactions(
+        chunks: Vec<Vec<u8>>,
+        aggregate_body: impl FnOnce(Vec<[u8; 32]>) -> Vec<u8>,
+    ) -> (Vec<Transaction>, Vec<u8>) {
+        let mut txs: Vec<Transaction> = chunks
+            .iter()
+            .map(|chunk| reveal_transaction(2, None, chunk))
+            .collect();
+        let chunk_ids = txs
+            .iter()
+            .map(|tx| tx.compute_txid().to_byte_array())
+            .collect();
+
+        let body = aggregate_body(chunk_ids);
+        let da_private_key = SecretKey::from_slice(&[0xcd; 32]).unwrap();
+        let signed = sign_blob_with_private_key(&body, &da_private_key);
+        let aggregate = reveal_transaction(1, Some(signed), &body);
+        let prefix = aggregate.compute_wtxid().to_byte_array().to_vec();
+        txs.push(aggregate);
+        (txs, prefix)
+    }
+
+    #[test]
+    fn reassembles_batch_proof_aggregate() {
+        let commitment = SequencerCommitment {
+            merkle_root: [7; 32],
+            l2_start_block_number: 10,
+            l2_end_block_number: 20,
+        };
+        let body = borsh::to_vec(&DaDataBatchProof::SequencerCommitment(commitment)).unwrap();
+        let chunks = body.chunks(20).map(<[u8]>::to_vec).collect();
+        let (txs, prefix) = chunked_transactions(chunks, |chunk_ids| {
+            borsh::to_vec(&BatchProofAggregate {
+                chunk_ids,
+                body_hash: calculate_sha256(&body),
+            })
+            .unwrap()
+        });
+        let params = RollupParams {
+            to_batch_proof_prefix: prefix,
+            to_light_client_prefix: vec![0xff; 32],
+            fork2_da_activation_height: 0,
+        };
+
+        // the chunks are not in the namespace, only the aggregate is listed
+        let inscriptions = inspect_transactions(&txs, &params);
+        assert_eq!(inscriptions.len(), 1);
+        let inscription = &inscriptions[0];
+        assert_eq!(inscription.kind, Some(InscriptionKind::Aggregate));
+        assert_eq!(inscription.signature_valid, Some(true));
+        assert_eq!(inscription.chunk_txids.len(), 3);
+        assert_eq!(inscription.error, None);
+        assert_eq!(
+            inscription.content,
+            Some(InscriptionContent::SequencerCommitment {
+                merkle_root: [7; 32],
+                l2_start_block_number: 10,
+                l2_end_block_number: 20,
+            })
+        );
+
+        // a missing chunk stops the decoding
+        let inscriptions = inspect_transactions(&txs[1..], &params);
+        assert!(inscriptions[0].content.is_none());
+        assert!(inscriptions[0].error.is_some());
+    }
+
+    #[test]
+    fn reassembles_light_client_aggregate() {
+        let proof = vec![5u8; 1000];
+        let compressed = compress_blob(
+            &borsh::to_vec(&proof).unwrap(),
+            BlobCompression::Envelope(CompressionCodec::None),
+        );
+        let chunks = compressed
+            .chunks(400)
+            .map(|chunk| borsh::to_vec(&DaDataLightClient::Chunk(chunk.to_vec())).unwrap())
+            .collect();
+        let (txs, prefix) = chunked_transactions(chunks, |chunk_ids| {
+            borsh::to_vec(&DaDataLightClient::Aggregate(chunk_ids)).unwrap()
+        });
+        let params = RollupParams {
+            to_batch_proof_prefix: vec![0xff; 32],
+            to_light_client_prefix: prefix,
+            fork2_da_activation_height: 0,
+        };
+
+        let inscriptions = inspect_transactions(&txs, &params);
+        assert_eq!(inscriptions.len(), 1);
+        let inscription = &inscriptions[0];
+        assert_eq!(inscription.namespace, Namespace::LightClient);
+        assert_eq!(inscription.kind, Some(InscriptionKind::Aggregate));
+        assert_eq!(inscription.chunk_txids.len(), 3);
+        assert_eq!(
+            inscription.content,
+            Some(InscriptionContent::Proof {
+                size: proof.len(),
+                sha256: calculate_sha256(&proof),
+                proof,
+            })
+        );
+    }
+}
@@ -1,4 +1,5 @@
 pub mod helpers;
+pub mod network;
 pub mod spec;
 
 #[cfg(feature = "native")]
@@ -13,6 +14,12 @@ pub mod fee;
 #[cfg(feature = "native")]
 pub mod rpc;
 
+#[cfg(feature = "native")]
+pub mod signer;
+
+#[cfg(feature = "native")]
+pub mod inspect;
+
 pub mod verifier;
 
 #[cfg(feature = "native")]
@@ -1,5 +1,7 @@
-use std::collections::HashMap;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::collections::{HashMap, HashSet};
+use std::mem::discriminant;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
@@ -11,17 +13,20 @@ use bitcoincore_rpc::{Client, RpcApi};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tokio::select;
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::{Mutex, Notify, RwLock};
 use tokio::time::interval;
 use tokio_util::sync::CancellationToken;
-use tracing::{debug, error, info, instrument};
+use tracing::{debug, error, info, instrument, warn};
 
-use crate::service::FINALITY_DEPTH;
+use crate::fee::BumpFeeMethod;
 use crate::spec::utxo::UTXO;
 
 const DEFAULT_CHECK_INTERVAL: u64 = 60;
 const DEFAULT_HISTORY_LIMIT: usize = 1_000; // Keep track of last 1k txs
 const DEFAULT_MAX_HISTORY_SIZE: usize = 200_000_000; // Default max monitored tx total size to 200mb
+const STATE_FILE_NAME: &str = "monitoring_state.json";
+// Changes within this delay are persisted with a single write
+const PERSIST_DEBOUNCE: Duration = Duration::from_secs(1);
 
 type BlockHeight = u64;
 type Result<T> = std::result::Result<T, MonitorError>;
@@ -49,27 +54,65 @@ pub enum TxStatus {
     Evicted,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MonitoredTxKind {
     Commit,
     Reveal,
     Cpfp,
 }
 
-#[derive(Debug, Clone)]
+/// A fee bump of a monitored tx.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeBump {
+    /// The CPFP child or the RBF replacement.
+    pub txid: Txid,
+    pub method: BumpFeeMethod,
+    /// Requested fee rate in sat/vB.
+    pub fee_rate: f64,
+    pub timestamp: u64,
+    /// Whether the bump was made by the [`crate::fee::FeeBumpPolicy`] instead of an RPC call.
+    pub automatic: bool,
+}
+
+/// A status a monitored tx moved to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TxStatusChange {
+    pub timestamp: u64,
+    pub status: TxStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MonitoredTx {
     pub tx: Transaction,
     address: Option<Address<NetworkUnchecked>>,
     pub initial_broadcast: u64,
     pub initial_height: BlockHeight,
+    #[serde(skip, default = "Instant::now")]
     last_checked: Instant,
     pub status: TxStatus,
+    /// Every status the tx moved to, the initial one included.
+    /// Changes within the same status, e.g. more confirmations, are not recorded.
+    pub status_history: Vec<TxStatusChange>,
     pub prev_txid: Option<Txid>, // Previous tx in chain
     pub next_txid: Option<Txid>, // Next tx in chain
     pub kind: MonitoredTxKind,
+    pub fee_bumps: Vec<FeeBump>,
 }
 
 impl MonitoredTx {
+    fn set_status(&mut self, status: TxStatus) {
+        if discriminant(&self.status) != discriminant(&status) {
+            self.status_history.push(TxStatusChange {
+                timestamp: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs(),
+                status: status.clone(),
+            });
+        }
+        self.status = status;
+    }
+
     pub fn to_utxos(&self) -> Option<Vec<UTXO>> {
         let confirmations = match self.status {
             TxStatus::Pending { .. } => 0,
@@ -122,6 +165,10 @@ pub enum MonitorError {
     BitcoinRpcError(#[from] bitcoincore_rpc::Error),
     #[error(transparent)]
     BitcoinEncodeError(#[from] bitcoin::consensus::encode::Error),
+    #[error("Failed to persist monitoring state: {0}")]
+    PersistenceError(#[from] std::io::Error),
+    #[error("Invalid monitoring state: {0}")]
+    InvalidState(#[from] serde_json::Error),
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
@@ -141,6 +188,13 @@ impl Default for MonitoringConfig {
     }
 }
 
+/// Monitored txs as persisted to the tx backup dir.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PersistedState {
+    monitored_txs: HashMap<Txid, MonitoredTx>,
+    last_tx: Option<Txid>,
+}
+
 #[derive(Debug)]
 pub struct MonitoringService {
     client: Arc<Client>,
@@ -152,10 +206,22 @@ pub struct MonitoringService {
     // Keep track of total monitored transaction size
     // Only takes into account inner tx field from MonitoredTx
     total_size: AtomicUsize,
+    // Monitored txs are persisted to this file shortly after they change
+    state_path: PathBuf,
+    // Whether the state file lags behind the monitored txs
+    state_dirty: AtomicBool,
+    persist_requested: Notify,
+    // Confirmations after which a tx is finalized
+    finality_depth: u64,
 }
 
 impl MonitoringService {
-    pub fn new(client: Arc<Client>, config: Option<MonitoringConfig>) -> Self {
+    pub fn new(
+        client: Arc<Client>,
+        config: Option<MonitoringConfig>,
+        tx_backup_dir: PathBuf,
+        finality_depth: u64,
+    ) -> Self {
         Self {
             client,
             monitored_txs: RwLock::new(HashMap::new()),
@@ -163,22 +229,100 @@ impl MonitoringService {
             config: config.unwrap_or_default(),
             last_tx: Mutex::new(None),
             total_size: AtomicUsize::new(0),
+            state_path: tx_backup_dir.join(STATE_FILE_NAME),
+            state_dirty: AtomicBool::new(false),
+            persist_requested: Notify::new(),
+            finality_depth,
         }
     }
 
+    /// Restores monitored txs from the persisted state, or from the wallet
+    /// if there is no persisted state yet.
     pub async fn restore(&self) -> Result<()> {
         self.initialize_chainstate().await?;
-        self.restore_from_mempool().await
+        if self.restore_from_state().await? {
+            self.check_transactions().await
+        } else {
+            self.restore_from_mempool().await
+        }
+    }
+
+    async fn restore_from_state(&self) -> Result<bool> {
+        let state = match tokio::fs::read(&self.state_path).await {
+            Ok(state) => state,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+            Err(e) => return Err(e.into()),
+        };
+        let state: PersistedState = serde_json::from_slice(&state)?;
+
+        let total_size = state
+            .monitored_txs
+            .values()
+            .map(|tx| tx.tx.total_size())
+            .sum();
+        self.total_size.store(total_size, Ordering::SeqCst);
+        info!(
+            "Restored {} monitored txs from {}",
+            state.monitored_txs.len(),
+            self.state_path.display()
+        );
+        *self.monitored_txs.write().await = state.monitored_txs;
+        *self.last_tx.lock().await = state.last_tx;
+
+        Ok(true)
+    }
+
+    /// Marks the monitored txs as changed, `run` persists them after [`PERSIST_DEBOUNCE`].
+    fn request_persist(&self) {
+        self.state_dirty.store(true, Ordering::SeqCst);
+        self.persist_requested.notify_one();
+    }
+
+    /// Writes the monitored txs to the state file if they changed since the last write.
+    /// The state is written to a temporary file first, so a crash can not leave a partial state.
+    async fn persist(&self) -> Result<()> {
+        if !self.state_dirty.swap(false, Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        let state = PersistedState {
+            monitored_txs: self.monitored_txs.read().await.clone(),
+            last_tx: *self.last_tx.lock().await,
+        };
+        let state_path = self.state_path.clone();
+        let result = tokio::task::spawn_blocking(move || -> Result<()> {
+            let state = serde_json::to_vec(&state)?;
+            let tmp_path = state_path.with_extension("json.tmp");
+            std::fs::write(&tmp_path, state)?;
+            std::fs::rename(&tmp_path, &state_path)?;
+            Ok(())
+        })
+        .await
+        .map_err(std::io::Error::other)
+        .map_err(MonitorError::from)
+        .and_then(|result| result);
+
+        if result.is_err() {
+            // Retried on the next change or shutdown
+            self.state_dirty.store(true, Ordering::SeqCst);
+        }
+        result
+    }
+
+    async fn persist_or_log(&self) {
+        if let Err(e) = self.persist().await {
+            error!("Failed to persist monitoring state: {}", e);
+        }
     }
 
     async fn initialize_chainstate(&self) -> Result<()> {
         let current_height = self.client.get_block_count().await?;
         let current_tip = self.client.get_best_block_hash().await?;
 
-        let mut recent_blocks = Vec::with_capacity(FINALITY_DEPTH as usize);
+        let mut recent_blocks = Vec::with_capacity(self.finality_depth as usize);
         let mut current_hash: BlockHash;
 
-        for height in (0..FINALITY_DEPTH).map(|i| current_height.saturating_sub(i)) {
+        for height in (0..self.finality_depth).map(|i| current_height.saturating_sub(i)) {
             current_hash = self.client.get_block_hash(height).await?;
             recent_blocks.push((current_hash, height));
         }
@@ -196,7 +340,7 @@ impl MonitoringService {
     async fn restore_from_mempool(&self) -> Result<()> {
         let mut unspent = self
             .client
-            .list_unspent(None, Some(FINALITY_DEPTH as usize), None, None, None)
+            .list_unspent(None, Some(self.finality_depth as usize), None, None, None)
             .await?;
 
         unspent.sort_unstable_by_key(|utxo| {
@@ -216,8 +360,13 @@ impl MonitoringService {
                 biased;
                 _ = token.cancelled() => {
                     debug!("Monitoring service received shutdown signal");
+                    self.persist_or_log().await;
                     break;
                 }
+                _ = self.persist_requested.notified() => {
+                    tokio::time::sleep(PERSIST_DEBOUNCE).await;
+                    self.persist_or_log().await;
+                }
                 _ = interval.tick() => {
                     if let Err(e) = self.check_chain_state().await {
                         error!("Error checking chain state: {}", e);
@@ -294,28 +443,35 @@ impl MonitoringService {
         self.total_size.fetch_add(tx.total_size(), Ordering::SeqCst);
 
         let status = self.determine_tx_status(&tx_result).await?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
         let monitored_tx = MonitoredTx {
             tx,
             address: tx_result
                 .details
                 .first()
                 .and_then(|detail| detail.address.clone()),
-            initial_broadcast: SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
+            initial_broadcast: now,
             initial_height: current_height,
             last_checked: Instant::now(),
+            status_history: vec![TxStatusChange {
+                timestamp: now,
+                status: status.clone(),
+            }],
             status,
             prev_txid,
             next_txid,
             kind,
+            fee_bumps: vec![],
         };
 
         self.monitored_txs.write().await.insert(txid, monitored_tx);
         *self.last_tx.lock().await = Some(txid);
         debug!("[monitor_transaction_chain] setting last_tx : {:?}", txid);
 
+        self.request_persist();
         Ok(())
     }
 
@@ -336,6 +492,10 @@ impl MonitoringService {
         self.total_size.fetch_add(tx.total_size(), Ordering::SeqCst);
 
         let status = self.determine_tx_status(&tx_result).await?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
 
         let new_tx = MonitoredTx {
             tx,
@@ -343,22 +503,24 @@ impl MonitoringService {
                 .details
                 .first()
                 .and_then(|detail| detail.address.clone()),
-            initial_broadcast: SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
+            initial_broadcast: now,
             initial_height: current_height,
             last_checked: Instant::now(),
+            status_history: vec![TxStatusChange {
+                timestamp: now,
+                status: status.clone(),
+            }],
             status,
             kind: monitored_tx.kind,
             prev_txid: monitored_tx.prev_txid,
             next_txid: monitored_tx.next_txid,
+            fee_bumps: vec![],
         };
 
         {
             let mut monitored_txs = self.monitored_txs.write().await;
             if let Some(prev_tx) = monitored_txs.get_mut(&prev_txid) {
-                prev_tx.status = TxStatus::Replaced { by_txid: new_txid };
+                prev_tx.set_status(TxStatus::Replaced { by_txid: new_txid });
             }
             monitored_txs.insert(new_txid, new_tx);
         }
@@ -370,6 +532,7 @@ impl MonitoringService {
             }
         }
 
+        self.request_persist();
         Ok(())
     }
 
@@ -386,7 +549,7 @@ impl MonitoringService {
             let mut reorg_detected = false;
             let mut reorg_depth = 0;
 
-            for i in 1..=FINALITY_DEPTH {
+            for i in 1..=self.finality_depth {
                 let height = new_height.saturating_sub(i);
                 current_hash = self.client.get_block_hash(height).await?;
                 new_blocks.push((current_hash, height));
@@ -418,47 +581,57 @@ impl MonitoringService {
     }
 
     async fn handle_reorg(&self, depth: u64) -> Result<()> {
-        let mut txs = self.monitored_txs.write().await;
-
-        for (txid, tx) in txs.iter_mut() {
-            if let TxStatus::Confirmed { confirmations, .. } = tx.status {
-                if confirmations <= depth {
-                    let tx_result = self.client.get_transaction(txid, None).await?;
-                    tx.status = self.determine_tx_status(&tx_result).await?;
-
-                    if let TxStatus::Pending { .. } = tx.status {
-                        info!("Rebroadcasting tx {tx:?}");
-                        let raw_tx = self.client.get_raw_transaction_hex(txid, None).await?;
-                        self.client.send_raw_transaction(raw_tx).await?;
+        {
+            let mut txs = self.monitored_txs.write().await;
+
+            for (txid, tx) in txs.iter_mut() {
+                if let TxStatus::Confirmed { confirmations, .. } = tx.status {
+                    if confirmations <= depth {
+                        let tx_result = self.client.get_transaction(txid, None).await?;
+                        tx.set_status(self.determine_tx_status(&tx_result).await?);
+
+                        if let TxStatus::Pending { .. } = tx.status {
+                            info!("Rebroadcasting tx {tx:?}");
+                            let raw_tx = self.client.get_raw_transaction_hex(txid, None).await?;
+                            self.client.send_raw_transaction(raw_tx).await?;
+                        }
                     }
                 }
             }
         }
 
+        self.request_persist();
         Ok(())
     }
 
     #[instrument(skip(self))]
     async fn check_transactions(&self) -> Result<()> {
-        let mut txs = self.monitored_txs.write().await;
-
-        for (txid, monitored_tx) in txs.iter_mut() {
-            match &monitored_tx.status {
-                // Check non-finalized TXs
-                TxStatus::Pending { .. }
-                | TxStatus::Confirmed { .. }
-                | TxStatus::Replaced { .. } => {
-                    let tx_result = self.client.get_transaction(txid, None).await?;
-                    let new_status = self.determine_tx_status(&tx_result).await?;
-
-                    monitored_tx.status = new_status;
+        let mut changed = false;
+        {
+            let mut txs = self.monitored_txs.write().await;
+
+            for (txid, monitored_tx) in txs.iter_mut() {
+                match &monitored_tx.status {
+                    // Check non-finalized TXs
+                    TxStatus::Pending { .. }
+                    | TxStatus::Confirmed { .. }
+                    | TxStatus::Replaced { .. } => {
+                        let tx_result = self.client.get_transaction(txid, None).await?;
+                        let new_status = self.determine_tx_status(&tx_result).await?;
+
+                        changed |= monitored_tx.status != new_status;
+                        monitored_tx.set_status(new_status);
+                    }
+                    _ => {}
                 }
-                _ => {}
-            }
 
-            monitored_tx.last_checked = Instant::now();
+                monitored_tx.last_checked = Instant::now();
+            }
         }
 
+        if changed {
+            self.request_persist();
+        }
         Ok(())
     }
 
@@ -476,7 +649,7 @@ impl MonitoringService {
                 .map(|header| header.height as u64)
                 .unwrap_or(0);
 
-            if confirmations >= FINALITY_DEPTH {
+            if confirmations >= self.finality_depth {
                 TxStatus::Finalized {
                     block_hash,
                     block_height,
@@ -509,8 +682,17 @@ impl MonitoringService {
     }
 
     async fn prune_old_transactions(&self) {
+        if self.prune_finalized_transactions().await {
+            self.request_persist();
+        }
+    }
+
+    /// Removes the oldest finalized txs until the history limits are met.
+    /// Returns whether any tx was removed.
+    async fn prune_finalized_transactions(&self) -> bool {
         let mut txs = self.monitored_txs.write().await;
         let current_size = self.total_size.load(Ordering::SeqCst);
+        let mut pruned = false;
 
         if txs.len() > self.config.history_limit || current_size > self.config.max_history_size {
             let to_remove: Vec<_> = txs
@@ -532,9 +714,12 @@ impl MonitoringService {
                 if let Some(removed_tx) = txs.remove(&txid) {
                     let tx_size = removed_tx.tx.total_size();
                     self.total_size.fetch_sub(tx_size, Ordering::SeqCst);
+                    pruned = true;
                 }
             }
         }
+
+        pruned
     }
 
     pub async fn get_tx_status(&self, txid: &Txid) -> Option<TxStatus> {
@@ -555,10 +740,250 @@ impl MonitoringService {
         Some((last_txid, tx))
     }
 
+    /// Returns the commit/reveal pair `txid` belongs to, in order, together with
+    /// its CPFP txs and every replaced version of them.
+    pub async fn get_tx_chain(&self, txid: &Txid) -> Vec<(Txid, MonitoredTx)> {
+        let monitored_txs = self.monitored_txs.read().await;
+        let Some(mut tx) = monitored_txs.get(txid) else {
+            return vec![];
+        };
+
+        // Walk back to the commit tx of the pair
+        let mut start = *txid;
+        while tx.kind != MonitoredTxKind::Commit {
+            let Some(prev) = tx.prev_txid.and_then(|prev| monitored_txs.get(&prev)) else {
+                warn!("Start of the tx chain of {} is not monitored anymore", txid);
+                break;
+            };
+            start = tx.prev_txid.expect("Checked above");
+            tx = prev;
+        }
+
+        // Walk forward, following replacements, until the commit tx of the next pair
+        let mut chain = vec![];
+        let mut visited = HashSet::new();
+        let mut current = Some(start);
+        while let Some(current_txid) = current {
+            let Some(tx) = monitored_txs.get(&current_txid) else {
+                break;
+            };
+            if !visited.insert(current_txid)
+                || (!chain.is_empty() && tx.kind == MonitoredTxKind::Commit)
+            {
+                break;
+            }
+            chain.push((current_txid, tx.clone()));
+
+            let replaced_by = tx
+                .status_history
+                .iter()
+                .find_map(|change| match change.status {
+                    TxStatus::Replaced { by_txid } => Some(by_txid),
+                    _ => None,
+                });
+            current = replaced_by.or(tx.next_txid);
+        }
+
+        chain
+    }
+
+    pub async fn record_fee_bump(&self, txid: &Txid, fee_bump: FeeBump) {
+        {
+            let mut monitored_txs = self.monitored_txs.write().await;
+            if let Some(tx) = monitored_txs.get_mut(txid) {
+                tx.fee_bumps.push(fee_bump);
+            }
+        }
+        self.request_persist();
+    }
+
     pub async fn set_next_tx(&self, txid: &Txid, next_txid: Txid) {
-        let mut monitored_txs = self.monitored_txs.write().await;
-        if let Some(parent) = monitored_txs.get_mut(txid) {
-            parent.next_txid = Some(next_txid);
+        {
+            let mut monitored_txs = self.monitored_txs.write().await;
+            if let Some(parent) = monitored_txs.get_mut(txid) {
+                parent.next_txid = Some(next_txid);
+            }
+        }
+        self.request_persist();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    use bitcoin::absolute::LockTime;
+    use bitcoin::hashes::Hash;
+    use bitcoin::transaction::Version;
+    use bitcoin::{Transaction, TxIn, Txid};
+    use bitcoincore_rpc::{Auth, Client};
+
+    use super::{MonitoredTx, MonitoredTxKind, MonitoringService, TxStatus, STATE_FILE_NAME};
+
+    async fn monitoring_service(tx_backup_dir: &Path) -> MonitoringService {
+        // None of the tested methods reach the node
+        let client = Client::new("http://127.0.0.1:1", Auth::None).await.unwrap();
+        MonitoringService::new(Arc::new(client), None, tx_backup_dir.to_path_buf(), 6)
+    }
+
+    fn monitored_tx(
+        lock_time: u32,
+        kind: MonitoredTxKind,
+        prev_txid: Option<Txid>,
+        next_txid: Option<Txid>,
+    ) -> (Txid, MonitoredTx) {
+        let tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::from_consensus(lock_time),
+            input: vec![TxIn::default()],
+            output: vec![],
+        };
+        let monitored_tx = MonitoredTx {
+            tx,
+            address: None,
+            initial_broadcast: lock_time as u64,
+            initial_height: 0,
+            last_checked: Instant::now(),
+            status: TxStatus::Pending {
+                in_mempool: true,
+                base_fee: 0,
+                timestamp: 0,
+            },
+            status_history: vec![],
+            prev_txid,
+            next_txid,
+            kind,
+            fee_bumps: vec![],
+        };
+        (monitored_tx.tx.compute_txid(), monitored_tx)
+    }
+
+    async fn insert(service: &MonitoringService, (txid, tx): (Txid, MonitoredTx)) {
+        service.monitored_txs.write().await.insert(txid, tx);
+    }
+
+    #[tokio::test]
+    async fn persisted_state_is_restored_after_restart() {
+        let tx_backup_dir = tempfile::tempdir().unwrap();
+        let service = monitoring_service(tx_backup_dir.path()).await;
+
+        let (commit_txid, commit) = monitored_tx(1, MonitoredTxKind::Commit, None, None);
+        let (reveal_txid, reveal) =
+            monitored_tx(2, MonitoredTxKind::Reveal, Some(commit_txid), None);
+        insert(&service, (commit_txid, commit)).await;
+        insert(&service, (reveal_txid, reveal)).await;
+        *service.last_tx.lock().await = Some(reveal_txid);
+
+        // Nothing is written before a change is requested
+        service.persist().await.unwrap();
+        assert!(!tx_backup_dir.path().join(STATE_FILE_NAME).exists());
+
+        service.request_persist();
+        service.persist().await.unwrap();
+        assert!(tx_backup_dir.path().join(STATE_FILE_NAME).exists());
+        assert!(!tx_backup_dir
+            .path()
+            .join(STATE_FILE_NAME)
+            .with_extension("json.tmp")
+            .exists());
+
+        let restarted = monitoring_service(tx_backup_dir.path()).await;
+        assert!(restarted.restore_from_state().await.unwrap());
+
+        let restored_txs = restarted.get_monitored_txs().await;
+        assert_eq!(restored_txs.len(), 2);
+        assert_eq!(restored_txs[&reveal_txid].prev_txid, Some(commit_txid));
+        assert_eq!(restored_txs[&reveal_txid].kind, MonitoredTxKind::Reveal);
+        assert_eq!(
+            restarted.get_last_tx().await.map(|(txid, _)| txid),
+            Some(reveal_txid)
+        );
+        assert_eq!(
+            restarted.total_size.load(Ordering::SeqCst),
+            restored_txs
+                .values()
+                .map(|tx| tx.tx.total_size())
+                .sum::<usize>()
+        );
+    }
+
+    #[tokio::test]
+    async fn restore_without_state_file() {
+        let tx_backup_dir = tempfile::tempdir().unwrap();
+        let service = monitoring_service(tx_backup_dir.path()).await;
+
+        assert!(!service.restore_from_state().await.unwrap());
+        assert!(service.get_monitored_txs().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn tx_chain_follows_replacements_and_cpfp() {
+        let tx_backup_dir = tempfile::tempdir().unwrap();
+        let service = monitoring_service(tx_backup_dir.path()).await;
+
+        let (commit_txid, _) = monitored_tx(1, MonitoredTxKind::Commit, None, None);
+        let (reveal_txid, _) = monitored_tx(2, MonitoredTxKind::Reveal, Some(commit_txid), None);
+        let (replacement_txid, _) = monitored_tx(3, MonitoredTxKind::Reveal, None, None);
+        let (cpfp_txid, _) = monitored_tx(4, MonitoredTxKind::Cpfp, None, None);
+        let (next_commit_txid, _) = monitored_tx(5, MonitoredTxKind::Commit, None, None);
+
+        insert(
+            &service,
+            monitored_tx(1, MonitoredTxKind::Commit, None, Some(reveal_txid)),
+        )
+        .await;
+        let (_, mut reveal) = monitored_tx(2, MonitoredTxKind::Reveal, Some(commit_txid), None);
+        reveal.set_status(TxStatus::Replaced {
+            by_txid: replacement_txid,
+        });
+        insert(&service, (reveal_txid, reveal)).await;
+        insert(
+            &service,
+            monitored_tx(
+                3,
+                MonitoredTxKind::Reveal,
+                Some(commit_txid),
+                Some(cpfp_txid),
+            ),
+        )
+        .await;
+        insert(
+            &service,
+            monitored_tx(4, MonitoredTxKind::Cpfp, Some(replacement_txid), None),
+        )
+        .await;
+        insert(
+            &service,
+            monitored_tx(5, MonitoredTxKind::Commit, Some(replacement_txid), None),
+        )
+        .await;
+
+        let expected = vec![commit_txid, reveal_txid, replacement_txid, cpfp_txid];
+        for txid in [commit_txid, reveal_txid, replacement_txid, cpfp_txid] {
+            let chain: Vec<Txid> = service
+                .get_tx_chain(&txid)
+                .await
+                .into_iter()
+                .map(|(txid, _)| txid)
+                .collect();
+            assert_eq!(chain, expected);
         }
+
+        // The next pair starts a chain of its own
+        let chain: Vec<Txid> = service
+            .get_tx_chain(&next_commit_txid)
+            .await
+            .into_iter()
+            .map(|(txid, _)| txid)
+            .collect();
+        assert_eq!(chain, vec![next_commit_txid]);
+
+        assert!(service
+            .get_tx_chain(&Txid::from_byte_array([0; 32]))
+            .await
+            .is_empty());
     }
 }
@@ -0,0 +1,221 @@
+//! Parameters of the bitcoin networks the rollup can run on.
+
+use core::str::FromStr;
+
+use bitcoin::{BlockHash, Network};
+use citrea_primitives::forks::FORK2_DA_ACTIVATION_HEIGHT;
+use citrea_primitives::{TO_BATCH_PROOF_PREFIX, TO_LIGHT_CLIENT_PREFIX};
+use serde::{Deserialize, Serialize};
+
+use crate::spec::RollupParams;
+
+/// Confirmations after which a block of the named networks is finalized.
+pub const FINALITY_DEPTH: u64 = 30; // blocks
+
+const MEMPOOL_SPACE_URL: &str = "https://mempool.space/";
+
+/// Reveal txid prefixes of the public networks, regardless of the
+/// `short-prefix` feature the guests of local networks are built with.
+const PUBLIC_TO_BATCH_PROOF_PREFIX: &[u8] = &[1, 1];
+const PUBLIC_TO_LIGHT_CLIENT_PREFIX: &[u8] = &[2, 2];
+
+const MAINNET_GENESIS_HASH: &str =
+    "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f";
+const TESTNET4_GENESIS_HASH: &str =
+    "00000000da84f2bafbbc53dee25a72ae507ff4914b867c565be350b0da8bf043";
+const SIGNET_GENESIS_HASH: &str =
+    "00000008819873e925422c1ff0f99f7cc9bbb232af63a077a480a3633bee1ef6";
+const REGTEST_GENESIS_HASH: &str =
+    "0f9188f13cb7b2c71f2a335e3a4fc328bf5beb436012afca590b1a11466e2206";
+
+/// A bitcoin network the rollup runs on.
+///
+/// The public networks use the reveal txid prefixes of their released zk guests,
+/// regtest uses the ones the local guests are built with,
+/// see [`NetworkProfile::guest_rollup_params`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NetworkProfile {
+    Mainnet,
+    /// Testnet4, whose nodes report the `testnet` network.
+    Testnet4,
+    /// The default signet.
+    Signet,
+    Regtest,
+    Custom(CustomNetworkProfile),
+}
+
+/// Parameters of a network which is not one of the named ones, e.g. a custom signet.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CustomNetworkProfile {
+    /// Network reported by the bitcoin node.
+    pub network: Network,
+    /// Prefix of the reveal txids in the batch proof namespace, hex encoded.
+    #[serde(with = "hex::serde")]
+    pub to_batch_proof_prefix: Vec<u8>,
+    /// Prefix of the reveal txids in the light client namespace, hex encoded.
+    #[serde(with = "hex::serde")]
+    pub to_light_client_prefix: Vec<u8>,
+    /// Confirmations after which a block is finalized.
+    pub finality_depth: u64,
+    /// mempool.space compatible API used for fee estimation by default.
+    pub mempool_space_url: Option<String>,
+    /// Hash of the genesis block, checked against the bitcoin node on startup.
+    pub genesis_block_hash: BlockHash,
+}
+
+impl NetworkProfile {
+    /// Reveal txid prefixes the zk guests are compiled with, which are short under the
+    /// `short-prefix` feature. Nodes refuse profiles whose [`NetworkProfile::rollup_params`]
+    /// differ from these, as the guests could not verify their blobs.
+    pub fn guest_rollup_params() -> RollupParams {
+        RollupParams {
+            to_light_client_prefix: TO_LIGHT_CLIENT_PREFIX.to_vec(),
+            to_batch_proof_prefix: TO_BATCH_PROOF_PREFIX.to_vec(),
+            fork2_da_activation_height: FORK2_DA_ACTIVATION_HEIGHT,
+        }
+    }
+
+    /// Network reported by the bitcoin node.
+    pub fn network(&self) -> Network {
+        match self {
+            Self::Mainnet => Network::Bitcoin,
+            Self::Testnet4 => Network::Testnet,
+            Self::Signet => Network::Signet,
+            Self::Regtest => Network::Regtest,
+            Self::Custom(profile) => profile.network,
+        }
+    }
+
+    /// Reveal txid prefixes of the DA namespaces.
+    pub fn rollup_params(&self) -> RollupParams {
+        match self {
+            Self::Custom(profile) => RollupParams {
+                to_light_client_prefix: profile.to_light_client_prefix.clone(),
+                to_batch_proof_prefix: profile.to_batch_proof_prefix.clone(),
+                fork2_da_activation_height: FORK2_DA_ACTIVATION_HEIGHT,
+            },
+            Self::Regtest => Self::guest_rollup_params(),
+            Self::Mainnet | Self::Testnet4 | Self::Signet => RollupParams {
+                to_light_client_prefix: PUBLIC_TO_LIGHT_CLIENT_PREFIX.to_vec(),
+                to_batch_proof_prefix: PUBLIC_TO_BATCH_PROOF_PREFIX.to_vec(),
+                fork2_da_activation_height: FORK2_DA_ACTIVATION_HEIGHT,
+            },
+        }
+    }
+
+    /// Confirmations after which a block is finalized.
+    pub fn finality_depth(&self) -> u64 {
+        match self {
+            Self::Custom(profile) => profile.finality_depth,
+            _ => FINALITY_DEPTH,
+        }
+    }
+
+    /// mempool.space compatible API used for fee estimation by default, if there is one.
+    pub fn mempool_space_url(&self) -> Option<String> {
+        match self {
+            Self::Mainnet => Some(MEMPOOL_SPACE_URL.to_string()),
+            Self::Testnet4 => Some(format!("{}testnet4/", MEMPOOL_SPACE_URL)),
+            Self::Signet => Some(format!("{}signet/", MEMPOOL_SPACE_URL)),
+            Self::Regtest => None,
+            Self::Custom(profile) => profile.mempool_space_url.clone(),
+        }
+    }
+
+    /// Hash of the genesis block.
+    pub fn genesis_block_hash(&self) -> BlockHash {
+        let hash = match self {
+            Self::Mainnet => MAINNET_GENESIS_HASH,
+            Self::Testnet4 => TESTNET4_GENESIS_HASH,
+            Self::Signet => SIGNET_GENESIS_HASH,
+            Self::Regtest => REGTEST_GENESIS_HASH,
+            Self::Custom(profile) => return profile.genesis_block_hash,
+        };
+        BlockHash::from_str(hash).expect("Genesis hashes of the named networks are valid")
+    }
+}
+
+impl From<Network> for NetworkProfile {
+    /// Named profile of a network, testnet is assumed to be testnet4.
+    fn from(network: Network) -> Self {
+        match network {
+            Network::Bitcoin => Self::Mainnet,
+            Network::Testnet => Self::Testnet4,
+            Network::Signet => Self::Signet,
+            _ => Self::Regtest,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::Network;
+
+    use super::{NetworkProfile, PUBLIC_TO_BATCH_PROOF_PREFIX, PUBLIC_TO_LIGHT_CLIENT_PREFIX};
+
+    #[test]
+    fn named_profiles() {
+        for network in [
+            Network::Bitcoin,
+            Network::Testnet,
+            Network::Signet,
+            Network::Regtest,
+        ] {
+            let profile = NetworkProfile::from(network);
+            assert_eq!(profile.network(), network);
+        }
+
+        // Public networks don't depend on the prefixes the local guests are built with
+        for profile in [
+            NetworkProfile::Mainnet,
+            NetworkProfile::Testnet4,
+            NetworkProfile::Signet,
+        ] {
+            let params = profile.rollup_params();
+            assert_eq!(params.to_batch_proof_prefix, PUBLIC_TO_BATCH_PROOF_PREFIX);
+            assert_eq!(params.to_light_client_prefix, PUBLIC_TO_LIGHT_CLIENT_PREFIX);
+        }
+        assert_eq!(
+            NetworkProfile::Regtest.rollup_params(),
+            NetworkProfile::guest_rollup_params()
+        );
+
+        assert_eq!(
+            NetworkProfile::Mainnet.genesis_block_hash(),
+            bitcoin::constants::genesis_block(Network::Bitcoin).block_hash()
+        );
+        assert_eq!(
+            NetworkProfile::Regtest.genesis_block_hash(),
+            bitcoin::constants::genesis_block(Network::Regtest).block_hash()
+        );
+        assert_eq!(NetworkProfile::Regtest.mempool_space_url(), None);
+    }
+
+    #[test]
+    fn custom_profile_from_json() {
+        let profile: NetworkProfile = serde_json::from_str(
+            r#"{ "custom": {
+                "network": "signet",
+                "to_batch_proof_prefix": "0303",
+                "to_light_client_prefix": "04",
+                "finality_depth": 6,
+                "mempool_space_url": null,
+                "genesis_block_hash": "00000008819873e925422c1ff0f99f7cc9bbb232af63a077a480a3633bee1ef6"
+            } }"#,
+        )
+        .unwrap();
+
+        assert_eq!(profile.network(), Network::Signet);
+        assert_eq!(profile.rollup_params().to_batch_proof_prefix, vec![3, 3]);
+        assert_eq!(profile.rollup_params().to_light_client_prefix, vec![4]);
+        assert_eq!(profile.finality_depth(), 6);
+        assert_eq!(
+            profile.genesis_block_hash(),
+            NetworkProfile::Signet.genesis_block_hash()
+        );
+
+        let profile: NetworkProfile = serde_json::from_str("\"testnet4\"").unwrap();
+        assert_eq!(profile, NetworkProfile::Testnet4);
+    }
+}
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
-use bitcoin::Txid;
+use bitcoin::address::NetworkUnchecked;
+use bitcoin::{Address, Txid};
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use jsonrpsee::types::error::{INTERNAL_ERROR_CODE, INTERNAL_ERROR_MSG};
@@ -8,12 +9,14 @@ use jsonrpsee::types::ErrorObjectOwned;
 use serde::{Deserialize, Serialize};
 
 use crate::fee::BumpFeeMethod;
-use crate::monitoring::{MonitoredTx, TxStatus};
+use crate::monitoring::{FeeBump, MonitoredTx, MonitoredTxKind, TxStatus, TxStatusChange};
 use crate::service::BitcoinService;
+use crate::spec::utxo::UTXO;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MonitoredTxResponse {
     pub txid: Txid,
+    pub kind: MonitoredTxKind,
     pub vsize: usize,
     pub base_fee: Option<u64>,
     pub initial_broadcast: u64,
@@ -21,6 +24,8 @@ pub struct MonitoredTxResponse {
     pub prev_txid: Option<Txid>,
     pub next_txid: Option<Txid>,
     pub status: TxStatus,
+    pub status_history: Vec<TxStatusChange>,
+    pub fee_bumps: Vec<FeeBump>,
 }
 
 impl From<(Txid, MonitoredTx)> for MonitoredTxResponse {
@@ -33,6 +38,7 @@ impl From<(Txid, MonitoredTx)> for MonitoredTxResponse {
 
         MonitoredTxResponse {
             txid,
+            kind: tx.kind,
             base_fee,
             vsize: tx.tx.vsize(),
             initial_broadcast: tx.initial_broadcast,
@@ -40,6 +46,35 @@ impl From<(Txid, MonitoredTx)> for MonitoredTxResponse {
             prev_txid: tx.prev_txid,
             next_txid: tx.next_txid,
             status: tx.status,
+            status_history: tx.status_history,
+            fee_bumps: tx.fee_bumps,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletUtxoResponse {
+    pub txid: Txid,
+    pub vout: u32,
+    pub address: Option<Address<NetworkUnchecked>>,
+    pub amount: u64,
+    pub confirmations: u32,
+    /// Spent by a DA tx which is being built or sent.
+    pub reserved: bool,
+    /// May fund the next DA tx.
+    pub selectable: bool,
+}
+
+impl From<(UTXO, bool, bool)> for WalletUtxoResponse {
+    fn from((utxo, reserved, selectable): (UTXO, bool, bool)) -> Self {
+        WalletUtxoResponse {
+            txid: utxo.tx_id,
+            vout: utxo.vout,
+            address: utxo.address,
+            amount: utxo.amount,
+            confirmations: utxo.confirmations,
+            reserved,
+            selectable,
         }
     }
 }
@@ -58,6 +93,13 @@ pub trait DaRpc {
     #[method(name = "getLastMonitoredTx")]
     async fn da_get_last_monitored_tx(&self) -> RpcResult<Option<MonitoredTxResponse>>;
 
+    /// Returns the commit/reveal pair of the tx with its CPFP and replacement txs, in order.
+    #[method(name = "getTxChain")]
+    async fn da_get_tx_chain(&self, txid: Txid) -> RpcResult<Vec<MonitoredTxResponse>>;
+
+    #[method(name = "getWalletUtxos")]
+    async fn da_get_wallet_utxos(&self) -> RpcResult<Vec<WalletUtxoResponse>>;
+
     #[method(name = "bumpFeeCpfp")]
     async fn da_bump_transaction_fee_cpfp(
         &self,
@@ -110,6 +152,31 @@ impl DaRpcServer for DaRpcServerImpl {
         Ok(self.da.monitoring.get_last_tx().await.map(Into::into))
     }
 
+    async fn da_get_tx_chain(&self, txid: Txid) -> RpcResult<Vec<MonitoredTxResponse>> {
+        Ok(self
+            .da
+            .monitoring
+            .get_tx_chain(&txid)
+            .await
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
+    async fn da_get_wallet_utxos(&self) -> RpcResult<Vec<WalletUtxoResponse>> {
+        self.da
+            .get_wallet_utxos()
+            .await
+            .map(|utxos| utxos.into_iter().map(Into::into).collect())
+            .map_err(|e| {
+                ErrorObjectOwned::owned(
+                    INTERNAL_ERROR_CODE,
+                    INTERNAL_ERROR_MSG,
+                    Some(format!("{e}",)),
+                )
+            })
+    }
+
     async fn da_bump_transaction_fee_cpfp(
         &self,
         txid: Option<Txid>,
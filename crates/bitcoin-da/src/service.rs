@@ -1,12 +1,14 @@
 // fix clippy for tracing::instrument
 #![allow(clippy::blocks_in_conditions)]
 
+use core::ops::Deref;
 use core::result::Result::Ok;
 use core::str::FromStr;
 use core::time::Duration;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use anyhow::{anyhow, bail, Context, Result};
 use async_trait::async_trait;
@@ -16,17 +18,26 @@ use bitcoin::block::Header;
 use bitcoin::consensus::{encode, Decodable};
 use bitcoin::hashes::Hash;
 use bitcoin::secp256k1::SecretKey;
-use bitcoin::{Amount, BlockHash, CompactTarget, Transaction, Txid, Wtxid};
-use bitcoincore_rpc::json::{SignRawTransactionInput, TestMempoolAcceptResult};
+use bitcoin::{
+    Amount, BlockHash, CompactTarget, OutPoint, Psbt, ScriptBuf, Sequence, Transaction, TxOut,
+    Txid, Wtxid,
+};
+use bitcoincore_rpc::json::{
+    CreateRawTransactionInput, SignRawTransactionInput, TestMempoolAcceptResult,
+    WalletCreateFundedPsbtOptions,
+};
 use bitcoincore_rpc::{Auth, Client, Error, RpcApi, RpcError};
 use borsh::BorshDeserialize;
-use citrea_primitives::compression::{compress_blob, decompress_blob};
+use citrea_primitives::compression::{compress_blob, decompress_blob, decompress_legacy_blob};
+use citrea_primitives::forks::blob_compression;
 use citrea_primitives::MAX_TXBODY_SIZE;
 use serde::{Deserialize, Serialize};
 use sov_rollup_interface::da::{
-    DaData, DaDataBatchProof, DaDataLightClient, DaNamespace, DaSpec, SequencerCommitment,
+    BlockHeaderTrait, DaData, DaDataBatchProof, DaDataLightClient, DaNamespace,
+    DaPublicationReceipt, DaSpec, SequencerCommitment,
 };
 use sov_rollup_interface::services::da::{DaService, SenderWithNotifier};
+use sov_rollup_interface::spec::SpecId;
 use sov_rollup_interface::zk::Proof;
 use tokio::select;
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
@@ -34,34 +45,42 @@ use tokio::sync::oneshot::channel as oneshot_channel;
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, instrument, trace, warn};
 
-use crate::fee::{BumpFeeMethod, FeeService};
+use crate::fee::{BumpFeeMethod, FeeBumpPolicy, FeeEstimatorConfig, FeeService};
 use crate::helpers::builders::batch_proof_namespace::{
-    create_seqcommitment_transactions, BatchProvingTxs,
+    create_chunked_batchproof_transactions, create_seqcommitment_transactions, BatchProvingTxs,
+    MAX_BATCH_PROOF_CHUNKS,
 };
 use crate::helpers::builders::light_client_proof_namespace::{
     create_zkproof_transactions, LightClientTxs, RawLightClientData,
 };
 use crate::helpers::builders::{TxListWithReveal, TxWithId};
+use crate::helpers::coin_selection::{CoinSelectionConfig, ConsolidationConfig};
 use crate::helpers::merkle_tree;
 use crate::helpers::merkle_tree::BitcoinMerkleTree;
 use crate::helpers::parsers::{
-    parse_batch_proof_transaction, parse_light_client_transaction, ParsedBatchProofTransaction,
+    collect_batch_proof_chunks, parse_batch_proof_aggregate, parse_batch_proof_transaction,
+    parse_light_client_transaction, reassemble_batch_proof_aggregate, ParsedBatchProofTransaction,
     ParsedLightClientTransaction, VerifyParsed,
 };
-use crate::monitoring::{MonitoredTxKind, MonitoringConfig, MonitoringService, TxStatus};
+use crate::monitoring::{
+    FeeBump, MonitoredTx, MonitoredTxKind, MonitoringConfig, MonitoringService, TxStatus,
+};
+use crate::network::NetworkProfile;
+pub use crate::network::FINALITY_DEPTH;
+use crate::signer::{DaSigner, DaSignerConfig, InProcessSigner};
 use crate::spec::blob::BlobWithSender;
 use crate::spec::block::BitcoinBlock;
 use crate::spec::header::HeaderWrapper;
 use crate::spec::header_stream::BitcoinHeaderStream;
-use crate::spec::proof::InclusionMultiProof;
+use crate::spec::proof::{InclusionMultiProof, WtxidInclusionProof};
 use crate::spec::transaction::TransactionWrapper;
 use crate::spec::utxo::UTXO;
 use crate::spec::{BitcoinSpec, RollupParams};
 use crate::verifier::BitcoinVerifier;
 use crate::REVEAL_OUTPUT_AMOUNT;
 
-pub const FINALITY_DEPTH: u64 = 30; // blocks
 const POLLING_INTERVAL: u64 = 10; // seconds
+const FEE_BUMP_CHECK_INTERVAL: u64 = 60; // seconds
 
 /// Runtime configuration for the DA service
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
@@ -77,10 +96,31 @@ pub struct BitcoinServiceConfig {
     // da private key of the sequencer
     pub da_private_key: Option<String>,
 
+    // signer of DA blobs and commit txs,
+    // defaults to an in-process signer with da_private_key if not set
+    pub signer: Option<DaSignerConfig>,
+
     // absolute path to the directory where the txs will be written to
     pub tx_backup_dir: String,
 
     pub monitoring: Option<MonitoringConfig>,
+
+    // automatic fee bumps of pending DA txs, disabled if not set
+    pub fee_bump: Option<FeeBumpPolicy>,
+
+    // fee rate estimation, defaults to the estimation of the network if not set
+    pub fee_estimator: Option<FeeEstimatorConfig>,
+
+    // rules for choosing the wallet utxos funding DA txs
+    pub coin_selection: Option<CoinSelectionConfig>,
+
+    // confirmations after which a block is finalized,
+    // defaults to the finality depth of the network profile if not set
+    pub finality_depth: Option<u64>,
+
+    // prefixes, finality depth, fee sources and genesis of the network,
+    // defaults to the named profile of the network if not set
+    pub network_profile: Option<NetworkProfile>,
 }
 
 impl citrea_common::FromEnv for BitcoinServiceConfig {
@@ -91,28 +131,175 @@ impl citrea_common::FromEnv for BitcoinServiceConfig {
             node_password: std::env::var("NODE_PASSWORD")?,
             network: serde_json::from_str(&format!("\"{}\"", std::env::var("NETWORK")?))?,
             da_private_key: std::env::var("DA_PRIVATE_KEY").ok(),
+            signer: std::env::var("DA_SIGNER")
+                .ok()
+                .map(|signer| serde_json::from_str(&signer))
+                .transpose()?,
             tx_backup_dir: std::env::var("TX_BACKUP_DIR")?,
             monitoring: Some(MonitoringConfig {
                 check_interval: std::env::var("DA_MONITORING_CHECK_INTERVAL")?.parse()?,
                 history_limit: std::env::var("DA_MONITORING_HISTORY_LIMIT")?.parse()?,
                 max_history_size: std::env::var("DA_MONITORING_MAX_HISTORY_SIZE")?.parse()?,
             }),
+            fee_bump: std::env::var("DA_FEE_BUMP_METHOD")
+                .ok()
+                .map(|method| -> Result<FeeBumpPolicy> {
+                    Ok(FeeBumpPolicy {
+                        max_pending_blocks: std::env::var("DA_FEE_BUMP_MAX_PENDING_BLOCKS")
+                            .ok()
+                            .map(|blocks| blocks.parse())
+                            .transpose()?,
+                        max_pending_secs: std::env::var("DA_FEE_BUMP_MAX_PENDING_SECS")
+                            .ok()
+                            .map(|secs| secs.parse())
+                            .transpose()?,
+                        fee_rate_multiplier: std::env::var("DA_FEE_BUMP_FEE_RATE_MULTIPLIER")?
+                            .parse()?,
+                        max_fee_rate: std::env::var("DA_FEE_BUMP_MAX_FEE_RATE")?.parse()?,
+                        method: serde_json::from_str(&format!("\"{}\"", method))?,
+                    })
+                })
+                .transpose()?,
+            fee_estimator: std::env::var("DA_FEE_ESTIMATOR")
+                .ok()
+                .map(|estimator| serde_json::from_str(&estimator))
+                .transpose()?,
+            coin_selection: std::env::var("DA_COIN_SELECTION")
+                .ok()
+                .map(|coin_selection| serde_json::from_str(&coin_selection))
+                .transpose()?,
+            finality_depth: std::env::var("DA_FINALITY_DEPTH")
+                .ok()
+                .map(|depth| depth.parse())
+                .transpose()?,
+            network_profile: std::env::var("DA_NETWORK_PROFILE")
+                .ok()
+                .map(|profile| serde_json::from_str(&profile))
+                .transpose()?,
         })
     }
 }
 
+impl BitcoinServiceConfig {
+    /// The configured network profile, or the named profile of the network.
+    pub fn network_profile(&self) -> Result<NetworkProfile> {
+        let Some(profile) = self.network_profile.clone() else {
+            return Ok(NetworkProfile::from(self.network));
+        };
+        if profile.network() != self.network {
+            bail!(
+                "Network profile is for {} but the network is {}",
+                profile.network(),
+                self.network
+            );
+        }
+        Ok(profile)
+    }
+
+    /// Reveal txid prefixes of the configured network profile.
+    ///
+    /// The zk guests filter the DA namespaces with the prefixes they are built with,
+    /// so profiles with other prefixes are refused.
+    pub fn rollup_params(&self) -> Result<RollupParams> {
+        let params = self.network_profile()?.rollup_params();
+        let guest_params = NetworkProfile::guest_rollup_params();
+        if params != guest_params {
+            bail!(
+                "Network profile prefixes (batch proof {}, light client {}) differ from the ones the zk guests are built with (batch proof {}, light client {})",
+                hex::encode(&params.to_batch_proof_prefix),
+                hex::encode(&params.to_light_client_prefix),
+                hex::encode(&guest_params.to_batch_proof_prefix),
+                hex::encode(&guest_params.to_light_client_prefix),
+            );
+        }
+        Ok(params)
+    }
+
+    /// The configured DA signer, or an in-process signer if a DA private key is set.
+    pub fn signer(&self) -> Result<Option<Arc<dyn DaSigner>>> {
+        let da_private_key = self
+            .da_private_key
+            .as_ref()
+            .map(|pk| SecretKey::from_str(pk))
+            .transpose()
+            .context("Invalid private key")?;
+        match &self.signer {
+            Some(signer) => signer.build(da_private_key).map(Some),
+            None => Ok(
+                da_private_key.map(|pk| Arc::new(InProcessSigner::new(pk)) as Arc<dyn DaSigner>)
+            ),
+        }
+    }
+}
+
 /// A service that provides data and data availability proofs for Bitcoin
 #[derive(Debug)]
 pub struct BitcoinService {
     client: Arc<Client>,
     network: bitcoin::Network,
-    da_private_key: Option<SecretKey>,
+    signer: Option<Arc<dyn DaSigner>>,
     to_light_client_prefix: Vec<u8>,
     to_batch_proof_prefix: Vec<u8>,
+    fork2_da_activation_height: u64,
     inscribes_queue: UnboundedSender<SenderWithNotifier<TxidWrapper>>,
     tx_backup_dir: PathBuf,
     pub monitoring: Arc<MonitoringService>,
     fee: FeeService,
+    fee_bump_policy: Option<FeeBumpPolicy>,
+    coin_selection: CoinSelectionConfig,
+    finality_depth: u64,
+    // Utxos spent by txs which are being built or sent
+    reserved_utxos: Mutex<HashSet<OutPoint>>,
+}
+
+/// Releases reserved utxos when dropped.
+#[derive(Debug)]
+struct UtxoReservation<'a> {
+    reserved_utxos: &'a Mutex<HashSet<OutPoint>>,
+    outpoints: Vec<OutPoint>,
+}
+
+impl<'a> UtxoReservation<'a> {
+    fn new(reserved_utxos: &'a Mutex<HashSet<OutPoint>>) -> Self {
+        Self {
+            reserved_utxos,
+            outpoints: vec![],
+        }
+    }
+
+    /// Adds `outpoint` to the reservation. Returns false if another tx reserved it.
+    fn reserve(&mut self, outpoint: OutPoint) -> bool {
+        if self.outpoints.contains(&outpoint) {
+            return true;
+        }
+        let mut reserved_utxos = self.reserved_utxos.lock().unwrap();
+        if !reserved_utxos.insert(outpoint) {
+            return false;
+        }
+        self.outpoints.push(outpoint);
+        true
+    }
+
+    /// Releases the reserved utxos which are not in `outpoints`.
+    fn retain(&mut self, outpoints: &[OutPoint]) {
+        let mut reserved_utxos = self.reserved_utxos.lock().unwrap();
+        self.outpoints.retain(|outpoint| {
+            let keep = outpoints.contains(outpoint);
+            if !keep {
+                reserved_utxos.remove(outpoint);
+            }
+            keep
+        });
+    }
+}
+
+impl Drop for UtxoReservation<'_> {
+    fn drop(&mut self) {
+        let mut reserved_utxos = self.reserved_utxos.lock().unwrap();
+        for outpoint in &self.outpoints {
+            reserved_utxos.remove(outpoint);
+        }
+    }
 }
 
 impl BitcoinService {
@@ -122,6 +309,8 @@ impl BitcoinService {
         chain_params: RollupParams,
         tx: UnboundedSender<SenderWithNotifier<TxidWrapper>>,
     ) -> Result<Self> {
+        let network_profile = config.network_profile()?;
+        let signer = config.signer()?;
         let client = Arc::new(
             Client::new(
                 &config.node_url,
@@ -130,11 +319,7 @@ impl BitcoinService {
             .await?,
         );
 
-        let private_key = config
-            .da_private_key
-            .map(|pk| SecretKey::from_str(&pk))
-            .transpose()
-            .context("Invalid private key")?;
+        check_genesis_block(&client, &network_profile).await?;
 
         let wallets = client
             .list_wallets()
@@ -152,18 +337,31 @@ impl BitcoinService {
                 .context("Failed to create tx backup directory")?;
         }
 
-        let monitoring = Arc::new(MonitoringService::new(client.clone(), config.monitoring));
-        let fee = FeeService::new(client.clone(), config.network);
+        let finality_depth = config
+            .finality_depth
+            .unwrap_or(network_profile.finality_depth());
+        let monitoring = Arc::new(MonitoringService::new(
+            client.clone(),
+            config.monitoring,
+            tx_backup_dir.to_path_buf(),
+            finality_depth,
+        ));
+        let fee = FeeService::new(client.clone(), &network_profile, config.fee_estimator)?;
         Ok(Self {
             client,
             network: config.network,
-            da_private_key: private_key,
+            signer,
             to_light_client_prefix: chain_params.to_light_client_prefix,
             to_batch_proof_prefix: chain_params.to_batch_proof_prefix,
+            fork2_da_activation_height: chain_params.fork2_da_activation_height,
             inscribes_queue: tx,
             tx_backup_dir: tx_backup_dir.to_path_buf(),
             monitoring,
             fee,
+            fee_bump_policy: config.fee_bump,
+            coin_selection: config.coin_selection.unwrap_or_default(),
+            finality_depth,
+            reserved_utxos: Mutex::new(HashSet::new()),
         })
     }
 
@@ -172,6 +370,8 @@ impl BitcoinService {
         chain_params: RollupParams,
         tx: UnboundedSender<SenderWithNotifier<TxidWrapper>>,
     ) -> Result<Self> {
+        let network_profile = config.network_profile()?;
+        let signer = config.signer()?;
         let client = Arc::new(
             Client::new(
                 &config.node_url,
@@ -180,11 +380,7 @@ impl BitcoinService {
             .await?,
         );
 
-        let da_private_key = config
-            .da_private_key
-            .map(|pk| SecretKey::from_str(&pk))
-            .transpose()
-            .context("Invalid private key")?;
+        check_genesis_block(&client, &network_profile).await?;
 
         // check if config.tx_backup_dir exists
         let tx_backup_dir = std::path::Path::new(&config.tx_backup_dir);
@@ -194,19 +390,32 @@ impl BitcoinService {
                 .context("Failed to create tx backup directory")?;
         }
 
-        let monitoring = Arc::new(MonitoringService::new(client.clone(), config.monitoring));
-        let fee = FeeService::new(client.clone(), config.network);
+        let finality_depth = config
+            .finality_depth
+            .unwrap_or(network_profile.finality_depth());
+        let monitoring = Arc::new(MonitoringService::new(
+            client.clone(),
+            config.monitoring,
+            tx_backup_dir.to_path_buf(),
+            finality_depth,
+        ));
+        let fee = FeeService::new(client.clone(), &network_profile, config.fee_estimator)?;
 
         Ok(Self {
             client,
             network: config.network,
-            da_private_key,
+            signer,
             to_light_client_prefix: chain_params.to_light_client_prefix,
             to_batch_proof_prefix: chain_params.to_batch_proof_prefix,
+            fork2_da_activation_height: chain_params.fork2_da_activation_height,
             inscribes_queue: tx,
             tx_backup_dir: tx_backup_dir.to_path_buf(),
             monitoring,
             fee,
+            fee_bump_policy: config.fee_bump,
+            coin_selection: config.coin_selection.unwrap_or_default(),
+            finality_depth,
+            reserved_utxos: Mutex::new(HashSet::new()),
         })
     }
 
@@ -283,8 +492,19 @@ impl BitcoinService {
         utxos.into_iter().next()
     }
 
+    /// Adds the most recent spendable utxo of the tx chain to `reservation`, so fee bumps and
+    /// DA txs never spend it concurrently. Returns `None` if there is none or it is reserved.
+    async fn reserve_prev_utxo(&self, reservation: &mut UtxoReservation<'_>) -> Option<UTXO> {
+        let prev_utxo = self.get_prev_utxo().await?;
+        reservation
+            .reserve(OutPoint::new(prev_utxo.tx_id, prev_utxo.vout))
+            .then_some(prev_utxo)
+    }
+
+    /// Returns the utxos which may fund a DA tx, reserved until the returned reservation is
+    /// dropped or narrowed down to the utxos the tx spends.
     #[instrument(level = "trace", skip_all, ret)]
-    async fn get_utxos(&self) -> Result<Vec<UTXO>> {
+    async fn get_utxos(&self) -> Result<(Vec<UTXO>, UtxoReservation<'_>)> {
         let utxos = self
             .client
             .list_unspent(Some(0), None, None, None, None)
@@ -293,20 +513,166 @@ impl BitcoinService {
             bail!("There are no UTXOs");
         }
 
+        let utxos: Vec<UTXO> = utxos.into_iter().map(Into::into).collect();
+        let monitored_txs = self.monitoring.get_monitored_txs().await;
+        // Select and reserve under the same lock, so concurrent selections never share utxos
+        let mut reserved_utxos = self.reserved_utxos.lock().unwrap();
+        let selectable = self.selectable_utxos(&utxos, &monitored_txs, &reserved_utxos);
         let utxos: Vec<UTXO> = utxos
             .into_iter()
-            .filter(|utxo| {
-                utxo.spendable
+            .zip(selectable)
+            .filter_map(|(utxo, selectable)| selectable.then_some(utxo))
+            .collect();
+        if utxos.is_empty() {
+            bail!("There are no spendable UTXOs");
+        }
+
+        let outpoints: Vec<OutPoint> = utxos
+            .iter()
+            .map(|utxo| OutPoint::new(utxo.tx_id, utxo.vout))
+            .collect();
+        reserved_utxos.extend(outpoints.iter().copied());
+        let reservation = UtxoReservation {
+            reserved_utxos: &self.reserved_utxos,
+            outpoints,
+        };
+
+        Ok((utxos, reservation))
+    }
+
+    /// Whether each of the wallet utxos may fund a DA tx.
+    fn selectable_utxos(
+        &self,
+        utxos: &[UTXO],
+        monitored_txs: &HashMap<Txid, MonitoredTx>,
+        reserved_utxos: &HashSet<OutPoint>,
+    ) -> Vec<bool> {
+        utxos
+            .iter()
+            .map(|utxo| {
+                // Outputs of our own DA txs may be spent before they are confirmed
+                let confirmed_enough = utxo.confirmations >= self.coin_selection.min_confirmations
+                    || monitored_txs.contains_key(&utxo.tx_id);
+                // The wallet only watches the utxos whose keys an external signer holds
+                let spendable = utxo.spendable
+                    || self
+                        .signer
+                        .as_ref()
+                        .is_some_and(|signer| signer.signs_wallet_utxos());
+                spendable
                     && utxo.solvable
-                    && utxo.amount > Amount::from_sat(REVEAL_OUTPUT_AMOUNT)
+                    && utxo.amount > REVEAL_OUTPUT_AMOUNT
+                    && confirmed_enough
+                    && !reserved_utxos.contains(&OutPoint::new(utxo.tx_id, utxo.vout))
             })
+            .collect()
+    }
+
+    /// Looks up a reveal tx in the wallet, returning its block hash, block height and commit txid
+    /// if it is in a finalized block.
+    async fn get_finalized_wallet_reveal(
+        &self,
+        reveal_txid: Txid,
+    ) -> Result<Option<(BlockHash, u64, Txid)>> {
+        let tx_result = match self.client.get_transaction(&reveal_txid, None).await {
+            Ok(tx_result) => tx_result,
+            // Invalid or non-wallet transaction id
+            Err(Error::JsonRpc(RpcError::Rpc(rpc_err))) if rpc_err.code == -5 => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        if tx_result.info.confirmations < 0
+            || (tx_result.info.confirmations as u64) < self.finality_depth
+        {
+            return Ok(None);
+        }
+        let Some(block_hash) = tx_result.info.blockhash else {
+            return Ok(None);
+        };
+        let block_height = self.client.get_block_info(&block_hash).await?.height as u64;
+        let commit_txid = tx_result
+            .transaction()?
+            .input
+            .first()
+            .context("Reveal tx has no inputs")?
+            .previous_output
+            .txid;
+        Ok(Some((block_hash, block_height, commit_txid)))
+    }
+
+    /// Returns all wallet utxos, each with whether it is reserved and whether it may fund a DA tx.
+    pub async fn get_wallet_utxos(&self) -> Result<Vec<(UTXO, bool, bool)>> {
+        let utxos: Vec<UTXO> = self
+            .client
+            .list_unspent(Some(0), None, None, None, None)
+            .await?
+            .into_iter()
             .map(Into::into)
             .collect();
-        if utxos.is_empty() {
-            bail!("There are no spendable UTXOs");
+        let monitored_txs = self.monitoring.get_monitored_txs().await;
+        let reserved_utxos = self.reserved_utxos.lock().unwrap();
+        let selectable = self.selectable_utxos(&utxos, &monitored_txs, &reserved_utxos);
+        Ok(utxos
+            .into_iter()
+            .zip(selectable)
+            .map(|(utxo, selectable)| {
+                let reserved = reserved_utxos.contains(&OutPoint::new(utxo.tx_id, utxo.vout));
+                (utxo, reserved, selectable)
+            })
+            .collect())
+    }
+
+    /// Fetches the chunks listed by the signed batch proof aggregates in `txs` which are not in
+    /// `txs` themselves, e.g. because they were mined in an earlier block than their aggregate.
+    /// Chunks which can't be fetched are left out, so their aggregates can't be reassembled.
+    async fn get_missing_batch_proof_chunks(
+        &self,
+        txs: &[&Transaction],
+    ) -> Vec<TransactionWrapper> {
+        let chunks = collect_batch_proof_chunks(txs.iter().copied());
+        let mut missing_chunk_ids = HashSet::new();
+        for tx in txs {
+            let Ok(ParsedBatchProofTransaction::Aggregate(aggregate)) =
+                parse_batch_proof_transaction(tx)
+            else {
+                continue;
+            };
+            if aggregate.get_sig_verified_hash().is_none() {
+                continue;
+            }
+            let Some(aggregate) = parse_batch_proof_aggregate(&aggregate) else {
+                continue;
+            };
+            missing_chunk_ids.extend(
+                aggregate
+                    .chunk_ids
+                    .into_iter()
+                    .filter(|chunk_id| !chunks.contains_key(chunk_id)),
+            );
         }
 
-        Ok(utxos)
+        let mut missing_chunks = Vec::with_capacity(missing_chunk_ids.len());
+        for chunk_id in missing_chunk_ids {
+            let chunk_id = Txid::from_byte_array(chunk_id);
+            let exponential_backoff = ExponentialBackoff::default();
+            let res = retry_backoff(exponential_backoff, || async move {
+                self.client
+                    .get_raw_transaction(&chunk_id, None)
+                    .await
+                    .map_err(|e| {
+                        use bitcoincore_rpc::Error;
+                        match e {
+                            Error::Io(_) => backoff::Error::transient(e),
+                            _ => backoff::Error::permanent(e),
+                        }
+                    })
+            })
+            .await;
+            match res {
+                Ok(tx) => missing_chunks.push(tx.into()),
+                Err(e) => error!("{}: Failed to request batch proof chunk: {e}", chunk_id),
+            }
+        }
+        missing_chunks
     }
 
     #[instrument(level = "trace", skip_all, ret)]
@@ -325,14 +691,32 @@ impl BitcoinService {
         &self,
         da_data: DaData,
         fee_sat_per_vbyte: u64,
+    ) -> Result<Vec<Txid>> {
+        match da_data {
+            DaData::ZKProof(zkproof, spec_id) => {
+                self.send_zkproof(zkproof, spec_id, fee_sat_per_vbyte).await
+            }
+            DaData::SequencerCommitment(comm) => {
+                let data = DaDataBatchProof::SequencerCommitment(comm);
+                let blob = borsh::to_vec(&data).expect("DaDataBatchProof serialize must not fail");
+                self.send_batch_proof_blob(blob, fee_sat_per_vbyte).await
+            }
+        }
+    }
+
+    async fn send_zkproof(
+        &self,
+        zkproof: Proof,
+        spec_id: SpecId,
+        fee_sat_per_vbyte: u64,
     ) -> Result<Vec<Txid>> {
         let network = self.network;
 
-        let da_private_key = self.da_private_key.expect("No private key set");
+        let signer = self.signer.clone().expect("No DA signer set");
 
         // get all available utxos
-        let utxos = self.get_utxos().await?;
-        let prev_utxo = self.get_prev_utxo().await;
+        let (utxos, mut reservation) = self.get_utxos().await?;
+        let prev_utxo = self.reserve_prev_utxo(&mut reservation).await;
 
         // get address from a utxo
         let address = utxos[0]
@@ -342,77 +726,198 @@ impl BitcoinService {
             .require_network(network)
             .context("Invalid network for address")?;
 
-        match da_data {
-            DaData::ZKProof(zkproof) => {
-                let data = split_proof(zkproof);
-
-                let reveal_light_client_prefix = self.to_light_client_prefix.clone();
-                // create inscribe transactions
-                let inscription_txs = tokio::task::spawn_blocking(move || {
-                    // Since this is CPU bound work, we use spawn_blocking
-                    // to release the tokio runtime execution
-                    create_zkproof_transactions(
-                        data,
-                        da_private_key,
-                        prev_utxo,
-                        utxos,
-                        address,
-                        fee_sat_per_vbyte,
-                        fee_sat_per_vbyte,
-                        network,
-                        reveal_light_client_prefix,
-                    )
-                })
-                .await??;
-
-                // write txs to file, it can be used to continue revealing blob if something goes wrong
-                inscription_txs.write_to_file(self.tx_backup_dir.clone())?;
+        let data = split_proof(zkproof, spec_id);
+
+        let reveal_light_client_prefix = self.to_light_client_prefix.clone();
+        // create inscribe transactions
+        let inscription_txs = tokio::task::spawn_blocking(move || {
+            // Since this is CPU bound work, we use spawn_blocking
+            // to release the tokio runtime execution
+            create_zkproof_transactions(
+                data,
+                &*signer,
+                prev_utxo,
+                utxos,
+                address,
+                fee_sat_per_vbyte,
+                fee_sat_per_vbyte,
+                network,
+                reveal_light_client_prefix,
+            )
+        })
+        .await??;
+
+        // write txs to file, it can be used to continue revealing blob if something goes wrong
+        inscription_txs.write_to_file(self.tx_backup_dir.clone())?;
+
+        let txs: Vec<&Transaction> = match &inscription_txs {
+            LightClientTxs::Complete { commit, reveal } => vec![commit, &reveal.tx],
+            LightClientTxs::Chunked {
+                commit_chunks,
+                reveal_chunks,
+                commit,
+                reveal,
+            } => commit_chunks
+                .iter()
+                .chain(reveal_chunks)
+                .chain([commit, &reveal.tx])
+                .collect(),
+        };
+        reservation.retain(&spent_outpoints(txs));
 
-                match inscription_txs {
-                    LightClientTxs::Complete { commit, reveal } => {
-                        self.send_complete_transaction(commit, reveal).await
-                    }
-                    LightClientTxs::Chunked {
-                        commit_chunks,
-                        reveal_chunks,
-                        commit,
-                        reveal,
-                    } => {
-                        self.send_chunked_transaction(commit_chunks, reveal_chunks, commit, reveal)
-                            .await
-                    }
-                }
+        match inscription_txs {
+            LightClientTxs::Complete { commit, reveal } => {
+                self.send_complete_transaction(commit, reveal).await
             }
-            DaData::SequencerCommitment(comm) => {
-                let data = DaDataBatchProof::SequencerCommitment(comm);
-                let blob = borsh::to_vec(&data).expect("DaDataBatchProof serialize must not fail");
+            LightClientTxs::Chunked {
+                commit_chunks,
+                reveal_chunks,
+                commit,
+                reveal,
+            } => {
+                self.send_chunked_transaction(commit_chunks, reveal_chunks, commit, reveal)
+                    .await
+            }
+        }
+    }
 
-                let prefix = self.to_batch_proof_prefix.clone();
-                // create inscribe transactions
-                let inscription_txs = tokio::task::spawn_blocking(move || {
-                    // Since this is CPU bound work, we use spawn_blocking
-                    // to release the tokio runtime execution
-                    create_seqcommitment_transactions(
-                        blob,
-                        da_private_key,
-                        prev_utxo,
-                        utxos,
-                        address,
-                        fee_sat_per_vbyte,
-                        fee_sat_per_vbyte,
-                        network,
-                        prefix,
-                    )
-                })
-                .await??;
+    /// Sends `blob` to the batch proof namespace in a single reveal.
+    async fn send_batch_proof_blob(
+        &self,
+        blob: Vec<u8>,
+        fee_sat_per_vbyte: u64,
+    ) -> Result<Vec<Txid>> {
+        let network = self.network;
 
-                // write txs to file, it can be used to continue revealing blob if something goes wrong
-                inscription_txs.write_to_file(self.tx_backup_dir.clone())?;
+        let signer = self.signer.clone().expect("No DA signer set");
 
-                let BatchProvingTxs { commit, reveal } = inscription_txs;
+        // get all available utxos
+        let (utxos, mut reservation) = self.get_utxos().await?;
+        let prev_utxo = self.reserve_prev_utxo(&mut reservation).await;
+
+        // get address from a utxo
+        let address = utxos[0]
+            .address
+            .clone()
+            .context("Missing address")?
+            .require_network(network)
+            .context("Invalid network for address")?;
 
+        let prefix = self.to_batch_proof_prefix.clone();
+        // create inscribe transactions
+        let inscription_txs = tokio::task::spawn_blocking(move || {
+            // Since this is CPU bound work, we use spawn_blocking
+            // to release the tokio runtime execution
+            create_seqcommitment_transactions(
+                blob,
+                &*signer,
+                prev_utxo,
+                utxos,
+                address,
+                fee_sat_per_vbyte,
+                fee_sat_per_vbyte,
+                network,
+                prefix,
+            )
+        })
+        .await??;
+
+        self.send_batch_proving_txs(inscription_txs, reservation)
+            .await
+    }
+
+    /// Sends `blob` to the batch proof namespace, split into chunks of at most
+    /// [`MAX_TXBODY_SIZE`] bytes and a signed aggregate listing the chunks.
+    ///
+    /// Readers reassemble the chunks into `blob`, e.g. a borsh encoded [`DaDataBatchProof`]
+    /// too large for a single reveal. The chunks may be mined in earlier blocks than the aggregate.
+    /// Blobs needing more than [`MAX_BATCH_PROOF_CHUNKS`] chunks are refused.
+    #[instrument(level = "trace", skip(self, blob), ret, err)]
+    pub async fn send_chunked_batch_proof_blob(
+        &self,
+        blob: Vec<u8>,
+        fee_sat_per_vbyte: u64,
+    ) -> Result<Vec<Txid>> {
+        let network = self.network;
+
+        let signer = self.signer.clone().expect("No DA signer set");
+
+        // get all available utxos
+        let (utxos, mut reservation) = self.get_utxos().await?;
+        let prev_utxo = self.reserve_prev_utxo(&mut reservation).await;
+
+        // get address from a utxo
+        let address = utxos[0]
+            .address
+            .clone()
+            .context("Missing address")?
+            .require_network(network)
+            .context("Invalid network for address")?;
+
+        let chunks = blob
+            .chunks(MAX_TXBODY_SIZE)
+            .map(|chunk| chunk.to_vec())
+            .collect();
+
+        let prefix = self.to_batch_proof_prefix.clone();
+        // create inscribe transactions
+        let inscription_txs = tokio::task::spawn_blocking(move || {
+            // Since this is CPU bound work, we use spawn_blocking
+            // to release the tokio runtime execution
+            create_chunked_batchproof_transactions(
+                chunks,
+                &*signer,
+                prev_utxo,
+                utxos,
+                address,
+                fee_sat_per_vbyte,
+                fee_sat_per_vbyte,
+                network,
+                prefix,
+            )
+        })
+        .await??;
+
+        self.send_batch_proving_txs(inscription_txs, reservation)
+            .await
+    }
+
+    async fn send_batch_proving_txs(
+        &self,
+        inscription_txs: BatchProvingTxs,
+        mut reservation: UtxoReservation<'_>,
+    ) -> Result<Vec<Txid>> {
+        // write txs to file, it can be used to continue revealing blob if something goes wrong
+        inscription_txs.write_to_file(self.tx_backup_dir.clone())?;
+
+        let txs: Vec<&Transaction> = match &inscription_txs {
+            BatchProvingTxs::SequencerCommitment { commit, reveal } => vec![commit, &reveal.tx],
+            BatchProvingTxs::Chunked {
+                commit_chunks,
+                reveal_chunks,
+                commit,
+                reveal,
+            } => commit_chunks
+                .iter()
+                .chain(reveal_chunks)
+                .chain([commit, &reveal.tx])
+                .collect(),
+        };
+        reservation.retain(&spent_outpoints(txs));
+
+        match inscription_txs {
+            BatchProvingTxs::SequencerCommitment { commit, reveal } => {
                 self.send_complete_transaction(commit, reveal).await
             }
+            BatchProvingTxs::Chunked {
+                commit_chunks,
+                reveal_chunks,
+                commit,
+                reveal,
+            } => {
+                self.send_chunked_transaction(commit_chunks, reveal_chunks, commit, reveal)
+                    .await
+            }
         }
     }
 
@@ -442,53 +947,15 @@ impl BitcoinService {
         let mut raw_txs = Vec::with_capacity(all_tx_map.len());
 
         for (commit, reveal) in commit_chunks.into_iter().zip(reveal_chunks) {
-            let mut inputs = vec![];
-
-            for input in commit.input.iter() {
-                if let Some(entry) = all_tx_map.get(&input.previous_output.txid) {
-                    inputs.push(SignRawTransactionInput {
-                        txid: input.previous_output.txid,
-                        vout: input.previous_output.vout,
-                        script_pub_key: entry.output[input.previous_output.vout as usize]
-                            .script_pubkey
-                            .clone(),
-                        redeem_script: None,
-                        amount: Some(entry.output[input.previous_output.vout as usize].value),
-                    });
-                }
-            }
-
-            let signed_raw_commit_tx = self
-                .client
-                .sign_raw_transaction_with_wallet(&commit, Some(inputs.as_slice()), None)
-                .await?;
-            raw_txs.push(signed_raw_commit_tx.hex);
+            let signed_raw_commit_tx = self.sign_commit_transaction(&commit, &all_tx_map).await?;
+            raw_txs.push(signed_raw_commit_tx);
 
             let serialized_reveal_tx = encode::serialize(&reveal);
             raw_txs.push(serialized_reveal_tx);
         }
 
-        let mut inputs = vec![];
-
-        for input in commit.input.iter() {
-            if let Some(entry) = all_tx_map.get(&input.previous_output.txid) {
-                inputs.push(SignRawTransactionInput {
-                    txid: input.previous_output.txid,
-                    vout: input.previous_output.vout,
-                    script_pub_key: entry.output[input.previous_output.vout as usize]
-                        .script_pubkey
-                        .clone(),
-                    redeem_script: None,
-                    amount: Some(entry.output[input.previous_output.vout as usize].value),
-                });
-            }
-        }
-        let signed_raw_commit_tx = self
-            .client
-            .sign_raw_transaction_with_wallet(&commit, Some(inputs.as_slice()), None)
-            .await?;
-
-        raw_txs.push(signed_raw_commit_tx.hex);
+        let signed_raw_commit_tx = self.sign_commit_transaction(&commit, &all_tx_map).await?;
+        raw_txs.push(signed_raw_commit_tx);
 
         let serialized_reveal_tx = encode::serialize(&reveal.tx);
         raw_txs.push(serialized_reveal_tx);
@@ -514,11 +981,10 @@ impl BitcoinService {
         reveal: TxWithId,
     ) -> Result<Vec<Txid>> {
         let signed_raw_commit_tx = self
-            .client
-            .sign_raw_transaction_with_wallet(&commit, None, None)
+            .sign_commit_transaction(&commit, &HashMap::new())
             .await?;
         let serialized_reveal_tx = encode::serialize(&reveal.tx);
-        let raw_txs = [signed_raw_commit_tx.hex, serialized_reveal_tx];
+        let raw_txs = [signed_raw_commit_tx, serialized_reveal_tx];
 
         self.test_mempool_accept(&raw_txs).await?;
 
@@ -527,6 +993,113 @@ impl BitcoinService {
         Ok(txids)
     }
 
+    /// Signs a commit tx with the DA signer, the inputs it leaves unsigned are signed by the wallet.
+    /// `package` holds the unbroadcast txs whose outputs the commit tx may spend.
+    #[instrument(level = "trace", skip_all, err)]
+    async fn sign_commit_transaction(
+        &self,
+        commit: &Transaction,
+        package: &HashMap<Txid, Transaction>,
+    ) -> Result<Vec<u8>> {
+        let signer = self.signer.clone().context("No DA signer set")?;
+
+        let mut psbt = Psbt::from_unsigned_tx(commit.clone())?;
+        let mut package_inputs = vec![];
+        for (input, psbt_input) in commit.input.iter().zip(psbt.inputs.iter_mut()) {
+            let outpoint = input.previous_output;
+            let prev_output = match package.get(&outpoint.txid) {
+                Some(parent) => {
+                    let prev_output = parent.output[outpoint.vout as usize].clone();
+                    package_inputs.push(SignRawTransactionInput {
+                        txid: outpoint.txid,
+                        vout: outpoint.vout,
+                        script_pub_key: prev_output.script_pubkey.clone(),
+                        redeem_script: None,
+                        amount: Some(prev_output.value),
+                    });
+                    prev_output
+                }
+                None => {
+                    let prev_output = self
+                        .client
+                        .get_tx_out(&outpoint.txid, outpoint.vout, Some(true))
+                        .await?
+                        .with_context(|| format!("Commit tx input {} is not unspent", outpoint))?;
+                    TxOut {
+                        value: prev_output.value,
+                        script_pubkey: ScriptBuf::from_bytes(prev_output.script_pub_key.hex),
+                    }
+                }
+            };
+            psbt_input.witness_utxo = Some(prev_output);
+        }
+
+        let psbt = tokio::task::spawn_blocking(move || signer.sign_commit_psbt(psbt)).await??;
+
+        let mut commit = commit.clone();
+        let mut signed_by_signer = true;
+        for (input, psbt_input) in commit.input.iter_mut().zip(psbt.inputs) {
+            match (psbt_input.final_script_witness, psbt_input.final_script_sig) {
+                (None, None) => signed_by_signer = false,
+                (witness, script_sig) => {
+                    input.witness = witness.unwrap_or_default();
+                    input.script_sig = script_sig.unwrap_or_default();
+                }
+            }
+        }
+        if signed_by_signer {
+            return Ok(encode::serialize(&commit));
+        }
+
+        // The wallet keeps the signatures of the inputs the signer finalized
+        let signed_raw_commit_tx = self
+            .client
+            .sign_raw_transaction_with_wallet(&commit, Some(package_inputs.as_slice()), None)
+            .await?;
+        Ok(signed_raw_commit_tx.hex)
+    }
+
+    /// Signs a tx funded by the wallet, e.g. a fee bump or a consolidation, with the DA signer.
+    /// The inputs it leaves unsigned are signed by the wallet.
+    #[instrument(level = "trace", skip_all, err)]
+    async fn sign_wallet_psbt(&self, mut psbt: Psbt) -> Result<Vec<u8>> {
+        let signer = self.signer.clone().context("No DA signer set")?;
+
+        for (input, psbt_input) in psbt.unsigned_tx.input.iter().zip(psbt.inputs.iter_mut()) {
+            if psbt_input.witness_utxo.is_some() {
+                continue;
+            }
+            let outpoint = input.previous_output;
+            let prev_output = self
+                .client
+                .get_tx_out(&outpoint.txid, outpoint.vout, Some(true))
+                .await?
+                .with_context(|| format!("Wallet tx input {} is not unspent", outpoint))?;
+            psbt_input.witness_utxo = Some(TxOut {
+                value: prev_output.value,
+                script_pubkey: ScriptBuf::from_bytes(prev_output.script_pub_key.hex),
+            });
+        }
+
+        let psbt = tokio::task::spawn_blocking(move || signer.sign_commit_psbt(psbt)).await??;
+
+        let signed_by_signer = psbt
+            .inputs
+            .iter()
+            .all(|input| input.final_script_witness.is_some() || input.final_script_sig.is_some());
+        if signed_by_signer {
+            return Ok(encode::serialize(&psbt.extract_tx_unchecked_fee_rate()));
+        }
+
+        // The wallet keeps the signatures of the inputs the signer finalized
+        let wallet_psbt = self
+            .client
+            .wallet_process_psbt(&psbt, Some(true), None, None)
+            .await?;
+        let processed = self.client.finalize_psbt(&wallet_psbt.psbt, None).await?;
+        processed.hex.context("Couldn't finalize psbt")
+    }
+
     #[instrument(level = "trace", skip_all, ret)]
     async fn test_mempool_accept(&self, raw_txs: &[Vec<u8>]) -> Result<()> {
         let results = self
@@ -572,6 +1145,18 @@ impl BitcoinService {
         fee_rate: f64,
         force: Option<bool>,
         method: BumpFeeMethod,
+    ) -> Result<Txid> {
+        self.bump_fee_inner(txid, fee_rate, force, method, false)
+            .await
+    }
+
+    async fn bump_fee_inner(
+        &self,
+        txid: Option<Txid>,
+        fee_rate: f64,
+        force: Option<bool>,
+        method: BumpFeeMethod,
+        automatic: bool,
     ) -> Result<Txid> {
         // Look for input tx or resolve to monitored last_tx
         let (txid, tx) = match txid {
@@ -597,7 +1182,8 @@ impl BitcoinService {
             )
         };
 
-        let Some(utxo) = self.get_prev_utxo().await else {
+        let mut reservation = UtxoReservation::new(&self.reserved_utxos);
+        let Some(utxo) = self.reserve_prev_utxo(&mut reservation).await else {
             bail!("Cannot bump fee without prev_utxo available")
         };
 
@@ -609,17 +1195,17 @@ impl BitcoinService {
             }
             BumpFeeMethod::Rbf => self.fee.bump_fee_rbf(tx.kind, &txid).await,
         }?;
+        // The wallet may add inputs a DA tx or a consolidation is about to spend
+        for input in &funded_psbt.unsigned_tx.input {
+            if !reservation.reserve(input.previous_output) {
+                bail!(
+                    "Fee bump tx spends {} which is reserved by another tx",
+                    input.previous_output
+                );
+            }
+        }
 
-        let wallet_psbt = self
-            .client
-            .wallet_process_psbt(&funded_psbt, Some(true), None, None)
-            .await?;
-
-        let processed = self.client.finalize_psbt(&wallet_psbt.psbt, None).await?;
-
-        let Some(raw_hex) = processed.hex else {
-            bail!("Couldn't finalize psbt")
-        };
+        let raw_hex = self.sign_wallet_psbt(funded_psbt).await?;
 
         if let Err(e) = self.client.test_mempool_accept(&[&raw_hex]).await {
             bail!("Tx not accepted in mempool : {e}");
@@ -637,8 +1223,185 @@ impl BitcoinService {
             BumpFeeMethod::Rbf => self.monitoring.replace_txid(txid, new_txid).await?,
         };
 
+        self.monitoring
+            .record_fee_bump(
+                &txid,
+                FeeBump {
+                    txid: new_txid,
+                    method,
+                    fee_rate,
+                    timestamp: SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap()
+                        .as_secs(),
+                    automatic,
+                },
+            )
+            .await;
+
         Ok(new_txid)
     }
+
+    /// Bumps the fee of the last monitored tx whenever it is stuck according to the
+    /// configured [`FeeBumpPolicy`]. Returns immediately if no policy is configured.
+    pub async fn run_fee_bumper(self: Arc<Self>, token: CancellationToken) {
+        let Some(policy) = self.fee_bump_policy.clone() else {
+            return;
+        };
+
+        let mut interval = tokio::time::interval(Duration::from_secs(FEE_BUMP_CHECK_INTERVAL));
+        loop {
+            select! {
+                biased;
+                _ = token.cancelled() => {
+                    debug!("Fee bumper received shutdown signal");
+                    break;
+                }
+                _ = interval.tick() => {
+                    if let Err(e) = self.bump_stuck_tx(&policy).await {
+                        error!(?e, "Failed to bump fee of stuck tx");
+                    }
+                }
+            }
+        }
+    }
+
+    async fn bump_stuck_tx(&self, policy: &FeeBumpPolicy) -> Result<()> {
+        let Some((txid, tx)) = self.monitoring.get_last_tx().await else {
+            return Ok(());
+        };
+        let TxStatus::Pending { base_fee, .. } = tx.status else {
+            return Ok(());
+        };
+
+        let current_height = self.client.get_block_count().await?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        if !policy.is_stuck(tx.initial_height, tx.initial_broadcast, current_height, now) {
+            return Ok(());
+        }
+
+        let estimated_fee_rate = self.fee.get_fee_rate().await?;
+        let current_fee_rate = base_fee as f64 / tx.tx.vsize() as f64;
+        let Some(fee_rate) = policy.bump_fee_rate(estimated_fee_rate, current_fee_rate) else {
+            warn!(
+                %txid,
+                current_fee_rate,
+                max_fee_rate = policy.max_fee_rate,
+                "Stuck tx already pays the fee rate ceiling"
+            );
+            return Ok(());
+        };
+
+        // Only CPFP txs can be replaced
+        let method = match (policy.method, tx.kind) {
+            (BumpFeeMethod::Rbf, MonitoredTxKind::Cpfp) => BumpFeeMethod::Rbf,
+            _ => BumpFeeMethod::Cpfp,
+        };
+
+        let new_txid = self
+            .bump_fee_inner(Some(txid), fee_rate, None, method, true)
+            .await?;
+        info!(%txid, %new_txid, fee_rate, ?method, "Bumped fee of stuck tx");
+
+        Ok(())
+    }
+
+    /// Consolidates small wallet utxos whenever fees are low, according to the configured
+    /// [`ConsolidationConfig`]. Returns immediately if consolidation is not configured.
+    pub async fn run_utxo_consolidation(self: Arc<Self>, token: CancellationToken) {
+        let Some(config) = self.coin_selection.consolidation.clone() else {
+            return;
+        };
+
+        let mut interval = tokio::time::interval(Duration::from_secs(config.check_interval));
+        loop {
+            select! {
+                biased;
+                _ = token.cancelled() => {
+                    debug!("Utxo consolidation received shutdown signal");
+                    break;
+                }
+                _ = interval.tick() => {
+                    match self.consolidate_utxos(&config).await {
+                        Ok(Some(txid)) => info!(%txid, "Consolidated wallet utxos"),
+                        Ok(None) => {}
+                        Err(e) => error!(?e, "Failed to consolidate wallet utxos"),
+                    }
+                }
+            }
+        }
+    }
+
+    async fn consolidate_utxos(&self, config: &ConsolidationConfig) -> Result<Option<Txid>> {
+        let fee_rate = self.fee.get_fee_rate().await?;
+        if fee_rate > config.max_fee_rate {
+            return Ok(None);
+        }
+
+        let (mut utxos, mut reservation) = match self.get_utxos().await {
+            Ok(selection) => selection,
+            Err(_) => return Ok(None),
+        };
+        // Never spend the output the DA tx chain continues from, nor in-flight outputs
+        let prev_utxo = self.get_prev_utxo().await;
+        utxos.retain(|utxo| {
+            utxo.confirmations > 0
+                && prev_utxo.as_ref().map_or(true, |prev| {
+                    (prev.tx_id, prev.vout) != (utxo.tx_id, utxo.vout)
+                })
+        });
+        if utxos.len() < config.min_utxos.max(2) {
+            return Ok(None);
+        }
+
+        // Smallest first, those are the most expensive to spend later
+        utxos.sort_by_key(|utxo| utxo.amount);
+        utxos.truncate(config.max_inputs);
+
+        let address = utxos[0]
+            .address
+            .clone()
+            .context("Missing address")?
+            .require_network(self.network)
+            .context("Invalid network for address")?;
+        let total = utxos.iter().map(|utxo| utxo.amount).sum();
+
+        let inputs: Vec<CreateRawTransactionInput> = utxos
+            .iter()
+            .map(|utxo| CreateRawTransactionInput {
+                txid: utxo.tx_id,
+                vout: utxo.vout,
+                sequence: Some(Sequence::ENABLE_RBF_NO_LOCKTIME.to_consensus_u32()),
+            })
+            .collect();
+        reservation.retain(
+            &inputs
+                .iter()
+                .map(|input| OutPoint::new(input.txid, input.vout))
+                .collect::<Vec<_>>(),
+        );
+
+        let mut outputs = HashMap::new();
+        outputs.insert(address.to_string(), Amount::from_sat(total));
+        let options = WalletCreateFundedPsbtOptions {
+            add_inputs: Some(false),
+            fee_rate: Some(Amount::from_btc(fee_rate as f64 / 100_000.0)?), // sat/vB to BTC/kB
+            subtract_fee_from_outputs: vec![0],
+            replaceable: Some(true),
+            ..Default::default()
+        };
+        let funded_psbt = self
+            .client
+            .wallet_create_funded_psbt(&inputs, &outputs, None, Some(options), None)
+            .await?;
+
+        let raw_hex = self.sign_wallet_psbt(funded_psbt.psbt).await?;
+
+        Ok(Some(self.client.send_raw_transaction(&raw_hex).await?))
+    }
 }
 
 #[async_trait]
@@ -699,7 +1462,11 @@ impl DaService for BitcoinService {
 
         let finalized_blockhash = self
             .client
-            .get_block_hash(block_count.saturating_sub(FINALITY_DEPTH).saturating_add(1))
+            .get_block_hash(
+                block_count
+                    .saturating_sub(self.finality_depth)
+                    .saturating_add(1),
+            )
             .await?;
 
         let finalized_block_header = self.get_block_by_hash(finalized_blockhash).await?;
@@ -743,7 +1510,9 @@ impl DaService for BitcoinService {
                             && complete.get_sig_verified_hash().is_some()
                         {
                             // push only when signature is correct
-                            let body = decompress_blob(&complete.body);
+                            let body = decompress_blob(&complete.body).map_err(|e| {
+                                anyhow!("{}: Failed to decompress complete: {e}", tx_id)
+                            })?;
                             let data = DaDataLightClient::try_from_slice(&body)
                                 .map_err(|e| anyhow!("{}: Failed to parse complete: {e}", tx_id))?;
                             let DaDataLightClient::Complete(zk_proof) = data else {
@@ -831,7 +1600,9 @@ impl DaService for BitcoinService {
                     }
                 }
             }
-            let zk_proof: Proof = borsh::from_slice(decompress_blob(&body).as_slice())
+            let body = decompress_blob(&body)
+                .map_err(|e| anyhow!("{}: Failed to decompress aggregate: {e}", tx_id))?;
+            let zk_proof: Proof = borsh::from_slice(body.as_slice())
                 .map_err(|e| anyhow!("{}: Failed to parse Proof from Aggregate: {e}", tx_id))?;
             aggregates.push((i, zk_proof));
         }
@@ -854,29 +1625,56 @@ impl DaService for BitcoinService {
         sequencer_da_pub_key: &[u8],
     ) -> Result<Vec<SequencerCommitment>> {
         let mut sequencer_commitments = Vec::new();
+        let fork2_active = block.header.height() >= self.fork2_da_activation_height;
 
-        for tx in &block.txdata {
-            if !tx
-                .compute_wtxid()
-                .to_byte_array()
-                .as_slice()
-                .starts_with(&self.to_batch_proof_prefix)
-            {
-                continue;
-            }
+        let relevant_txs: Vec<&Transaction> = block
+            .txdata
+            .iter()
+            .map(Deref::deref)
+            .filter(|tx| {
+                tx.compute_wtxid()
+                    .to_byte_array()
+                    .as_slice()
+                    .starts_with(&self.to_batch_proof_prefix)
+            })
+            .collect();
+        let chunks = collect_batch_proof_chunks(
+            relevant_txs
+                .iter()
+                .copied()
+                .chain(block.batch_proof_chunks.iter().map(Deref::deref)),
+        );
 
+        for tx in relevant_txs {
             if let Ok(tx) = parse_batch_proof_transaction(tx) {
-                match tx {
+                let body = match tx {
                     ParsedBatchProofTransaction::SequencerCommitment(seq_comm) => {
-                        if seq_comm.get_sig_verified_hash().is_some()
-                            && seq_comm.public_key() == sequencer_da_pub_key
+                        if seq_comm.get_sig_verified_hash().is_none()
+                            || seq_comm.public_key() != sequencer_da_pub_key
                         {
-                            let data = DaDataBatchProof::try_from_slice(&seq_comm.body);
-                            if let Ok(DaDataBatchProof::SequencerCommitment(seq_com)) = data {
-                                sequencer_commitments.push(seq_com);
-                            }
+                            continue;
+                        }
+                        seq_comm.body
+                    }
+                    // Chunked data is only read from Fork2 on
+                    ParsedBatchProofTransaction::Aggregate(_) if !fork2_active => continue,
+                    ParsedBatchProofTransaction::Aggregate(aggregate) => {
+                        if aggregate.get_sig_verified_hash().is_none()
+                            || aggregate.public_key() != sequencer_da_pub_key
+                        {
+                            continue;
                         }
+                        let Some(body) = reassemble_batch_proof_aggregate(&aggregate, &chunks)
+                        else {
+                            continue;
+                        };
+                        body
                     }
+                    ParsedBatchProofTransaction::Chunk(_) => continue,
+                };
+                let data = DaDataBatchProof::try_from_slice(&body);
+                if let Ok(DaDataBatchProof::SequencerCommitment(seq_com)) = data {
+                    sequencer_commitments.push(seq_com);
                 }
             }
         }
@@ -946,6 +1744,18 @@ impl DaService for BitcoinService {
         let inclusion_proof =
             InclusionMultiProof::new(wtxids, block.txdata[0].clone(), coinbase_proof);
 
+        // chunks of aggregates mined in earlier blocks are fetched with the block
+        let batch_proof_chunks = match namespace {
+            DaNamespace::ToBatchProver => collect_batch_proof_chunks(
+                completeness_proof
+                    .iter()
+                    .chain(&block.batch_proof_chunks)
+                    .map(Deref::deref),
+            ),
+            DaNamespace::ToLightClientProver => HashMap::new(),
+        };
+
+        let fork2_active = block.header.height() >= self.fork2_da_activation_height;
         let mut relevant_txs = vec![];
         for tx in &completeness_proof {
             match namespace {
@@ -963,6 +1773,30 @@ impl DaService for BitcoinService {
                                     relevant_txs.push(relevant_tx);
                                 }
                             }
+                            // Chunked data is only read from Fork2 on
+                            ParsedBatchProofTransaction::Aggregate(_) if !fork2_active => {}
+                            ParsedBatchProofTransaction::Aggregate(aggregate) => {
+                                if let Some(hash) = aggregate.get_sig_verified_hash() {
+                                    // Aggregates with unavailable chunks can't be read, skip them
+                                    let Some(blob) = reassemble_batch_proof_aggregate(
+                                        &aggregate,
+                                        &batch_proof_chunks,
+                                    ) else {
+                                        error!(
+                                            "Failed to reassemble aggregate blob {}",
+                                            hex::encode(hash)
+                                        );
+                                        continue;
+                                    };
+                                    let relevant_tx =
+                                        BlobWithSender::new(blob, aggregate.public_key, hash);
+
+                                    relevant_txs.push(relevant_tx);
+                                }
+                            }
+                            ParsedBatchProofTransaction::Chunk(_) => {
+                                // reassembled with their aggregate
+                            }
                         }
                     }
                 }
@@ -971,7 +1805,19 @@ impl DaService for BitcoinService {
                         match tx {
                             ParsedLightClientTransaction::Complete(complete) => {
                                 if let Some(hash) = complete.get_sig_verified_hash() {
-                                    let blob = decompress_blob(&complete.body);
+                                    let blob = if fork2_active {
+                                        // Malformed bodies can't be read by anyone, skip them
+                                        let Ok(blob) = decompress_blob(&complete.body) else {
+                                            error!(
+                                                "Failed to decompress complete blob {}",
+                                                hex::encode(hash)
+                                            );
+                                            continue;
+                                        };
+                                        blob
+                                    } else {
+                                        decompress_legacy_blob(&complete.body)
+                                    };
                                     let relevant_tx =
                                         BlobWithSender::new(blob, complete.public_key, hash);
 
@@ -1056,12 +1902,107 @@ impl DaService for BitcoinService {
 
         let witness_root = calculate_witness_root(&txs);
 
+        let relevant_txs: Vec<&Transaction> = txs
+            .iter()
+            .map(Deref::deref)
+            .filter(|tx| {
+                tx.compute_wtxid()
+                    .to_byte_array()
+                    .as_slice()
+                    .starts_with(&self.to_batch_proof_prefix)
+            })
+            .collect();
+        let batch_proof_chunks = if block.height >= self.fork2_da_activation_height {
+            self.get_missing_batch_proof_chunks(&relevant_txs).await
+        } else {
+            vec![]
+        };
+
         Ok(BitcoinBlock {
             header: HeaderWrapper::new(header, txs.len() as u32, block.height, witness_root),
             txdata: txs,
+            batch_proof_chunks,
         })
     }
 
+    /// Only reports txs included in a finalized block, which are followed through fee bump
+    /// replacements while they are monitored. Txs no longer monitored, e.g. pruned after
+    /// finalization or sent before a restart, are looked up in the wallet.
+    #[instrument(level = "trace", skip(self), err)]
+    async fn get_publication_receipt(
+        &self,
+        tx_id: [u8; 32],
+    ) -> Result<Option<DaPublicationReceipt>> {
+        let mut reveal_txid = Txid::from_byte_array(tx_id);
+        let mut reveal = None;
+        while let Some(monitored_tx) = self.monitoring.get_monitored_tx(&reveal_txid).await {
+            match monitored_tx.status {
+                TxStatus::Replaced { by_txid } => reveal_txid = by_txid,
+                _ => {
+                    reveal = Some(monitored_tx);
+                    break;
+                }
+            }
+        }
+        let (block_hash, block_height, commit_txid) = match reveal {
+            Some(reveal) => {
+                let TxStatus::Finalized {
+                    block_hash,
+                    block_height,
+                    ..
+                } = reveal.status
+                else {
+                    return Ok(None);
+                };
+                let commit_txid = reveal
+                    .prev_txid
+                    .context("Reveal tx is not preceded by its commit tx")?;
+                (block_hash, block_height, commit_txid)
+            }
+            None => match self.get_finalized_wallet_reveal(reveal_txid).await? {
+                Some(found) => found,
+                None => return Ok(None),
+            },
+        };
+
+        let block = self.get_block_by_hash(block_hash).await?;
+        let mut wtxids = Vec::with_capacity(block.txdata.len());
+        wtxids.push([0u8; 32]);
+        wtxids.extend(
+            block.txdata[1..]
+                .iter()
+                .map(|tx| tx.compute_wtxid().to_byte_array()),
+        );
+        let wtxid_index = block.txdata[1..]
+            .iter()
+            .position(|tx| tx.compute_txid() == reveal_txid)
+            .context("Reveal tx is not in its block")?
+            + 1;
+
+        let txid_merkle_tree = BitcoinMerkleTree::new(
+            block
+                .txdata
+                .iter()
+                .map(|tx| tx.compute_txid().to_byte_array())
+                .collect(),
+        );
+        let inclusion_proof = WtxidInclusionProof {
+            wtxid_index: wtxid_index as u32,
+            wtxid_merkle_proof: BitcoinMerkleTree::new(wtxids).get_idx_path(wtxid_index as u32),
+            coinbase_tx: block.txdata[0].clone(),
+            coinbase_merkle_proof: txid_merkle_tree.get_idx_path(0),
+        };
+
+        Ok(Some(DaPublicationReceipt {
+            commit_tx_id: commit_txid.to_byte_array(),
+            reveal_tx_id: reveal_txid.to_byte_array(),
+            l1_height: block_height,
+            l1_block_hash: block_hash.to_byte_array(),
+            inclusion_proof: borsh::to_vec(&inclusion_proof)
+                .expect("Inclusion proof serialization must not fail"),
+        }))
+    }
+
     async fn get_pending_sequencer_commitments(
         &self,
         sequencer_da_pub_key: &[u8],
@@ -1070,35 +2011,60 @@ impl DaService for BitcoinService {
 
         let mut sequencer_commitments = Vec::new();
 
-        for tx in &pending_txs {
-            if !tx
-                .compute_wtxid()
-                .to_byte_array()
-                .as_slice()
-                .starts_with(&self.to_batch_proof_prefix)
-            {
-                continue;
-            }
+        let relevant_txs: Vec<&Transaction> = pending_txs
+            .iter()
+            .filter(|tx| {
+                tx.compute_wtxid()
+                    .to_byte_array()
+                    .as_slice()
+                    .starts_with(&self.to_batch_proof_prefix)
+            })
+            .collect();
+        // chunks may already be mined while their aggregate is pending
+        let missing_chunks = self.get_missing_batch_proof_chunks(&relevant_txs).await;
+        let chunks = collect_batch_proof_chunks(
+            relevant_txs
+                .iter()
+                .copied()
+                .chain(missing_chunks.iter().map(Deref::deref)),
+        );
 
+        for tx in relevant_txs {
             if let Ok(tx) = parse_batch_proof_transaction(tx) {
-                match tx {
+                let body = match tx {
                     ParsedBatchProofTransaction::SequencerCommitment(seq_comm) => {
                         // we check on da pending txs of our wallet however let's keep consistency
-                        if seq_comm.get_sig_verified_hash().is_some()
-                            && seq_comm.public_key == sequencer_da_pub_key
+                        if seq_comm.get_sig_verified_hash().is_none()
+                            || seq_comm.public_key != sequencer_da_pub_key
                         {
-                            let da_data = DaDataBatchProof::try_from_slice(&seq_comm.body);
-                            match da_data {
-                                Ok(da_data) => match da_data {
-                                    DaDataBatchProof::SequencerCommitment(commitment) => {
-                                        sequencer_commitments.push(commitment);
-                                    }
-                                },
-                                Err(err) => {
-                                    warn!("Pending transaction blob failed to be parsed: {}", err);
-                                }
-                            }
+                            continue;
                         }
+                        seq_comm.body
+                    }
+                    ParsedBatchProofTransaction::Aggregate(aggregate) => {
+                        if aggregate.get_sig_verified_hash().is_none()
+                            || aggregate.public_key != sequencer_da_pub_key
+                        {
+                            continue;
+                        }
+                        let Some(body) = reassemble_batch_proof_aggregate(&aggregate, &chunks)
+                        else {
+                            warn!("Pending aggregate is missing chunks");
+                            continue;
+                        };
+                        body
+                    }
+                    ParsedBatchProofTransaction::Chunk(_) => continue,
+                };
+                let da_data = DaDataBatchProof::try_from_slice(&body);
+                match da_data {
+                    Ok(da_data) => match da_data {
+                        DaDataBatchProof::SequencerCommitment(commitment) => {
+                            sequencer_commitments.push(commitment);
+                        }
+                    },
+                    Err(err) => {
+                        warn!("Pending transaction blob failed to be parsed: {}", err);
                     }
                 }
             }
@@ -1113,17 +2079,19 @@ pub fn get_relevant_blobs_from_txs(
 ) -> Vec<BlobWithSender> {
     let mut relevant_txs = Vec::new();
 
-    for tx in txs {
-        if !tx
-            .compute_wtxid()
-            .to_byte_array()
-            .as_slice()
-            .starts_with(reveal_wtxid_prefix)
-        {
-            continue;
-        }
+    let txs: Vec<Transaction> = txs
+        .into_iter()
+        .filter(|tx| {
+            tx.compute_wtxid()
+                .to_byte_array()
+                .as_slice()
+                .starts_with(reveal_wtxid_prefix)
+        })
+        .collect();
+    let chunks = collect_batch_proof_chunks(&txs);
 
-        if let Ok(tx) = parse_batch_proof_transaction(&tx) {
+    for tx in &txs {
+        if let Ok(tx) = parse_batch_proof_transaction(tx) {
             match tx {
                 ParsedBatchProofTransaction::SequencerCommitment(seq_comm) => {
                     if let Some(hash) = seq_comm.get_sig_verified_hash() {
@@ -1133,13 +2101,23 @@ pub fn get_relevant_blobs_from_txs(
                         relevant_txs.push(relevant_tx);
                     }
                 }
+                ParsedBatchProofTransaction::Aggregate(aggregate) => {
+                    if let Some(hash) = aggregate.get_sig_verified_hash() {
+                        if let Some(blob) = reassemble_batch_proof_aggregate(&aggregate, &chunks) {
+                            let relevant_tx = BlobWithSender::new(blob, aggregate.public_key, hash);
+
+                            relevant_txs.push(relevant_tx);
+                        }
+                    }
+                }
+                ParsedBatchProofTransaction::Chunk(_) => {}
             }
         }
     }
     relevant_txs
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, core::hash::Hash)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, core::hash::Hash)]
 pub struct TxidWrapper(Txid);
 impl From<TxidWrapper> for [u8; 32] {
     fn from(val: TxidWrapper) -> Self {
@@ -1147,19 +2125,42 @@ impl From<TxidWrapper> for [u8; 32] {
     }
 }
 
+/// Makes sure the bitcoin node is on the chain of the network profile.
+async fn check_genesis_block(client: &Client, network_profile: &NetworkProfile) -> Result<()> {
+    let genesis_block_hash = client.get_block_hash(0).await?;
+    if genesis_block_hash != network_profile.genesis_block_hash() {
+        bail!(
+            "Genesis block {} of the bitcoin node does not match the network profile {:?}",
+            genesis_block_hash,
+            network_profile
+        );
+    }
+    Ok(())
+}
+
+/// Outpoints spent by the inputs of `txs`.
+fn spent_outpoints<'a>(txs: impl IntoIterator<Item = &'a Transaction>) -> Vec<OutPoint> {
+    txs.into_iter()
+        .flat_map(|tx| tx.input.iter().map(|input| input.previous_output))
+        .collect()
+}
+
 /// This function splits Proof based on its size. It is either:
 /// 1: compress(borsh(DaDataLightClient::Complete(Proof)))
 /// 2:
 ///   let compressed = compress(borsh(Proof))
 ///   let chunks = compressed.chunks(MAX_TXBODY_SIZE)
 ///   [borsh(DaDataLightClient::Chunk(chunk)) for chunk in chunks]
-fn split_proof(zk_proof: Proof) -> RawLightClientData {
+///
+/// The proof is compressed as the fork with `spec_id` prescribes.
+fn split_proof(zk_proof: Proof, spec_id: SpecId) -> RawLightClientData {
+    let compression = blob_compression(spec_id);
     let original_blob = borsh::to_vec(&zk_proof).expect("zk::Proof serialize must not fail");
-    let original_compressed = compress_blob(&original_blob);
+    let original_compressed = compress_blob(&original_blob, compression);
     if original_compressed.len() < MAX_TXBODY_SIZE {
         let data = DaDataLightClient::Complete(zk_proof);
         let blob = borsh::to_vec(&data).expect("zk::Proof serialize must not fail");
-        let blob = compress_blob(&blob);
+        let blob = compress_blob(&blob, compression);
         RawLightClientData::Complete(blob)
     } else {
         let mut chunks = vec![];
@@ -1187,3 +2188,71 @@ fn calculate_witness_root(txdata: &[TransactionWrapper]) -> [u8; 32] {
         .collect();
     BitcoinMerkleTree::new(hashes).root()
 }
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::Network;
+
+    use super::BitcoinServiceConfig;
+    use crate::network::{CustomNetworkProfile, NetworkProfile};
+
+    fn config(network: Network, network_profile: Option<NetworkProfile>) -> BitcoinServiceConfig {
+        BitcoinServiceConfig {
+            node_url: "http://127.0.0.1:38332".to_string(),
+            node_username: String::new(),
+            node_password: String::new(),
+            network,
+            da_private_key: None,
+            signer: None,
+            tx_backup_dir: String::new(),
+            monitoring: None,
+            fee_bump: None,
+            fee_estimator: None,
+            coin_selection: None,
+            finality_depth: None,
+            network_profile,
+        }
+    }
+
+    fn custom_profile(
+        to_batch_proof_prefix: Vec<u8>,
+        to_light_client_prefix: Vec<u8>,
+    ) -> NetworkProfile {
+        NetworkProfile::Custom(CustomNetworkProfile {
+            network: Network::Signet,
+            to_batch_proof_prefix,
+            to_light_client_prefix,
+            finality_depth: 6,
+            mempool_space_url: None,
+            genesis_block_hash: NetworkProfile::Signet.genesis_block_hash(),
+        })
+    }
+
+    #[test]
+    fn rollup_params_of_configured_profile() {
+        let guest_params = NetworkProfile::guest_rollup_params();
+        let profile = custom_profile(
+            guest_params.to_batch_proof_prefix.clone(),
+            guest_params.to_light_client_prefix.clone(),
+        );
+
+        let params = config(Network::Signet, Some(profile.clone()))
+            .rollup_params()
+            .unwrap();
+        assert_eq!(params, guest_params);
+
+        let params = config(Network::Regtest, None).rollup_params().unwrap();
+        assert_eq!(params, guest_params);
+
+        // the profile must be for the configured network
+        assert!(config(Network::Regtest, Some(profile))
+            .rollup_params()
+            .is_err());
+
+        // the guests can't verify blobs with other prefixes
+        let profile = custom_profile(vec![3, 3], vec![4]);
+        assert!(config(Network::Signet, Some(profile))
+            .rollup_params()
+            .is_err());
+    }
+}
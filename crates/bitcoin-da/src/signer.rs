@@ -0,0 +1,384 @@
+//! Signing of DA blobs and commit transactions.
+//!
+//! Reveal transactions are signed in the builders with a throwaway key generated for every
+//! inscription, as their wtxid has to be ground for the namespace prefix. Only the signature
+//! authenticating the blob and the wallet inputs of the commit transactions need the keys
+//! of the publisher, so those are handed to a [`DaSigner`].
+
+use core::fmt;
+use core::time::Duration;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context, Result};
+use bitcoin::secp256k1::{ecdsa, Message, PublicKey, Secp256k1, SecretKey};
+use bitcoin::Psbt;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::helpers::builders::sign_blob_with_private_key;
+use crate::helpers::calculate_sha256;
+
+/// Timeout of a request to a [`SocketSigner`] if none is configured.
+const DEFAULT_SOCKET_TIMEOUT: u64 = 30; // seconds
+
+/// Signature of a blob and the public key it verifies with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobSignature {
+    /// Compact ECDSA signature of sha256(blob), hex encoded.
+    #[serde(with = "hex::serde")]
+    pub signature: Vec<u8>,
+    /// Compressed public key, hex encoded.
+    #[serde(with = "hex::serde")]
+    pub public_key: Vec<u8>,
+}
+
+/// Signs on behalf of the DA publisher.
+///
+/// Methods block until signed, callers run them off the async runtime.
+pub trait DaSigner: fmt::Debug + Send + Sync {
+    /// Signs a blob with the DA key, so readers can authenticate its sender.
+    fn sign_blob(&self, blob: &[u8]) -> Result<BlobSignature>;
+
+    /// Signs the inputs of an unsigned commit transaction, or of a fee bump or consolidation
+    /// funded by the wallet. Every input has its witness utxo set.
+    ///
+    /// Inputs left unfinalized are signed by the wallet of the bitcoin node.
+    fn sign_commit_psbt(&self, psbt: Psbt) -> Result<Psbt>;
+
+    /// Whether the signer holds the keys of the wallet utxos, so the wallet of the node
+    /// may only be watching them.
+    fn signs_wallet_utxos(&self) -> bool {
+        false
+    }
+}
+
+/// Signs blobs with a DA key held in memory, commit transactions are signed by the wallet
+/// of the bitcoin node.
+pub struct InProcessSigner {
+    da_private_key: SecretKey,
+}
+
+impl InProcessSigner {
+    pub fn new(da_private_key: SecretKey) -> Self {
+        Self { da_private_key }
+    }
+}
+
+impl fmt::Debug for InProcessSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InProcessSigner")
+            .field("da_private_key", &"...")
+            .finish()
+    }
+}
+
+impl DaSigner for InProcessSigner {
+    fn sign_blob(&self, blob: &[u8]) -> Result<BlobSignature> {
+        let (signature, public_key) = sign_blob_with_private_key(blob, &self.da_private_key);
+        Ok(BlobSignature {
+            signature,
+            public_key,
+        })
+    }
+
+    fn sign_commit_psbt(&self, psbt: Psbt) -> Result<Psbt> {
+        Ok(psbt)
+    }
+}
+
+/// Request sent to a [`SocketSigner`], one JSON object per line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum SignerRequest {
+    /// Answered with a [`BlobSignature`].
+    SignBlob {
+        #[serde(with = "hex::serde")]
+        blob: Vec<u8>,
+    },
+    /// Answered with a [`SignedPsbt`].
+    SignCommitPsbt {
+        /// Serialized PSBT, hex encoded.
+        #[serde(with = "hex::serde")]
+        psbt: Vec<u8>,
+    },
+}
+
+/// Answer to [`SignerRequest::SignCommitPsbt`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedPsbt {
+    /// Serialized PSBT, hex encoded.
+    #[serde(with = "hex::serde")]
+    pub psbt: Vec<u8>,
+}
+
+/// Answer of a [`SocketSigner`], either the result or `{"error": "..."}`.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum SignerResponse<T> {
+    Ok(T),
+    Err { error: String },
+}
+
+/// Forwards signing requests to an external signer listening on a unix socket,
+/// so the keys stay off the publishing host.
+///
+/// Every request is sent over a new connection as a line of JSON, see [`SignerRequest`].
+#[derive(Debug)]
+pub struct SocketSigner {
+    path: PathBuf,
+    timeout: Duration,
+    /// Compressed DA public key the signer must sign blobs with.
+    da_public_key: Vec<u8>,
+}
+
+impl SocketSigner {
+    pub fn new(path: PathBuf, timeout: Duration, da_public_key: Vec<u8>) -> Self {
+        Self {
+            path,
+            timeout,
+            da_public_key,
+        }
+    }
+
+    fn request<T: DeserializeOwned>(&self, request: &SignerRequest) -> Result<T> {
+        let mut stream = UnixStream::connect(&self.path)
+            .with_context(|| format!("Failed to connect to signer at {:?}", self.path))?;
+        stream.set_read_timeout(Some(self.timeout))?;
+        stream.set_write_timeout(Some(self.timeout))?;
+
+        let mut line = serde_json::to_vec(request)?;
+        line.push(b'\n');
+        stream.write_all(&line)?;
+
+        let mut response = String::new();
+        BufReader::new(stream)
+            .read_line(&mut response)
+            .context("Failed to read signer response")?;
+        match serde_json::from_str(&response).context("Invalid signer response")? {
+            SignerResponse::Ok(result) => Ok(result),
+            SignerResponse::Err { error } => Err(anyhow!("Signer refused request: {}", error)),
+        }
+    }
+}
+
+impl DaSigner for SocketSigner {
+    fn sign_blob(&self, blob: &[u8]) -> Result<BlobSignature> {
+        let signature: BlobSignature = self.request(&SignerRequest::SignBlob {
+            blob: blob.to_vec(),
+        })?;
+        if signature.public_key != self.da_public_key {
+            return Err(anyhow!(
+                "Signer signed with {} instead of the DA public key {}",
+                hex::encode(&signature.public_key),
+                hex::encode(&self.da_public_key)
+            ));
+        }
+        if !is_valid_blob_signature(blob, &signature) {
+            return Err(anyhow!("Signer returned an invalid signature of the blob"));
+        }
+        Ok(signature)
+    }
+
+    fn sign_commit_psbt(&self, psbt: Psbt) -> Result<Psbt> {
+        let SignedPsbt { psbt: signed } = self.request(&SignerRequest::SignCommitPsbt {
+            psbt: psbt.serialize(),
+        })?;
+        let signed = Psbt::deserialize(&signed).context("Invalid PSBT from signer")?;
+        if signed.unsigned_tx != psbt.unsigned_tx {
+            return Err(anyhow!("Signer changed the commit transaction"));
+        }
+        Ok(signed)
+    }
+
+    fn signs_wallet_utxos(&self) -> bool {
+        true
+    }
+}
+
+/// Whether `signature` is a valid signature of sha256(`blob`) by its public key.
+fn is_valid_blob_signature(blob: &[u8], signature: &BlobSignature) -> bool {
+    let (Ok(public_key), Ok(sig)) = (
+        PublicKey::from_slice(&signature.public_key),
+        ecdsa::Signature::from_compact(&signature.signature),
+    ) else {
+        return false;
+    };
+    let message = Message::from_digest(calculate_sha256(blob));
+    Secp256k1::verification_only()
+        .verify_ecdsa(&message, &sig, &public_key)
+        .is_ok()
+}
+
+/// Which [`DaSigner`] the DA service uses.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DaSignerConfig {
+    /// Signs with `da_private_key`, commit transactions are signed by the wallet of the node.
+    InProcess,
+    /// Forwards requests to an external signer, the wallet of the node can be watch-only.
+    Socket {
+        path: PathBuf,
+        /// Seconds to wait for an answer.
+        timeout: Option<u64>,
+        /// Compressed DA public key, hex encoded, blobs signed with another key are rejected.
+        #[serde(with = "hex::serde")]
+        da_public_key: Vec<u8>,
+    },
+}
+
+impl DaSignerConfig {
+    /// Creates the configured signer, `da_private_key` is required by the in-process one.
+    pub fn build(&self, da_private_key: Option<SecretKey>) -> Result<Arc<dyn DaSigner>> {
+        Ok(match self {
+            Self::InProcess => Arc::new(InProcessSigner::new(
+                da_private_key.context("In-process DA signer requires a DA private key")?,
+            )),
+            Self::Socket {
+                path,
+                timeout,
+                da_public_key,
+            } => Arc::new(SocketSigner::new(
+                path.clone(),
+                Duration::from_secs(timeout.unwrap_or(DEFAULT_SOCKET_TIMEOUT)),
+                da_public_key.clone(),
+            )),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixListener;
+
+    use bitcoin::absolute::LockTime;
+    use bitcoin::secp256k1::SecretKey;
+    use bitcoin::{Psbt, Transaction, Witness};
+
+    use super::{DaSigner, InProcessSigner, SignedPsbt, SignerRequest, SocketSigner};
+
+    #[test]
+    fn socket_signer_forwards_requests() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("signer.sock");
+        let listener = UnixListener::bind(&path).unwrap();
+
+        // Stands in for the external signer, holding the DA key
+        let da_private_key = SecretKey::from_slice(&[0xcd; 32]).unwrap();
+        let external = std::thread::spawn(move || {
+            let key_signer = InProcessSigner::new(da_private_key);
+            for _ in 0..3 {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut line = String::new();
+                BufReader::new(&stream).read_line(&mut line).unwrap();
+                let response = match serde_json::from_str(&line).unwrap() {
+                    SignerRequest::SignBlob { blob } if blob.is_empty() => {
+                        serde_json::json!({ "error": "empty blob" })
+                    }
+                    SignerRequest::SignBlob { blob } => {
+                        serde_json::to_value(key_signer.sign_blob(&blob).unwrap()).unwrap()
+                    }
+                    SignerRequest::SignCommitPsbt { psbt } => {
+                        let mut psbt = Psbt::deserialize(&psbt).unwrap();
+                        for input in psbt.inputs.iter_mut() {
+                            input.final_script_witness = Some(Witness::from_slice(&[[1u8; 64]]));
+                        }
+                        serde_json::to_value(SignedPsbt {
+                            psbt: psbt.serialize(),
+                        })
+                        .unwrap()
+                    }
+                };
+                let mut response = serde_json::to_vec(&response).unwrap();
+                response.push(b'\n');
+                stream.write_all(&response).unwrap();
+            }
+        });
+
+        let expected = InProcessSigner::new(da_private_key)
+            .sign_blob(b"blob")
+            .unwrap();
+        let signer = SocketSigner::new(path, Duration::from_secs(5), expected.public_key.clone());
+        assert_eq!(signer.sign_blob(b"blob").unwrap(), expected);
+        assert!(signer.sign_blob(&[]).is_err());
+
+        let psbt = Psbt::from_unsigned_tx(Transaction {
+            version: bitcoin::transaction::Version(2),
+            lock_time: LockTime::ZERO,
+            input: vec![Default::default()],
+            output: vec![],
+        })
+        .unwrap();
+        let signed = signer.sign_commit_psbt(psbt).unwrap();
+        assert!(signed.inputs[0].final_script_witness.is_some());
+
+        external.join().unwrap();
+    }
+
+    #[test]
+    fn socket_signer_rejects_other_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("signer.sock");
+        let listener = UnixListener::bind(&path).unwrap();
+
+        // Signs with a key other than the configured DA key
+        let other_private_key = SecretKey::from_slice(&[0xef; 32]).unwrap();
+        let external = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut line = String::new();
+            BufReader::new(&stream).read_line(&mut line).unwrap();
+            let SignerRequest::SignBlob { blob } = serde_json::from_str(&line).unwrap() else {
+                panic!("expected a blob to sign");
+            };
+            let signature = InProcessSigner::new(other_private_key)
+                .sign_blob(&blob)
+                .unwrap();
+            let mut response = serde_json::to_vec(&signature).unwrap();
+            response.push(b'\n');
+            stream.write_all(&response).unwrap();
+        });
+
+        let da_public_key = InProcessSigner::new(SecretKey::from_slice(&[0xcd; 32]).unwrap())
+            .sign_blob(b"blob")
+            .unwrap()
+            .public_key;
+        let signer = SocketSigner::new(path, Duration::from_secs(5), da_public_key);
+        assert!(signer.sign_blob(b"blob").is_err());
+
+        external.join().unwrap();
+    }
+
+    #[test]
+    fn socket_signer_rejects_invalid_signatures() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("signer.sock");
+        let listener = UnixListener::bind(&path).unwrap();
+
+        // Signs another blob with the DA key
+        let da_private_key = SecretKey::from_slice(&[0xcd; 32]).unwrap();
+        let external = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut line = String::new();
+            BufReader::new(&stream).read_line(&mut line).unwrap();
+            let signature = InProcessSigner::new(da_private_key)
+                .sign_blob(b"other blob")
+                .unwrap();
+            let mut response = serde_json::to_vec(&signature).unwrap();
+            response.push(b'\n');
+            stream.write_all(&response).unwrap();
+        });
+
+        let da_public_key = InProcessSigner::new(da_private_key)
+            .sign_blob(b"blob")
+            .unwrap()
+            .public_key;
+        let signer = SocketSigner::new(path, Duration::from_secs(5), da_public_key);
+        assert!(signer.sign_blob(b"blob").is_err());
+
+        external.join().unwrap();
+    }
+}
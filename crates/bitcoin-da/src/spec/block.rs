@@ -10,6 +10,9 @@ use super::transaction::TransactionWrapper;
 pub struct BitcoinBlock {
     pub header: HeaderWrapper,
     pub txdata: Vec<TransactionWrapper>,
+    /// Chunks listed by the batch proof aggregates in `txdata` which were mined in earlier blocks.
+    #[serde(default)]
+    pub batch_proof_chunks: Vec<TransactionWrapper>,
 }
 
 impl SlotData for BitcoinBlock {
@@ -23,9 +23,13 @@ pub mod utxo;
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct BitcoinSpec;
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RollupParams {
     pub to_light_client_prefix: Vec<u8>,
     pub to_batch_proof_prefix: Vec<u8>,
+    /// L1 height from which DA transactions are read with the rules of Fork2,
+    /// see [`citrea_primitives::forks::FORK2_DA_ACTIVATION_HEIGHT`].
+    pub fork2_da_activation_height: u64,
 }
 
 impl DaSpec for BitcoinSpec {
@@ -35,3 +35,13 @@ impl Default for InclusionMultiProof {
         }
     }
 }
+
+/// Proof of the inclusion of a single transaction in a block by its wtxid.
+/// The wtxid merkle root is committed to by the coinbase tx, which is proven against the header.
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+pub struct WtxidInclusionProof {
+    pub wtxid_index: u32,
+    pub wtxid_merkle_proof: Vec<[u8; 32]>,
+    pub coinbase_tx: TransactionWrapper,
+    pub coinbase_merkle_proof: Vec<[u8; 32]>,
+}
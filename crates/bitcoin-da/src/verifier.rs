@@ -1,5 +1,5 @@
 use bitcoin::hashes::Hash;
-use citrea_primitives::compression::decompress_blob;
+use citrea_primitives::compression::{decompress_blob, decompress_legacy_blob};
 use crypto_bigint::{Encoding, U256};
 use itertools::Itertools;
 use sov_rollup_interface::da::{
@@ -8,10 +8,10 @@ use sov_rollup_interface::da::{
 use sov_rollup_interface::zk::LightClientCircuitOutput;
 
 use crate::helpers::parsers::{
-    parse_batch_proof_transaction, parse_light_client_transaction, ParsedBatchProofTransaction,
-    ParsedLightClientTransaction, VerifyParsed,
+    parse_batch_proof_aggregate, parse_batch_proof_transaction, parse_light_client_transaction,
+    ParsedBatchProofTransaction, ParsedLightClientTransaction, VerifyParsed,
 };
-use crate::helpers::{calculate_double_sha256, merkle_tree};
+use crate::helpers::{calculate_double_sha256, calculate_sha256, merkle_tree};
 use crate::spec::blob::BlobWithSender;
 use crate::spec::BitcoinSpec;
 
@@ -31,6 +31,7 @@ const BLOCKS_PER_EPOCH: u64 = 2016;
 pub struct BitcoinVerifier {
     to_batch_proof_prefix: Vec<u8>,
     to_light_client_prefix: Vec<u8>,
+    fork2_da_activation_height: u64,
 }
 
 // TODO: custom errors based on our implementation
@@ -67,6 +68,7 @@ impl DaVerifier for BitcoinVerifier {
         Self {
             to_batch_proof_prefix: params.to_batch_proof_prefix,
             to_light_client_prefix: params.to_light_client_prefix,
+            fork2_da_activation_height: params.fork2_da_activation_height,
         }
     }
 
@@ -84,13 +86,15 @@ impl DaVerifier for BitcoinVerifier {
         }
 
         // create hash set of blobs
-        let mut blobs_iter = blobs.iter();
+        let mut blobs_iter = blobs.iter().peekable();
 
         let prefix = match namespace {
             DaNamespace::ToBatchProver => self.to_batch_proof_prefix.as_slice(),
             DaNamespace::ToLightClientProver => self.to_light_client_prefix.as_slice(),
         };
 
+        let fork2_active = block_header.height() >= self.fork2_da_activation_height;
+
         let relevant_wtxid_iter = inclusion_proof
             .wtxids
             .iter()
@@ -116,6 +120,34 @@ impl DaVerifier for BitcoinVerifier {
                                     }
                                 }
                             }
+                            // Chunked data is only read from Fork2 on
+                            ParsedBatchProofTransaction::Aggregate(_) if !fork2_active => {}
+                            ParsedBatchProofTransaction::Aggregate(aggregate) => {
+                                // Malformed aggregates are skipped when extracting blobs
+                                let Some(body_hash) = parse_batch_proof_aggregate(&aggregate)
+                                    .map(|aggregate| aggregate.body_hash)
+                                else {
+                                    continue;
+                                };
+                                // Chunks mined in earlier blocks may be unavailable to the prover,
+                                // which then leaves the aggregate out of the blobs
+                                if blobs_iter.peek().map(|blob| blob.hash)
+                                    != Some(calculate_sha256(&aggregate.body))
+                                {
+                                    continue;
+                                }
+                                if let Some(blob_content) =
+                                    verified_blob_content(&aggregate, &mut blobs_iter)?
+                                {
+                                    // assert the reassembled chunks are the signed ones
+                                    if calculate_sha256(blob_content) != body_hash {
+                                        return Err(ValidationError::BlobContentWasModified);
+                                    }
+                                }
+                            }
+                            ParsedBatchProofTransaction::Chunk(_chunk) => {
+                                // reassembled with their aggregate
+                            }
                         }
                     }
                 }
@@ -123,11 +155,22 @@ impl DaVerifier for BitcoinVerifier {
                     if let Ok(parsed_tx) = parse_light_client_transaction(tx) {
                         match parsed_tx {
                             ParsedLightClientTransaction::Complete(complete) => {
+                                // From Fork2 on, bodies which don't decompress are skipped
+                                // when extracting blobs
+                                let body = if fork2_active {
+                                    let Ok(body) = decompress_blob(&complete.body) else {
+                                        continue;
+                                    };
+                                    Some(body)
+                                } else {
+                                    None
+                                };
                                 if let Some(blob_content) =
                                     verified_blob_content(&complete, &mut blobs_iter)?
                                 {
+                                    let body = body
+                                        .unwrap_or_else(|| decompress_legacy_blob(&complete.body));
                                     // assert tx content is not modified
-                                    let body = decompress_blob(&complete.body);
                                     if blob_content != body {
                                         return Err(ValidationError::BlobContentWasModified);
                                     }
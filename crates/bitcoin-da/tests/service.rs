@@ -43,6 +43,7 @@ impl TestCase for BitcoinServiceTest {
         let verifier = BitcoinVerifier::new(RollupParams {
             to_batch_proof_prefix: TO_BATCH_PROOF_PREFIX.to_vec(),
             to_light_client_prefix: TO_LIGHT_CLIENT_PREFIX.to_vec(),
+            fork2_da_activation_height: 0,
         });
 
         let (block, block_commitments, block_proofs) =
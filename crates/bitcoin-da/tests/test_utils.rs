@@ -25,6 +25,7 @@ use citrea_primitives::compression::decompress_blob;
 use citrea_primitives::{MAX_TXBODY_SIZE, TO_BATCH_PROOF_PREFIX, TO_LIGHT_CLIENT_PREFIX};
 use sov_rollup_interface::da::{DaData, SequencerCommitment};
 use sov_rollup_interface::services::da::DaService;
+use sov_rollup_interface::spec::SpecId;
 
 pub const DEFAULT_DA_PRIVATE_KEY: &str =
     "E9873D79C6D87DC0FB6A5778633389F4453213303DA61F20BD67FC233AA33262";
@@ -60,8 +61,14 @@ pub async fn get_service(
         node_password: config.rpc_password.clone(),
         network: bitcoin::Network::Regtest,
         da_private_key: Some(da_private_key),
+        signer: None,
         tx_backup_dir: get_tx_backup_dir(),
         monitoring: None,
+        fee_bump: None,
+        fee_estimator: None,
+        coin_selection: None,
+        finality_depth: None,
+        network_profile: None,
     };
 
     let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
@@ -71,6 +78,7 @@ pub async fn get_service(
         RollupParams {
             to_batch_proof_prefix,
             to_light_client_prefix,
+            fork2_da_activation_height: 0,
         },
         tx,
     )
@@ -159,7 +167,7 @@ pub async fn generate_mock_txs(
 
     valid_proofs.push(blob.clone());
     da_service
-        .send_transaction(DaData::ZKProof(blob))
+        .send_transaction(DaData::ZKProof(blob, SpecId::Genesis))
         .await
         .expect("Failed to send transaction");
 
@@ -169,7 +177,7 @@ pub async fn generate_mock_txs(
 
     valid_proofs.push(blob.clone());
     da_service
-        .send_transaction(DaData::ZKProof(blob))
+        .send_transaction(DaData::ZKProof(blob, SpecId::Genesis))
         .await
         .expect("Failed to send transaction");
 
@@ -188,7 +196,7 @@ pub async fn generate_mock_txs(
 
     valid_proofs.push(blob.clone());
     da_service
-        .send_transaction(DaData::ZKProof(blob))
+        .send_transaction(DaData::ZKProof(blob, SpecId::Genesis))
         .await
         .expect("Failed to send transaction");
 
@@ -219,7 +227,7 @@ pub async fn generate_mock_txs(
 
     valid_proofs.push(blob.clone());
     da_service
-        .send_transaction(DaData::ZKProof(blob))
+        .send_transaction(DaData::ZKProof(blob, SpecId::Genesis))
         .await
         .expect("Failed to send transaction");
 
@@ -279,6 +287,7 @@ pub fn get_mock_nonsegwit_block() -> BitcoinBlock {
     BitcoinBlock {
         header,
         txdata: txs,
+        batch_proof_chunks: vec![],
     }
 }
 
@@ -323,6 +332,7 @@ pub fn get_mock_false_signature_txs_block() -> BitcoinBlock {
     BitcoinBlock {
         header,
         txdata: txs,
+        batch_proof_chunks: vec![],
     }
 }
 
@@ -391,6 +401,12 @@ pub fn get_blob_with_sender(tx: &Transaction, ty: MockData) -> anyhow::Result<Bl
                         .expect("Invalid sighash on commitment");
                     (seq_com.body, seq_com.public_key, hash)
                 }
+                ParsedBatchProofTransaction::Aggregate(_) => {
+                    anyhow::bail!("Aggregate blob has to be reassembled from its chunks")
+                }
+                ParsedBatchProofTransaction::Chunk(_) => {
+                    anyhow::bail!("Chunk is only read through its aggregate")
+                }
             }
         }
         MockData::ToLightClient => {
@@ -400,7 +416,7 @@ pub fn get_blob_with_sender(tx: &Transaction, ty: MockData) -> anyhow::Result<Bl
                     let hash = complete
                         .get_sig_verified_hash()
                         .expect("Invalid sighash on complete zk proof");
-                    let blob = decompress_blob(&complete.body);
+                    let blob = decompress_blob(&complete.body)?;
                     (blob, complete.public_key, hash)
                 }
                 ParsedLightClientTransaction::Aggregate(aggregate) => {
@@ -409,7 +425,9 @@ pub fn get_blob_with_sender(tx: &Transaction, ty: MockData) -> anyhow::Result<Bl
                         .expect("Invalid sighash on aggregate zk proof");
                     (aggregate.body, aggregate.public_key, hash)
                 }
-                _ => unimplemented!(),
+                ParsedLightClientTransaction::Chunk(_) => {
+                    anyhow::bail!("Chunk is only read through its aggregate")
+                }
             }
         }
     };
@@ -14,8 +14,8 @@ use citrea_e2e::config::TestCaseConfig;
 use citrea_e2e::framework::TestFramework;
 use citrea_e2e::test_case::{TestCase, TestCaseRunner};
 use citrea_e2e::Result;
-use citrea_primitives::{TO_BATCH_PROOF_PREFIX, TO_LIGHT_CLIENT_PREFIX};
-use sov_rollup_interface::da::{BlobReaderTrait, DaNamespace, DaVerifier};
+use citrea_primitives::{MAX_TXBODY_SIZE, TO_BATCH_PROOF_PREFIX, TO_LIGHT_CLIENT_PREFIX};
+use sov_rollup_interface::da::{BlobReaderTrait, BlockHeaderTrait, DaNamespace, DaVerifier};
 use sov_rollup_interface::services::da::DaService;
 use test_utils::macros::assert_panic;
 use test_utils::{
@@ -56,6 +56,7 @@ impl TestCase for BitcoinVerifierTest {
         let verifier = BitcoinVerifier::new(RollupParams {
             to_batch_proof_prefix: TO_BATCH_PROOF_PREFIX.to_vec(),
             to_light_client_prefix: TO_LIGHT_CLIENT_PREFIX.to_vec(),
+            fork2_da_activation_height: 0,
         });
 
         // Correct batch proof
@@ -613,3 +614,157 @@ async fn test_bitcoin_verifier() -> Result<()> {
         .run()
         .await
 }
+
+struct BatchProofAggregateVerifierTest;
+
+#[async_trait]
+impl TestCase for BatchProofAggregateVerifierTest {
+    fn test_config() -> TestCaseConfig {
+        // Only run bitcoin regtest
+        TestCaseConfig {
+            with_sequencer: false,
+            ..Default::default()
+        }
+    }
+
+    async fn run_test(&mut self, f: &mut TestFramework) -> Result<()> {
+        let mut task_manager = TaskManager::default();
+        let da_node = f.bitcoin_nodes.get(0).unwrap();
+
+        let service = get_default_service(&mut task_manager, &da_node.config).await;
+
+        // Blob of 2 chunks and an aggregate
+        let size = MAX_TXBODY_SIZE + 1500;
+        let blob = (0..size).map(|_| rand::random::<u8>()).collect::<Vec<u8>>();
+        service
+            .send_chunked_batch_proof_blob(blob.clone(), 10)
+            .await
+            .expect("Failed to send chunked blob");
+
+        let block_hash = da_node.generate(1).await.unwrap()[0];
+        let block = service.get_block_by_hash(block_hash).await.unwrap();
+
+        let (mut b_txs, b_inclusion_proof, b_completeness_proof) =
+            service.extract_relevant_blobs_with_proof(&block, DaNamespace::ToBatchProver);
+        assert_eq!(b_completeness_proof.len(), 3);
+        assert_eq!(b_txs.len(), 1);
+        assert_eq!(b_txs[0].full_data(), blob.as_slice());
+
+        let verifier = BitcoinVerifier::new(RollupParams {
+            to_batch_proof_prefix: TO_BATCH_PROOF_PREFIX.to_vec(),
+            to_light_client_prefix: TO_LIGHT_CLIENT_PREFIX.to_vec(),
+            fork2_da_activation_height: 0,
+        });
+
+        // Correct reassembled aggregate
+        {
+            assert_eq!(
+                verifier.verify_transactions(
+                    &block.header,
+                    &b_txs,
+                    b_inclusion_proof.clone(),
+                    b_completeness_proof.clone(),
+                    DaNamespace::ToBatchProver,
+                ),
+                Ok(()),
+            );
+        }
+
+        // Aggregate blob which doesn't match its chunks should fail
+        {
+            let mut b_txs = b_txs.clone();
+
+            let mut tampered = blob.clone();
+            tampered[MAX_TXBODY_SIZE] ^= 1;
+            b_txs[0] = BlobWithSender::new(tampered, b_txs[0].sender.0.clone(), b_txs[0].hash);
+            assert_eq!(
+                verifier.verify_transactions(
+                    &block.header,
+                    &b_txs,
+                    b_inclusion_proof.clone(),
+                    b_completeness_proof.clone(),
+                    DaNamespace::ToBatchProver,
+                ),
+                Err(ValidationError::BlobContentWasModified),
+            );
+        }
+
+        // Aggregate blob truncated to its first chunk should fail
+        {
+            let mut b_txs = b_txs.clone();
+
+            b_txs[0] = BlobWithSender::new(
+                blob[..MAX_TXBODY_SIZE].to_vec(),
+                b_txs[0].sender.0.clone(),
+                b_txs[0].hash,
+            );
+            assert_eq!(
+                verifier.verify_transactions(
+                    &block.header,
+                    &b_txs,
+                    b_inclusion_proof.clone(),
+                    b_completeness_proof.clone(),
+                    DaNamespace::ToBatchProver,
+                ),
+                Err(ValidationError::BlobContentWasModified),
+            );
+        }
+
+        // Chunks may be mined in earlier blocks the verifier doesn't see, so an aggregate
+        // whose chunks are unavailable may be left out
+        {
+            assert_eq!(
+                verifier.verify_transactions(
+                    &block.header,
+                    &[],
+                    b_inclusion_proof.clone(),
+                    b_completeness_proof.clone(),
+                    DaNamespace::ToBatchProver,
+                ),
+                Ok(()),
+            );
+        }
+
+        // Aggregates are ignored before the Fork2 DA activation height
+        {
+            let verifier = BitcoinVerifier::new(RollupParams {
+                to_batch_proof_prefix: TO_BATCH_PROOF_PREFIX.to_vec(),
+                to_light_client_prefix: TO_LIGHT_CLIENT_PREFIX.to_vec(),
+                fork2_da_activation_height: block.header.height() + 1,
+            });
+
+            assert_eq!(
+                verifier.verify_transactions(
+                    &block.header,
+                    &[],
+                    b_inclusion_proof.clone(),
+                    b_completeness_proof.clone(),
+                    DaNamespace::ToBatchProver,
+                ),
+                Ok(()),
+            );
+            assert_eq!(
+                verifier.verify_transactions(
+                    &block.header,
+                    &b_txs,
+                    b_inclusion_proof,
+                    b_completeness_proof,
+                    DaNamespace::ToBatchProver,
+                ),
+                Err(ValidationError::IncorrectCompletenessProof),
+            );
+        }
+
+        task_manager.abort().await;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "native")]
+#[tokio::test]
+async fn test_batch_proof_aggregate_verifier() -> Result<()> {
+    TestCaseRunner::new(BatchProofAggregateVerifierTest)
+        .set_citrea_path(get_citrea_path())
+        .run()
+        .await
+}
@@ -87,4 +87,8 @@ where
     ) -> Result<Self::GenesisConfig, anyhow::Error> {
         crate::genesis_config::get_genesis_config(genesis_paths)
     }
+
+    fn revert_latest_call(&mut self) {
+        self.evm.revert_latest_call();
+    }
 }
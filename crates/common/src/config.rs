@@ -14,6 +14,10 @@ impl FromEnv for PruningConfig {
     fn from_env() -> anyhow::Result<Self> {
         Ok(PruningConfig {
             distance: std::env::var("PRUNING_DISTANCE")?.parse()?,
+            criteria: std::env::var("PRUNING_CRITERIA")
+                .ok()
+                .map(|criteria| serde_json::from_str(&criteria))
+                .transpose()?,
         })
     }
 }
@@ -311,6 +315,13 @@ pub struct SequencerConfig {
     pub da_update_interval_ms: u64,
     /// Block production interval in ms
     pub block_production_interval_ms: u64,
+    /// Order in which mempool transactions are tried for an L2 block
+    #[serde(default)]
+    pub ordering_policy: TxOrderingPolicyConfig,
+    /// Max total estimated state diff size in bytes of the transactions in an L2 block.
+    /// Transactions over the budget are left in the mempool for the following blocks.
+    #[serde(default)]
+    pub max_l1_diff_bytes_per_block: Option<u64>,
 }
 
 impl Default for SequencerConfig {
@@ -324,6 +335,8 @@ impl Default for SequencerConfig {
             block_production_interval_ms: 100,
             da_update_interval_ms: 100,
             mempool_conf: Default::default(),
+            ordering_policy: Default::default(),
+            max_l1_diff_bytes_per_block: None,
         }
     }
 }
@@ -340,9 +353,38 @@ impl FromEnv for SequencerConfig {
             mempool_conf: SequencerMempoolConfig::from_env()?,
             da_update_interval_ms: std::env::var("DA_UPDATE_INTERVAL_MS")?.parse()?,
             block_production_interval_ms: std::env::var("BLOCK_PRODUCTION_INTERVAL_MS")?.parse()?,
+            ordering_policy: std::env::var("TX_ORDERING_POLICY")
+                .ok()
+                .map(|policy| serde_json::from_str(&policy))
+                .transpose()?
+                .unwrap_or_default(),
+            max_l1_diff_bytes_per_block: std::env::var("MAX_L1_DIFF_BYTES_PER_BLOCK")
+                .ok()
+                .map(|size| size.parse())
+                .transpose()?,
         })
     }
 }
+
+/// Selects the order in which the sequencer tries mempool transactions for an L2 block.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TxOrderingPolicyConfig {
+    /// Highest priority fee first.
+    #[default]
+    PriorityFee,
+    /// Earliest arrival in the mempool first.
+    FirstComeFirstServed,
+    /// Highest priority fee first, capping what a single sender gets in every L2 block.
+    /// Transactions over a cap are left in the mempool for the following blocks.
+    FairShare {
+        /// Max number of transactions of a sender per L2 block.
+        max_txs_per_sender: Option<u64>,
+        /// Max total gas limit of the transactions of a sender per L2 block.
+        max_gas_per_sender: Option<u64>,
+    },
+}
+
 /// Mempool Config for the sequencer
 /// Read: https://github.com/ledgerwatch/erigon/wiki/Transaction-Pool-Design
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
@@ -393,6 +435,7 @@ impl FromEnv for SequencerMempoolConfig {
 mod tests {
     use std::io::Write;
 
+    use citrea_pruning::PruningCriteria;
     use tempfile::NamedTempFile;
 
     use super::*;
@@ -471,6 +514,49 @@ mod tests {
         assert_eq!(config, expected);
     }
 
+    #[test]
+    fn test_pruning_config_with_criteria() {
+        let config = r#"
+            distance = 1000
+
+            [criteria]
+            type = "and"
+
+            [[criteria.criteria]]
+            type = "verified_batch_proof"
+
+            [[criteria.criteria]]
+            type = "or"
+            criteria = [
+                { type = "age", max_age_secs = 86400 },
+                { type = "db_size", max_size_bytes = 1073741824 },
+            ]
+        "#;
+
+        let config_file = create_config_from(config);
+
+        let config: PruningConfig = from_toml_path(config_file.path()).unwrap();
+        let expected = PruningConfig {
+            distance: 1000,
+            criteria: Some(PruningCriteria::And {
+                criteria: vec![
+                    PruningCriteria::VerifiedBatchProof,
+                    PruningCriteria::Or {
+                        criteria: vec![
+                            PruningCriteria::Age {
+                                max_age_secs: 86400,
+                            },
+                            PruningCriteria::DbSize {
+                                max_size_bytes: 1073741824,
+                            },
+                        ],
+                    },
+                ],
+            }),
+        };
+        assert_eq!(config, expected);
+    }
+
     #[test]
     fn test_correct_prover_config() {
         let config = r#"
@@ -498,6 +584,10 @@ mod tests {
             deposit_mempool_fetch_limit = 10
             da_update_interval_ms = 1000
             block_production_interval_ms = 1000
+            max_l1_diff_bytes_per_block = 4000
+            [ordering_policy]
+            type = "fair_share"
+            max_txs_per_sender = 4
             [mempool_conf]
             pending_tx_limit = 100000
             pending_tx_size = 200
@@ -529,6 +619,11 @@ mod tests {
             },
             da_update_interval_ms: 1000,
             block_production_interval_ms: 1000,
+            ordering_policy: TxOrderingPolicyConfig::FairShare {
+                max_txs_per_sender: Some(4),
+                max_gas_per_sender: None,
+            },
+            max_l1_diff_bytes_per_block: Some(4000),
         };
         assert_eq!(config, expected);
     }
@@ -559,6 +654,11 @@ mod tests {
         std::env::set_var("DEPOSIT_MEMPOOL_FETCH_LIMIT", "10");
         std::env::set_var("DA_UPDATE_INTERVAL_MS", "1000");
         std::env::set_var("BLOCK_PRODUCTION_INTERVAL_MS", "1000");
+        std::env::set_var(
+            "TX_ORDERING_POLICY",
+            r#"{"type":"first_come_first_served"}"#,
+        );
+        std::env::set_var("MAX_L1_DIFF_BYTES_PER_BLOCK", "4000");
         std::env::set_var("PENDING_TX_LIMIT", "100000");
         std::env::set_var("PENDING_TX_SIZE", "200");
         std::env::set_var("QUEUE_TX_LIMIT", "100000");
@@ -586,6 +686,8 @@ mod tests {
             },
             da_update_interval_ms: 1000,
             block_production_interval_ms: 1000,
+            ordering_policy: TxOrderingPolicyConfig::FirstComeFirstServed,
+            max_l1_diff_bytes_per_block: Some(4000),
         };
         assert_eq!(sequencer_config, expected);
     }
@@ -643,7 +745,10 @@ mod tests {
                 sequencer_client_url: "http://0.0.0.0:12346".to_string(),
                 include_tx_body: true,
                 sync_blocks_count: default_sync_blocks_count(),
-                pruning_config: Some(PruningConfig { distance: 1000 }),
+                pruning_config: Some(PruningConfig {
+                    distance: 1000,
+                    criteria: None,
+                }),
             }),
             da: sov_mock_da::MockDaConfig {
                 sender_address: [0; 32].into(),
@@ -1,16 +1,52 @@
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use anyhow::anyhow;
 use backoff::future::retry as retry_backoff;
 use backoff::ExponentialBackoffBuilder;
+use sov_db::ledger_db::SharedLedgerOps;
+pub use sov_db::schema::types::PublishedData;
+use sov_db::schema::types::StoredPendingPublication;
 use sov_rollup_interface::da::{BlockHeaderTrait, SequencerCommitment};
 use sov_rollup_interface::services::da::{DaService, SlotData};
 use sov_rollup_interface::zk::Proof;
-use tokio::sync::Mutex;
-use tracing::warn;
+use tokio::sync::{mpsc, Mutex};
+use tokio::time::sleep;
+use tracing::{error, info, warn};
 
 use crate::cache::L1BlockCache;
+use crate::reorg::{find_fork_height, L1ReorgDetector};
+
+/// Interval between checks whether a DA tx is finalized.
+const PUBLICATION_RECEIPT_POLL_INTERVAL: Duration = Duration::from_secs(60);
+/// Time after which a DA tx is assumed to be dropped.
+pub const PUBLICATION_RECEIPT_TIMEOUT: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Event of [`sync_l1`].
+pub enum L1SyncEvent<Da: DaService> {
+    /// The next L1 block of the canonical chain.
+    Block(Da::FilteredBlock),
+    /// The L1 blocks above `fork_height` were orphaned by a reorg.
+    /// The blocks of the new canonical chain above it follow.
+    Reorg {
+        fork_height: u64,
+        /// Hashes of the orphaned blocks which were already sent.
+        orphaned_hashes: Vec<[u8; 32]>,
+    },
+}
+
+/// Halts the node if rolling back the L1 blocks orphaned above `fork_height` failed.
+///
+/// The L1 sync has already moved back to the fork point, going on with the orphaned data
+/// still recorded would corrupt the ledger.
+pub fn expect_l1_rollback(fork_height: u64, result: anyhow::Result<()>) {
+    if let Err(e) = result {
+        panic!(
+            "Could not roll back L1 blocks orphaned after height {}: {:?}",
+            fork_height, e
+        );
+    }
+}
 
 pub async fn get_da_block_at_height<Da: DaService>(
     da_service: &Arc<Da>,
@@ -72,3 +108,225 @@ pub async fn extract_zk_proofs<Da: DaService>(
         .extract_relevant_zk_proofs(l1_block, prover_da_pub_key)
         .await
 }
+
+/// Records the DA tx `tx_id` publishing `data` in the ledger, then waits until it is finalized
+/// and stores its receipt with [`wait_for_publication_receipt`].
+pub async fn store_publication_receipt<Da, DB>(
+    da_service: Arc<Da>,
+    ledger_db: DB,
+    tx_id: [u8; 32],
+    data: PublishedData,
+) where
+    Da: DaService,
+    DB: SharedLedgerOps,
+{
+    let pending = StoredPendingPublication {
+        data,
+        sent_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs(),
+    };
+    if let Err(e) = ledger_db.put_pending_publication(tx_id, &pending) {
+        error!("Failed to record pending publication of {:?}: {}", data, e);
+    }
+    wait_for_publication_receipt(da_service, ledger_db, tx_id, pending).await;
+}
+
+/// Resumes waiting for the receipts of the DA txs recorded by [`store_publication_receipt`]
+/// before the node was restarted.
+pub fn resume_publication_receipts<Da, DB>(da_service: Arc<Da>, ledger_db: DB) -> anyhow::Result<()>
+where
+    Da: DaService,
+    DB: SharedLedgerOps + Clone + Send + Sync + 'static,
+{
+    for (tx_id, pending) in ledger_db.get_pending_publications()? {
+        tokio::spawn(wait_for_publication_receipt(
+            da_service.clone(),
+            ledger_db.clone(),
+            tx_id,
+            pending,
+        ));
+    }
+    Ok(())
+}
+
+/// Waits until the DA tx `tx_id` is finalized, then stores its receipt and removes the tx from
+/// the pending publications. Gives up [`PUBLICATION_RECEIPT_TIMEOUT`] after the tx was sent,
+/// e.g. if it was dropped.
+async fn wait_for_publication_receipt<Da, DB>(
+    da_service: Arc<Da>,
+    ledger_db: DB,
+    tx_id: [u8; 32],
+    pending: StoredPendingPublication,
+) where
+    Da: DaService,
+    DB: SharedLedgerOps,
+{
+    let data = pending.data;
+    let deadline = UNIX_EPOCH + Duration::from_secs(pending.sent_at) + PUBLICATION_RECEIPT_TIMEOUT;
+    let receipt = loop {
+        match da_service.get_publication_receipt(tx_id).await {
+            Ok(Some(receipt)) => break Some(receipt),
+            Ok(None) => {}
+            Err(e) => warn!("Failed to get publication receipt of {:?}: {}", data, e),
+        }
+        if SystemTime::now() > deadline {
+            warn!(
+                "DA tx {} publishing {:?} was not finalized in time, no receipt is stored",
+                hex::encode(tx_id),
+                data
+            );
+            break None;
+        }
+        sleep(PUBLICATION_RECEIPT_POLL_INTERVAL).await;
+    };
+
+    if let Some(receipt) = receipt {
+        let result = match data {
+            PublishedData::SequencerCommitment(merkle_root) => {
+                ledger_db.put_commitment_publication_receipt(merkle_root, &receipt)
+            }
+            PublishedData::BatchProof(proof_hash) => {
+                ledger_db.put_batch_proof_publication_receipt(proof_hash, &receipt)
+            }
+        };
+        match result {
+            Ok(()) => info!("{:?} was published in L1 block {}", data, receipt.l1_height),
+            Err(e) => {
+                // keep it pending, so it is stored after a restart
+                error!("Failed to store publication receipt of {:?}: {}", data, e);
+                return;
+            }
+        }
+    }
+
+    if let Err(e) = ledger_db.delete_pending_publication(tx_id) {
+        error!("Failed to delete pending publication of {:?}: {}", data, e);
+    }
+}
+
+/// Sends the finalized L1 blocks after `start_l1_height` in order.
+///
+/// If a block sent before is orphaned by a reorg, a [`L1SyncEvent::Reorg`] is sent
+/// and syncing continues from the fork point.
+pub async fn sync_l1<Da>(
+    start_l1_height: u64,
+    da_service: Arc<Da>,
+    sender: mpsc::Sender<L1SyncEvent<Da>>,
+    l1_block_cache: Arc<Mutex<L1BlockCache<Da>>>,
+) where
+    Da: DaService,
+{
+    let mut l1_height = start_l1_height;
+    let mut reorg_detector = L1ReorgDetector::default();
+    info!("Starting to sync from L1 height {}", l1_height);
+
+    'block_sync: loop {
+        // TODO: for a node, the da block at slot_height might not have been finalized yet
+        // should wait for it to be finalized
+        let last_finalized_l1_block_header =
+            match da_service.get_last_finalized_block_header().await {
+                Ok(header) => header,
+                Err(e) => {
+                    error!("Could not fetch last finalized L1 block header: {}", e);
+                    sleep(Duration::from_secs(2)).await;
+                    continue;
+                }
+            };
+
+        let new_l1_height = last_finalized_l1_block_header.height();
+
+        // Without a new block, the last one is compared against the finalized header
+        let finalized_hash: [u8; 32] = last_finalized_l1_block_header.hash().into();
+        let tip_orphaned = new_l1_height == l1_height
+            && reorg_detector
+                .hash_at(l1_height)
+                .is_some_and(|hash| hash != finalized_hash);
+        if tip_orphaned {
+            l1_height =
+                match handle_l1_reorg(&da_service, &mut reorg_detector, &sender, &l1_block_cache)
+                    .await
+                {
+                    Ok(fork_height) => fork_height,
+                    Err(e) => {
+                        error!("Could not handle L1 reorg: {}", e);
+                        sleep(Duration::from_secs(2)).await;
+                        continue;
+                    }
+                };
+            continue;
+        }
+
+        for block_number in l1_height + 1..=new_l1_height {
+            let l1_block =
+                match get_da_block_at_height(&da_service, block_number, l1_block_cache.clone())
+                    .await
+                {
+                    Ok(block) => block,
+                    Err(e) => {
+                        error!("Could not fetch last finalized L1 block: {}", e);
+                        sleep(Duration::from_secs(2)).await;
+                        continue 'block_sync;
+                    }
+                };
+
+            if !reorg_detector.is_connected(block_number, l1_block.header().prev_hash().into()) {
+                l1_height = match handle_l1_reorg(
+                    &da_service,
+                    &mut reorg_detector,
+                    &sender,
+                    &l1_block_cache,
+                )
+                .await
+                {
+                    Ok(fork_height) => fork_height,
+                    Err(e) => {
+                        error!("Could not handle L1 reorg: {}", e);
+                        sleep(Duration::from_secs(2)).await;
+                        l1_height
+                    }
+                };
+                continue 'block_sync;
+            }
+
+            if block_number > l1_height {
+                l1_height = block_number;
+                reorg_detector.record(block_number, l1_block.hash());
+                if let Err(e) = sender.send(L1SyncEvent::Block(l1_block)).await {
+                    error!("Could not notify about L1 block: {}", e);
+                    continue 'block_sync;
+                }
+            }
+        }
+
+        sleep(Duration::from_secs(2)).await;
+    }
+}
+
+/// Rolls the sync back to the newest block still on the canonical chain and notifies about it.
+/// Returns the height of that block.
+async fn handle_l1_reorg<Da: DaService>(
+    da_service: &Arc<Da>,
+    reorg_detector: &mut L1ReorgDetector,
+    sender: &mpsc::Sender<L1SyncEvent<Da>>,
+    l1_block_cache: &Arc<Mutex<L1BlockCache<Da>>>,
+) -> anyhow::Result<u64> {
+    let fork_height = find_fork_height(da_service, reorg_detector).await?;
+    let orphaned_hashes = reorg_detector.rollback(fork_height);
+    l1_block_cache.lock().await.invalidate_above(fork_height);
+
+    warn!(
+        "L1 reorg detected, {} processed blocks above L1 height {} were orphaned",
+        orphaned_hashes.len(),
+        fork_height
+    );
+    sender
+        .send(L1SyncEvent::Reorg {
+            fork_height,
+            orphaned_hashes,
+        })
+        .await
+        .map_err(|e| anyhow!("Could not notify about L1 reorg: {}", e))?;
+    Ok(fork_height)
+}
@@ -5,6 +5,7 @@ pub mod cache;
 pub mod config;
 pub mod da;
 pub mod error;
+pub mod reorg;
 pub mod rpc;
 pub mod tasks;
 pub mod utils;
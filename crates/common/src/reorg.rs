@@ -0,0 +1,141 @@
+//! Detection of L1 reorgs orphaning blocks which were already processed.
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail};
+use sov_rollup_interface::services::da::{DaService, SlotData};
+
+/// Number of recent L1 blocks whose hashes are tracked.
+/// Reorgs orphaning older blocks can not be recovered from.
+pub const MAX_TRACKED_L1_BLOCKS: usize = 256;
+
+/// Tracks the hashes of recently processed L1 blocks, to tell whether the next L1 block
+/// still builds on them.
+///
+/// Hashes are only kept in memory, so a reorg of the last processed block while the node
+/// was stopped is not detected.
+#[derive(Debug)]
+pub struct L1ReorgDetector {
+    hashes: BTreeMap<u64, [u8; 32]>,
+    capacity: usize,
+}
+
+impl Default for L1ReorgDetector {
+    fn default() -> Self {
+        Self::new(MAX_TRACKED_L1_BLOCKS)
+    }
+}
+
+impl L1ReorgDetector {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            hashes: BTreeMap::new(),
+            capacity,
+        }
+    }
+
+    /// Records the hash of a processed L1 block, forgetting the oldest one if full.
+    pub fn record(&mut self, height: u64, hash: [u8; 32]) {
+        self.hashes.insert(height, hash);
+        while self.hashes.len() > self.capacity {
+            self.hashes.pop_first();
+        }
+    }
+
+    /// Hash of the processed L1 block at `height`, if tracked.
+    pub fn hash_at(&self, height: u64) -> Option<[u8; 32]> {
+        self.hashes.get(&height).copied()
+    }
+
+    /// Height and hash of the last processed L1 block.
+    pub fn tip(&self) -> Option<(u64, [u8; 32])> {
+        self.hashes
+            .last_key_value()
+            .map(|(height, hash)| (*height, *hash))
+    }
+
+    /// Whether an L1 block at `height` with parent `prev_hash` builds on the processed blocks.
+    /// Blocks whose parent is not tracked are assumed to.
+    pub fn is_connected(&self, height: u64, prev_hash: [u8; 32]) -> bool {
+        match height
+            .checked_sub(1)
+            .and_then(|parent| self.hash_at(parent))
+        {
+            Some(parent_hash) => parent_hash == prev_hash,
+            None => true,
+        }
+    }
+
+    /// Forgets the blocks above `fork_height`, returning the hashes of the orphaned blocks.
+    pub fn rollback(&mut self, fork_height: u64) -> Vec<[u8; 32]> {
+        self.hashes
+            .split_off(&(fork_height + 1))
+            .into_values()
+            .collect()
+    }
+
+    /// Heights tracked, newest first.
+    fn heights_desc(&self) -> Vec<u64> {
+        self.hashes.keys().rev().copied().collect()
+    }
+}
+
+/// Finds the newest processed L1 block which is still on the canonical chain.
+///
+/// Blocks are fetched from `da_service` directly, the [`crate::cache::L1BlockCache`] might
+/// still hold orphaned blocks.
+pub async fn find_fork_height<Da: DaService>(
+    da_service: &Arc<Da>,
+    reorg_detector: &L1ReorgDetector,
+) -> anyhow::Result<u64> {
+    for height in reorg_detector.heights_desc() {
+        let canonical = da_service
+            .get_block_at(height)
+            .await
+            .map_err(|e| anyhow!("Error while fetching L1 block {}: {}", height, e))?;
+        if reorg_detector.hash_at(height) == Some(canonical.hash()) {
+            return Ok(height);
+        }
+    }
+    bail!(
+        "L1 reorg is deeper than the {} tracked blocks",
+        reorg_detector.capacity
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::L1ReorgDetector;
+
+    #[test]
+    fn detects_orphaned_parent() {
+        let mut detector = L1ReorgDetector::new(3);
+        for height in 1..=4 {
+            detector.record(height, [height as u8; 32]);
+        }
+
+        // Only the newest blocks are kept
+        assert_eq!(detector.hash_at(1), None);
+        assert_eq!(detector.tip(), Some((4, [4; 32])));
+
+        assert!(detector.is_connected(5, [4; 32]));
+        assert!(!detector.is_connected(5, [9; 32]));
+        // Untracked parents are not reorgs
+        assert!(detector.is_connected(2, [9; 32]));
+    }
+
+    #[test]
+    fn rollback_returns_orphaned_hashes() {
+        let mut detector = L1ReorgDetector::default();
+        for height in 10..=14 {
+            detector.record(height, [height as u8; 32]);
+        }
+
+        assert_eq!(detector.rollback(12), vec![[13; 32], [14; 32]]);
+        assert_eq!(detector.tip(), Some((12, [12; 32])));
+        assert!(detector.rollback(12).is_empty());
+
+        detector.record(13, [42; 32]);
+        assert!(detector.is_connected(14, [42; 32]));
+    }
+}
@@ -3,11 +3,12 @@ mod gas_price;
 mod subscription;
 mod trace;
 
+use std::collections::HashSet;
 use std::sync::Arc;
 
 #[cfg(feature = "local")]
 pub use citrea_evm::DevSigner;
-use citrea_evm::{Evm, Filter};
+use citrea_evm::{Bridge, BridgeWithdrawal, Evm, EvmAccountProof, Filter};
 use citrea_sequencer::SequencerRpcClient;
 pub use ethereum::{EthRpcConfig, Ethereum};
 pub use gas_price::fee_history::FeeHistoryCacheConfig;
@@ -15,9 +16,14 @@ pub use gas_price::gas_oracle::GasPriceOracleConfig;
 use jsonrpsee::http_client::HttpClientBuilder;
 use jsonrpsee::types::ErrorObjectOwned;
 use jsonrpsee::RpcModule;
-use reth_primitives::{keccak256, BlockNumberOrTag, Bytes, B256, U256};
+use reth_primitives::{keccak256, Address, BlockNumberOrTag, Bytes, B256, U256};
 use reth_rpc_eth_types::EthApiError;
+use reth_rpc_types::trace::filter::TraceFilter;
 use reth_rpc_types::trace::geth::{GethDebugTracingOptions, GethTrace};
+use reth_rpc_types::trace::parity::{
+    LocalizedTransactionTrace, TraceResultsWithTransactionHash, TraceType,
+};
+use reth_rpc_types::txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus};
 use reth_rpc_types::{FeeHistory, Index};
 use serde_json::json;
 use sov_db::ledger_db::{LedgerDB, SharedLedgerOps};
@@ -25,7 +31,9 @@ use sov_ledger_rpc::LedgerRpcClient;
 use sov_modules_api::da::BlockHeaderTrait;
 use sov_modules_api::utils::to_jsonrpsee_error_object;
 use sov_modules_api::WorkingSet;
+use sov_rollup_interface::rpc::LedgerRpcProvider;
 use sov_rollup_interface::services::da::DaService;
+use sov_state::storage::NativeStorage;
 use tokio::join;
 use tokio::sync::broadcast;
 use trace::{debug_trace_by_block_number, handle_debug_trace_chain};
@@ -48,6 +56,26 @@ pub struct SyncStatus {
     pub l2_status: LayerStatus,
 }
 
+/// Withdrawal from the `Bridge` contract returned by `citrea_getWithdrawals`.
+///
+/// Only withdrawals indexed by the node are returned. Withdrawals in L2 blocks the node
+/// processed before it had the withdrawal index are missing; such a node must resync from
+/// genesis to serve them.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalWithProof {
+    #[serde(flatten)]
+    pub withdrawal: BridgeWithdrawal,
+    /// Whether the L2 block of the withdrawal is covered by the last verified batch proof.
+    pub verified: bool,
+    /// Proof of the `withdrawalUTXOs[index]` slots of the bridge.
+    /// For verified withdrawals it is taken at the last L2 block of the last verified batch proof,
+    /// so it opens against the final state root of that proof. Otherwise it is taken at the
+    /// L2 block of the withdrawal.
+    /// `None` if the history of that block was pruned.
+    pub proof: Option<EvmAccountProof>,
+}
+
 pub fn get_ethereum_rpc<C: sov_modules_api::Context, Da: DaService>(
     da_service: Arc<Da>,
     eth_rpc_config: EthRpcConfig,
@@ -55,7 +83,10 @@ pub fn get_ethereum_rpc<C: sov_modules_api::Context, Da: DaService>(
     ledger_db: LedgerDB,
     sequencer_client_url: Option<String>,
     soft_confirmation_rx: Option<broadcast::Receiver<u64>>,
-) -> RpcModule<Ethereum<C, Da>> {
+) -> RpcModule<Ethereum<C, Da>>
+where
+    C::Storage: NativeStorage,
+{
     // Unpack config
     let EthRpcConfig {
         #[cfg(feature = "local")]
@@ -91,7 +122,10 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
     // Checks wether the running node is a sequencer or not, if it is not a sequencer it should also have methods like eth_sendRawTransaction here.
     is_sequencer: bool,
     enable_subscriptions: bool,
-) -> Result<(), jsonrpsee::core::RegisterMethodError> {
+) -> Result<(), jsonrpsee::core::RegisterMethodError>
+where
+    C::Storage: NativeStorage,
+{
     rpc.register_async_method("web3_clientVersion", |_, ethereum, _| async move {
         Ok::<_, ErrorObjectOwned>(ethereum.web3_client_version.clone())
     })?;
@@ -454,9 +488,9 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
             let evm = Evm::<C>::default();
             let mut working_set = WorkingSet::new(ethereum.storage.clone());
 
+            // Errors if the transaction is known but its history has been pruned
             let tx = evm
-                .get_transaction_by_hash(tx_hash, &mut working_set)
-                .unwrap()
+                .get_transaction_by_hash(tx_hash, &mut working_set)?
                 .ok_or_else(|| EthApiError::UnknownBlockOrTxIndex)?;
             let trace_idx: u64 = tx
                 .transaction_index
@@ -480,15 +514,126 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
         },
     )?;
 
-    rpc.register_async_method("txpool_content", |_, _, _| async move {
-        // This is a simple mock for serde.
-        let json = json!({
-            "pending": {},
-            "queued": {}
-        });
+    rpc.register_blocking_method::<Result<Vec<LocalizedTransactionTrace>, ErrorObjectOwned>, _>(
+        "trace_block",
+        move |parameters, ethereum, _| {
+            let mut params = parameters.sequence();
 
-        Ok::<_, ErrorObjectOwned>(json)
-    })?;
+            let block_number: BlockNumberOrTag = params.next()?;
+
+            let evm = Evm::<C>::default();
+            let mut working_set = WorkingSet::new(ethereum.storage.clone());
+
+            let block_number = evm.block_number_for_id(&block_number, &mut working_set)?;
+            evm.trace_block(block_number, &mut working_set)
+        },
+    )?;
+
+    rpc.register_blocking_method::<Result<Option<Vec<LocalizedTransactionTrace>>, ErrorObjectOwned>, _>(
+        "trace_transaction",
+        move |parameters, ethereum, _| {
+            let mut params = parameters.sequence();
+
+            let tx_hash: B256 = params.next()?;
+
+            let evm = Evm::<C>::default();
+            let mut working_set = WorkingSet::new(ethereum.storage.clone());
+
+            evm.trace_transaction(tx_hash, &mut working_set)
+        },
+    )?;
+
+    rpc.register_blocking_method::<Result<Vec<LocalizedTransactionTrace>, ErrorObjectOwned>, _>(
+        "trace_filter",
+        move |parameters, ethereum, _| {
+            let mut params = parameters.sequence();
+
+            let filter: TraceFilter = params.next()?;
+
+            let evm = Evm::<C>::default();
+            let mut working_set = WorkingSet::new(ethereum.storage.clone());
+
+            evm.trace_filter(filter, &mut working_set)
+        },
+    )?;
+
+    rpc.register_blocking_method::<Result<Vec<TraceResultsWithTransactionHash>, ErrorObjectOwned>, _>(
+        "trace_replayBlockTransactions",
+        move |parameters, ethereum, _| {
+            let mut params = parameters.sequence();
+
+            let block_number: BlockNumberOrTag = params.next()?;
+            let trace_types: HashSet<TraceType> = params.next()?;
+
+            let evm = Evm::<C>::default();
+            let mut working_set = WorkingSet::new(ethereum.storage.clone());
+
+            let block_number = evm.block_number_for_id(&block_number, &mut working_set)?;
+            evm.trace_replay_block_transactions(block_number, trace_types, &mut working_set)
+        },
+    )?;
+
+    rpc.register_blocking_method::<Result<EvmAccountProof, ErrorObjectOwned>, _>(
+        "citrea_getProof",
+        move |parameters, ethereum, _| {
+            let mut params = parameters.sequence();
+
+            let address: Address = params.next()?;
+            let storage_keys: Vec<U256> = params.next()?;
+            let block_number: Option<BlockNumberOrTag> = params.optional_next()?;
+
+            let evm = Evm::<C>::default();
+            let mut working_set = WorkingSet::new(ethereum.storage.clone());
+
+            evm.get_proof(address, storage_keys, block_number, &mut working_set)
+        },
+    )?;
+
+    rpc.register_blocking_method::<Result<Vec<WithdrawalWithProof>, ErrorObjectOwned>, _>(
+        "citrea_getWithdrawals",
+        move |parameters, ethereum, _| {
+            let mut params = parameters.sequence();
+
+            let from_index: u64 = params.next()?;
+            let to_index: u64 = params.next()?;
+
+            let evm = Evm::<C>::default();
+            let mut working_set = WorkingSet::new(ethereum.storage.clone());
+
+            let withdrawals = evm.get_withdrawals(from_index, to_index, &mut working_set)?;
+
+            let last_verified_l2_height = last_verified_l2_height(&ethereum.ledger_db)
+                .map_err(|e| to_jsonrpsee_error_object("LEDGER_DB_ERROR", e))?;
+            let pruned: ErrorObjectOwned = EthApiError::PrunedHistoryUnavailable.into();
+
+            withdrawals
+                .into_iter()
+                .map(|withdrawal| {
+                    let block_number = withdrawal.block_number.to::<u64>();
+                    let (verified, proof_block_number) = match last_verified_l2_height {
+                        Some(height) if block_number <= height => (true, height),
+                        _ => (false, block_number),
+                    };
+                    // A pruned proof block must not fail the whole range
+                    let proof = match evm.get_proof(
+                        Bridge::address(),
+                        Bridge::withdrawal_utxo_slots(withdrawal.index.to()).to_vec(),
+                        Some(BlockNumberOrTag::Number(proof_block_number)),
+                        &mut working_set,
+                    ) {
+                        Ok(proof) => Some(proof),
+                        Err(e) if e == pruned => None,
+                        Err(e) => return Err(e),
+                    };
+                    Ok(WithdrawalWithProof {
+                        withdrawal,
+                        verified,
+                        proof,
+                    })
+                })
+                .collect()
+        },
+    )?;
 
     rpc.register_async_method(
         "eth_getUncleByBlockHashAndIndex",
@@ -588,6 +733,84 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
             },
         )?;
 
+        rpc.register_async_method::<Result<TxpoolContent, ErrorObjectOwned>, _, _>(
+            "txpool_content",
+            |_, ethereum, _| async move {
+                // txpool lives in the sequencer, forward the request
+                match ethereum
+                    .sequencer_client
+                    .as_ref()
+                    .unwrap()
+                    .txpool_content()
+                    .await
+                {
+                    Ok(content) => Ok(content),
+                    Err(e) => match e {
+                        jsonrpsee::core::client::Error::Call(e_owned) => Err(e_owned),
+                        _ => Err(to_jsonrpsee_error_object("SEQUENCER_CLIENT_ERROR", e)),
+                    },
+                }
+            },
+        )?;
+
+        rpc.register_async_method::<Result<TxpoolContentFrom, ErrorObjectOwned>, _, _>(
+            "txpool_contentFrom",
+            |parameters, ethereum, _| async move {
+                let from: Address = parameters.one()?;
+                match ethereum
+                    .sequencer_client
+                    .as_ref()
+                    .unwrap()
+                    .txpool_content_from(from)
+                    .await
+                {
+                    Ok(content) => Ok(content),
+                    Err(e) => match e {
+                        jsonrpsee::core::client::Error::Call(e_owned) => Err(e_owned),
+                        _ => Err(to_jsonrpsee_error_object("SEQUENCER_CLIENT_ERROR", e)),
+                    },
+                }
+            },
+        )?;
+
+        rpc.register_async_method::<Result<TxpoolStatus, ErrorObjectOwned>, _, _>(
+            "txpool_status",
+            |_, ethereum, _| async move {
+                match ethereum
+                    .sequencer_client
+                    .as_ref()
+                    .unwrap()
+                    .txpool_status()
+                    .await
+                {
+                    Ok(status) => Ok(status),
+                    Err(e) => match e {
+                        jsonrpsee::core::client::Error::Call(e_owned) => Err(e_owned),
+                        _ => Err(to_jsonrpsee_error_object("SEQUENCER_CLIENT_ERROR", e)),
+                    },
+                }
+            },
+        )?;
+
+        rpc.register_async_method::<Result<TxpoolInspect, ErrorObjectOwned>, _, _>(
+            "txpool_inspect",
+            |_, ethereum, _| async move {
+                match ethereum
+                    .sequencer_client
+                    .as_ref()
+                    .unwrap()
+                    .txpool_inspect()
+                    .await
+                {
+                    Ok(inspect) => Ok(inspect),
+                    Err(e) => match e {
+                        jsonrpsee::core::client::Error::Call(e_owned) => Err(e_owned),
+                        _ => Err(to_jsonrpsee_error_object("SEQUENCER_CLIENT_ERROR", e)),
+                    },
+                }
+            },
+        )?;
+
         rpc.register_async_method::<Result<SyncStatus, ErrorObjectOwned>, _, _>(
             "citrea_syncStatus",
             |_, ethereum, _| async move {
@@ -612,7 +835,8 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
 
                 // get l2 synced block number
 
-                let head_soft_confirmation = ethereum.ledger_db.get_head_soft_confirmation();
+                let head_soft_confirmation =
+                    SharedLedgerOps::get_head_soft_confirmation(&ethereum.ledger_db);
 
                 let l2_synced_block_number = match head_soft_confirmation {
                     Ok(Some((height, _))) => height.0,
@@ -627,11 +851,12 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
                 };
 
                 // get l1 synced block number
-                let l1_synced_block_number = match ethereum.ledger_db.get_last_scanned_l1_height() {
-                    Ok(Some(slot_number)) => slot_number.0,
-                    Ok(None) => 0u64,
-                    Err(e) => return Err(to_jsonrpsee_error_object("LEDGER_DB_ERROR", e)),
-                };
+                let l1_synced_block_number =
+                    match SharedLedgerOps::get_last_scanned_l1_height(&ethereum.ledger_db) {
+                        Ok(Some(slot_number)) => slot_number.0,
+                        Ok(None) => 0u64,
+                        Err(e) => return Err(to_jsonrpsee_error_object("LEDGER_DB_ERROR", e)),
+                    };
 
                 let l1_status = if l1_synced_block_number < l1_head_block_number {
                     LayerStatus::Syncing(SyncValues {
@@ -742,6 +967,13 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
     Ok(())
 }
 
+/// L2 height covered by the last verified batch proof, `None` if no proof is verified yet.
+fn last_verified_l2_height(ledger_db: &LedgerDB) -> anyhow::Result<Option<u64>> {
+    Ok(ledger_db
+        .get_last_verified_batch_proof()?
+        .map(|last_proof| last_proof.proof.proof_output.last_l2_height))
+}
+
 // fn get_call_request_and_params(
 //     from: Address,
 //     chain_id: u64,
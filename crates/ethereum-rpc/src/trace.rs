@@ -139,8 +139,12 @@ pub fn debug_trace_by_block_number<C: sov_modules_api::Context, Da: DaService>(
     working_set: &mut WorkingSet<C::Storage>,
     opts: Option<GethDebugTracingOptions>,
 ) -> Result<Vec<GethTrace>, ErrorObjectOwned> {
-    // If opts is None or if opts.tracer is None, then do not check cache or insert cache, just perform the operation
-    if opts.as_ref().map_or(true, |o| o.tracer.is_none()) {
+    // If opts is None or if the tracer can not be derived from the cached call traces,
+    // then do not check cache or insert cache, just perform the operation
+    if opts
+        .as_ref()
+        .map_or(true, |o| !is_derived_from_cached_traces(&o.tracer))
+    {
         let traces =
             evm.trace_block_transactions_by_number(block_number, opts, trace_idx, working_set)?;
         return match trace_idx {
@@ -187,6 +191,19 @@ pub fn debug_trace_by_block_number<C: sov_modules_api::Context, Da: DaService>(
     Ok(traces)
 }
 
+/// The cache holds call traces with logs, which the call, 4byte and noop tracers are built from.
+/// The struct logger, prestate and flat call tracers need to re-execute the transactions.
+fn is_derived_from_cached_traces(tracer: &Option<GethDebugTracerType>) -> bool {
+    matches!(
+        tracer,
+        Some(GethDebugTracerType::BuiltInTracer(
+            GethDebugBuiltInTracerType::CallTracer
+                | GethDebugBuiltInTracerType::FourByteTracer
+                | GethDebugBuiltInTracerType::NoopTracer
+        ))
+    )
+}
+
 fn apply_call_config(call_frame: CallFrame, call_config: CallConfig) -> CallFrame {
     // let only_top_call = call_config.only_top_call.unwrap_or();
     let mut new_call_frame = call_frame.clone();
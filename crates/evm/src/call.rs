@@ -152,6 +152,8 @@ impl<C: sov_modules_api::Context> Evm<C> {
             log_index_start = tx.receipt.log_index_start + tx.receipt.receipt.logs.len() as u64;
         }
 
+        self.latest_call_pending_start = self.pending_transactions.len();
+
         let evm_db: EvmDb<'_, C> = self.get_db(working_set, cfg_env.handler_cfg.spec_id);
 
         let results = executor::execute_multiple_tx(
@@ -163,6 +165,8 @@ impl<C: sov_modules_api::Context> Evm<C> {
             cumulative_gas_used,
         )?;
 
+        let mut l1_diff_size = 0;
+
         // Iterate each evm_txs_recovered and results pair
         // Create a PendingTransaction for each pair
         // Push each PendingTransaction to pending_transactions
@@ -192,6 +196,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
             };
 
             log_index_start += logs_len;
+            l1_diff_size += tx_info.l1_diff_size;
 
             let pending_transaction = PendingTransaction {
                 transaction: TransactionSignedAndRecovered {
@@ -204,7 +209,14 @@ impl<C: sov_modules_api::Context> Evm<C> {
 
             self.pending_transactions.push(pending_transaction);
         }
-        Ok(CallResponse::default())
+        Ok(CallResponse { l1_diff_size })
+    }
+
+    /// Drops the transactions of the latest call message from the pending transactions,
+    /// for when the working set they were executed on gets reverted.
+    pub fn revert_latest_call(&mut self) {
+        self.pending_transactions
+            .truncate(self.latest_call_pending_start);
     }
 }
 
@@ -1,8 +1,10 @@
 use reth_primitives::{
-    Bytes as RethBytes, TransactionSigned, TransactionSignedEcRecovered, TransactionSignedNoHash,
-    KECCAK_EMPTY,
+    Bytes as RethBytes, Transaction, TransactionSigned, TransactionSignedEcRecovered,
+    TransactionSignedNoHash, KECCAK_EMPTY,
+};
+use revm::primitives::{
+    AccountInfo as ReVmAccountInfo, AuthorizationList, SpecId, TransactTo, TxEnv, U256,
 };
-use revm::primitives::{AccountInfo as ReVmAccountInfo, SpecId, TransactTo, TxEnv, U256};
 
 use super::primitive_types::{RlpEvmTransaction, TransactionSignedAndRecovered};
 use super::AccountInfo;
@@ -78,6 +80,14 @@ pub(crate) fn create_tx_env(tx: &TransactionSignedEcRecovered, spec_id: SpecId)
         tx_env.max_fee_per_blob_gas = tx.max_fee_per_blob_gas().map(U256::from);
     }
 
+    if spec_id >= SpecId::PRAGUE {
+        // EIP-7702 related fields
+        if let Transaction::Eip7702(tx) = &tx.transaction {
+            tx_env.authorization_list =
+                Some(AuthorizationList::Signed(tx.authorization_list.clone()));
+        }
+    }
+
     tx_env
 }
 
@@ -1,6 +1,7 @@
-use reth_primitives::TransactionSignedEcRecovered;
+use reth_primitives::{TransactionSignedEcRecovered, TxType};
 use revm::primitives::{
     BlockEnv, CfgEnvWithHandlerCfg, EVMError, Env, EvmState, ExecutionResult, ResultAndState,
+    SpecId,
 };
 use revm::{self, Context, Database, DatabaseCommit, EvmContext};
 use sov_modules_api::{native_error, native_trace, SoftConfirmationModuleCallError};
@@ -107,6 +108,14 @@ pub(crate) fn execute_multiple_tx<
             ));
         }
 
+        // eip7702 txs are only supported after prague
+        if tx.tx_type() == TxType::Eip7702 && !evm.evm.spec_id().is_enabled_in(SpecId::PRAGUE) {
+            native_error!("EIP-7702 transaction is not supported before prague");
+            return Err(SoftConfirmationModuleCallError::EvmTxTypeNotSupported(
+                "EIP-7702".to_string(),
+            ));
+        }
+
         let result_and_state = evm.transact(tx).map_err(|e| {
             native_error!("Invalid tx {}. Error: {}", tx.hash(), e);
             match e {
@@ -52,6 +52,11 @@ const CODE_KEY_SIZE: usize = 39;
 /// The full calculation can be found here: https://github.com/chainwayxyz/citrea/blob/erce/l1-fee-overhead-calculations/l1_fee_overhead.md
 pub const L1_FEE_OVERHEAD: usize = 3;
 
+/// L1 fee of a transaction with the given diff size, paid by every caller but the system signer.
+pub(crate) fn calculate_l1_fee(l1_fee_rate: u128, diff_size: u64) -> U256 {
+    U256::from(l1_fee_rate) * (U256::from(diff_size) + U256::from(L1_FEE_OVERHEAD))
+}
+
 /// The brotli average compression ratio (compressed size / uncompressed size) was calculated as 0.33 by measuring the size of state diffs of batches before and after brotli compression.
 /// calculated diff size * BROTLI_COMPRESSION_PERCENTAGE/100 gives the estimated size of the state diff that is written to the da.
 pub const BROTLI_COMPRESSION_PERCENTAGE: usize = 33;
@@ -417,8 +422,7 @@ impl<SPEC: Spec, EXT: CitreaExternalExt, DB: Database> CitreaHandler<SPEC, EXT,
         let diff_size = (uncompressed_size * compression_percentage / 100) as u64;
 
         let l1_fee_rate = context.external.l1_fee_rate();
-        let l1_fee =
-            U256::from(l1_fee_rate) * (U256::from(diff_size) + U256::from(L1_FEE_OVERHEAD));
+        let l1_fee = calculate_l1_fee(l1_fee_rate, diff_size);
         context.external.set_tx_info(TxInfo {
             l1_diff_size: diff_size,
             l1_fee,
@@ -1,4 +1,5 @@
 use alloy_eips::eip1559::BaseFeeParams;
+use reth_primitives::hex_literal::hex;
 use reth_primitives::{address, Address, B256, U256};
 use revm::primitives::bitvec::view::BitViewSized;
 use revm::primitives::specification::SpecId;
@@ -43,6 +44,13 @@ pub const L1_FEE_VAULT: Address = address!("310000000000000000000000000000000000
 /// Priority fee vault address
 pub const PRIORITY_FEE_VAULT: Address = address!("3100000000000000000000000000000000000005");
 
+/// EIP-2935 history storage contract address, deployed when prague is activated
+pub const HISTORY_STORAGE_ADDRESS: Address = address!("0000f90827f1c53a10cb7a02335b175320002935");
+/// Number of block hashes served by the EIP-2935 history storage contract
+pub const HISTORY_SERVE_WINDOW: u64 = 8191;
+/// Runtime code of the EIP-2935 history storage contract
+pub(crate) const HISTORY_STORAGE_CODE: &[u8] = &hex!("3373fffffffffffffffffffffffffffffffffffffffe14604657602036036042575f35600143038111604257611fff81430311604257611fff9006545f5260205ff35b5f5ffd5b5f35611fff60014303065500");
+
 /// Prefix for Storage module for evm::Account::storage
 pub const DBACCOUNT_STORAGE_PREFIX: [u8; 6] = *b"Evm/s/";
 /// Prefix for Storage module for evm::Account::keys
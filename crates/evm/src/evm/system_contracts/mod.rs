@@ -1,4 +1,4 @@
-use alloy_primitives::{address, Address, Bytes, U256};
+use alloy_primitives::{address, keccak256, Address, Bytes, U256};
 use alloy_sol_types::{sol, SolCall};
 
 // BitcoinLightClient wrapper.
@@ -131,6 +131,16 @@ impl Bridge {
         func_selector.extend(params);
         func_selector.into()
     }
+
+    /// Return the storage slots of `withdrawalUTXOs[index]`, holding its `txId` and `outputId`.
+    pub fn withdrawal_utxo_slots(index: u64) -> [U256; 2] {
+        // `withdrawalUTXOs` is declared at slot 38, after `isOperatorMalicious` takes 32 slots.
+        // Ownable storage is namespaced, so it does not shift the layout.
+        let array_start = U256::from_be_bytes(keccak256(U256::from(38).to_be_bytes::<32>()).0);
+        // every UTXO takes two slots
+        let tx_id_slot = array_start.wrapping_add(U256::from(index) * U256::from(2));
+        [tx_id_slot, tx_id_slot.wrapping_add(U256::from(1))]
+    }
 }
 
 sol! {
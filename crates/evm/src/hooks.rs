@@ -1,16 +1,22 @@
 use alloy_primitives::B256;
 use citrea_primitives::basefee::calculate_next_block_base_fee;
+use citrea_primitives::forks::FORKS;
 use reth_primitives::{Bloom, Bytes, U256};
-use revm::primitives::{BlobExcessGasAndPrice, BlockEnv, SpecId};
+use revm::primitives::{BlobExcessGasAndPrice, BlockEnv, Bytecode, SpecId};
+use sov_modules_api::fork::{fork_from_block_number, Fork};
 use sov_modules_api::hooks::HookSoftConfirmationInfo;
 use sov_modules_api::prelude::*;
-use sov_modules_api::{AccessoryWorkingSet, WorkingSet};
+use sov_modules_api::{AccessoryWorkingSet, SpecId as CitreaSpecId, WorkingSet};
 use sov_state::Storage;
 #[cfg(feature = "native")]
 use tracing::instrument;
 
+use crate::evm::db_init::InitEvmDb;
 use crate::evm::primitive_types::Block;
 use crate::evm::system_events::SystemEvent;
+use crate::evm::{
+    AccountInfo, DbAccount, HISTORY_SERVE_WINDOW, HISTORY_STORAGE_ADDRESS, HISTORY_STORAGE_CODE,
+};
 use crate::{citrea_spec_id_to_evm_spec_id, Evm};
 
 impl<C: sov_modules_api::Context> Evm<C>
@@ -51,6 +57,23 @@ where
             working_set,
         );
 
+        // The fork manager activates a fork after the block at its activation height,
+        // so the fork activated by the parent block starts with this one.
+        let activated_fork = fork_from_block_number(FORKS, parent_block.header.number);
+        if activated_fork.activation_height == parent_block.header.number {
+            self.fork_activated(&activated_fork, working_set);
+        }
+
+        let active_evm_spec = citrea_spec_id_to_evm_spec_id(soft_confirmation_info.current_spec);
+
+        if active_evm_spec >= SpecId::PRAGUE {
+            self.store_historical_block_hash(
+                parent_block.header.number,
+                last_block_hash,
+                working_set,
+            );
+        }
+
         // populate system events
         let mut system_events = vec![];
         if let Some(last_l1_hash) = self.last_l1_hash.get(working_set) {
@@ -91,8 +114,6 @@ where
             cfg.base_fee_params,
         );
 
-        let active_evm_spec = citrea_spec_id_to_evm_spec_id(soft_confirmation_info.current_spec);
-
         let blob_excess_gas_and_price = if active_evm_spec >= SpecId::CANCUN {
             Some(BlobExcessGasAndPrice::new(0))
         } else {
@@ -242,7 +263,7 @@ where
 
         #[cfg(feature = "native")]
         {
-            use crate::PendingTransaction;
+            use crate::{BridgeWithdrawal, PendingTransaction};
 
             let mut accessory_state = working_set.accessory_state();
             self.pending_head.set(&block, &mut accessory_state);
@@ -253,6 +274,20 @@ where
                 receipt,
             } in pending_transactions
             {
+                for log in receipt.receipt.logs.iter() {
+                    if let Some(withdrawal) = BridgeWithdrawal::from_log(
+                        log,
+                        block.header.number,
+                        transaction.signed_transaction.hash,
+                    ) {
+                        self.withdrawals.set(
+                            &withdrawal.index.to(),
+                            &withdrawal,
+                            &mut accessory_state,
+                        );
+                    }
+                }
+
                 self.transactions.push(transaction, &mut accessory_state);
                 self.receipts.push(receipt, &mut accessory_state);
 
@@ -317,4 +352,48 @@ where
             self.pending_head.delete(accessory_working_set);
         }
     }
+
+    /// State migration of `fork`, run in the first block of the fork.
+    pub(crate) fn fork_activated(&self, fork: &Fork, working_set: &mut WorkingSet<C::Storage>) {
+        if fork.spec_id == CitreaSpecId::Fork2 {
+            self.deploy_history_storage_contract(working_set);
+        }
+    }
+
+    /// Deploys the EIP-2935 history storage contract.
+    fn deploy_history_storage_contract(&self, working_set: &mut WorkingSet<C::Storage>) {
+        // keep the balance in case the address was funded before the fork
+        let balance = self
+            .accounts
+            .get(&HISTORY_STORAGE_ADDRESS, working_set)
+            .map(|account| account.balance)
+            .unwrap_or_default();
+
+        let code = Bytecode::new_raw(Bytes::from_static(HISTORY_STORAGE_CODE));
+        let code_hash = code.hash_slow();
+        let mut evm_db = self.get_db(working_set, SpecId::PRAGUE);
+        evm_db.insert_account_info(
+            HISTORY_STORAGE_ADDRESS,
+            AccountInfo {
+                balance,
+                nonce: 1,
+                code_hash: Some(code_hash),
+            },
+        );
+        evm_db.insert_code(code_hash, code);
+    }
+
+    /// Stores the hash of block `number` in the EIP-2935 history storage contract.
+    fn store_historical_block_hash(
+        &self,
+        number: u64,
+        hash: B256,
+        working_set: &mut WorkingSet<C::Storage>,
+    ) {
+        DbAccount::new(HISTORY_STORAGE_ADDRESS).storage.set(
+            &U256::from(number % HISTORY_SERVE_WINDOW),
+            &U256::from_be_bytes(hash.0),
+            working_set,
+        );
+    }
 }
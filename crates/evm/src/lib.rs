@@ -4,12 +4,16 @@ mod call;
 mod evm;
 mod genesis;
 mod hooks;
+mod proof;
 #[cfg(feature = "native")]
 mod provider_functions;
+#[cfg(feature = "native")]
+mod pruning;
 
 pub use call::*;
 pub use evm::*;
 pub use genesis::*;
+pub use proof::*;
 pub use system_events::SYSTEM_SIGNER;
 
 #[cfg(feature = "native")]
@@ -28,6 +32,10 @@ mod signer;
 pub use signer::DevSigner;
 #[cfg(feature = "native")]
 pub mod smart_contracts;
+#[cfg(feature = "native")]
+mod withdrawals;
+#[cfg(feature = "native")]
+pub use withdrawals::*;
 
 #[cfg(all(test, feature = "native"))]
 mod tests;
@@ -106,6 +114,10 @@ pub struct Evm<C: sov_modules_api::Context> {
     #[memory]
     pub(crate) pending_transactions: Vec<PendingTransaction>,
 
+    /// Number of pending transactions before the latest call message.
+    #[memory]
+    pub(crate) latest_call_pending_start: usize,
+
     /// Head of the chain. The new head is set in `end_slot_hook` but without the inclusion of the `state_root` field.
     /// The `state_root` is added in `begin_slot_hook` of the next block because its calculation occurs after the `end_slot_hook`.
     #[state]
@@ -157,6 +169,11 @@ pub struct Evm<C: sov_modules_api::Context> {
     #[cfg(feature = "native")]
     #[state]
     pub(crate) receipts: sov_modules_api::AccessoryStateVec<Receipt, BcsCodec>,
+
+    /// Used only by the RPC: withdrawal index => withdrawal from the `Bridge` contract.
+    #[cfg(feature = "native")]
+    #[state]
+    pub(crate) withdrawals: sov_modules_api::AccessoryStateMap<u64, BridgeWithdrawal, BcsCodec>,
 }
 
 impl<C: sov_modules_api::Context> sov_modules_api::Module for Evm<C> {
@@ -200,7 +217,8 @@ impl<C: sov_modules_api::Context> Evm<C> {
 const fn citrea_spec_id_to_evm_spec_id(spec_id: CitreaSpecId) -> EvmSpecId {
     match spec_id {
         CitreaSpecId::Genesis => EvmSpecId::SHANGHAI,
-        // Any other citrea spec id mapped to cancun
-        _ => EvmSpecId::CANCUN,
+        CitreaSpecId::Fork1 => EvmSpecId::CANCUN,
+        // Any other citrea spec id mapped to prague
+        _ => EvmSpecId::PRAGUE,
     }
 }
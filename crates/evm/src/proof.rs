@@ -0,0 +1,237 @@
+use borsh::BorshDeserialize;
+use jmt::proof::SparseMerkleProof;
+#[cfg(feature = "native")]
+use jsonrpsee::core::RpcResult;
+#[cfg(feature = "native")]
+use reth_primitives::BlockNumberOrTag;
+use reth_primitives::{Address, Bytes, B256, U256, U64};
+#[cfg(feature = "native")]
+use reth_rpc_eth_types::error::EthApiError;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "native")]
+use sov_modules_api::WorkingSet;
+use sov_state::codec::BcsCodec;
+#[cfg(feature = "native")]
+use sov_state::storage::{NativeStorage, StateValueCodec};
+use sov_state::storage::{StorageKey, StorageProof, StorageValue};
+use sov_state::{DefaultHasher, DefaultWitness, Storage, ZkStorage};
+
+#[cfg(feature = "native")]
+use crate::evm::error::result::internal_rpc_err;
+use crate::evm::{AccountInfo, DbAccount};
+use crate::Evm;
+
+/// Account and storage values of an EVM account at the end of an L2 block,
+/// together with the JMT proofs against the state root of that block.
+///
+/// Returned by `citrea_getProof`. Unlike `eth_getProof`, the proofs are sparse merkle
+/// proofs of the Jellyfish Merkle Tree the rollup state is committed to.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvmAccountProof {
+    /// Address of the account.
+    pub address: Address,
+    /// Balance of the account.
+    pub balance: U256,
+    /// Nonce of the account.
+    pub nonce: U64,
+    /// Code hash of the account, `None` for accounts without code.
+    pub code_hash: Option<B256>,
+    /// Number of the L2 block at the end of which the values are proven.
+    pub block_number: U64,
+    /// State root of the L2 block.
+    pub state_root: B256,
+    /// Borsh encoded JMT proof of the account.
+    pub account_proof: Bytes,
+    /// Proofs of the requested storage slots.
+    pub storage_proof: Vec<EvmStorageProof>,
+}
+
+/// Value of a single storage slot and its JMT proof.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvmStorageProof {
+    /// Storage slot.
+    pub key: U256,
+    /// Value of the storage slot.
+    pub value: U256,
+    /// Borsh encoded JMT proof of the storage slot.
+    pub proof: Bytes,
+}
+
+/// Errors returned when verifying an [`EvmAccountProof`].
+#[derive(Debug, thiserror::Error)]
+pub enum ProofVerificationError {
+    /// The account proof does not match the state root.
+    #[error("Invalid account proof: {0}")]
+    InvalidAccountProof(String),
+    /// The proof of a storage slot does not match the state root.
+    #[error("Invalid storage proof for slot {slot}: {reason}")]
+    InvalidStorageProof {
+        /// Storage slot whose proof failed.
+        slot: U256,
+        /// Why verification failed.
+        reason: String,
+    },
+}
+
+/// Verifies an [`EvmAccountProof`] returned by `citrea_getProof` against `state_root`.
+///
+/// Does not need access to the state, only the state root has to be trusted,
+/// e.g. taken from a soft confirmation or from the output of a batch proof.
+pub fn verify_account_proof<C: sov_modules_api::Context>(
+    state_root: [u8; 32],
+    proof: &EvmAccountProof,
+) -> Result<(), ProofVerificationError> {
+    let account = AccountInfo {
+        balance: proof.balance,
+        nonce: proof.nonce.to(),
+        code_hash: proof.code_hash,
+    };
+    // only the prefix of the accounts map is needed, which doesn't depend on any state
+    let accounts = Evm::<C>::default().accounts;
+    verify_value(
+        state_root,
+        StorageKey::new::<Address, _, _>(accounts.prefix(), &proof.address, &BcsCodec {}),
+        StorageValue::new(&account, &BcsCodec {}),
+        account == AccountInfo::default(),
+        &proof.account_proof,
+    )
+    .map_err(ProofVerificationError::InvalidAccountProof)?;
+
+    let db_account = DbAccount::new(proof.address);
+    for storage_proof in &proof.storage_proof {
+        verify_value(
+            state_root,
+            StorageKey::new::<U256, _, _>(
+                db_account.storage.prefix(),
+                &storage_proof.key,
+                &BcsCodec {},
+            ),
+            StorageValue::new(&storage_proof.value, &BcsCodec {}),
+            storage_proof.value.is_zero(),
+            &storage_proof.proof,
+        )
+        .map_err(|reason| ProofVerificationError::InvalidStorageProof {
+            slot: storage_proof.key,
+            reason,
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Opens `proof` for `key` against `state_root` and checks it proves `value`.
+/// Default values are never written for untouched accounts and slots, so for those
+/// a proof of absence is accepted as well.
+fn verify_value(
+    state_root: [u8; 32],
+    key: StorageKey,
+    value: StorageValue,
+    is_default: bool,
+    proof: &[u8],
+) -> Result<(), String> {
+    let proof = SparseMerkleProof::<DefaultHasher>::try_from_slice(proof)
+        .map_err(|e| format!("Failed to decode proof: {}", e))?;
+    let open = |value: Option<StorageValue>| {
+        <ZkStorage<DefaultWitness, DefaultHasher> as Storage>::open_proof(
+            jmt::RootHash(state_root),
+            StorageProof {
+                key: key.clone(),
+                value,
+                proof: proof.clone(),
+            },
+        )
+    };
+
+    match open(Some(value)) {
+        Ok(_) => Ok(()),
+        Err(_) if is_default => open(None).map(|_| ()).map_err(|e| e.to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+#[cfg(feature = "native")]
+impl<C: sov_modules_api::Context> Evm<C> {
+    /// Handler for `citrea_getProof`
+    ///
+    /// Returns the account and the requested storage slots at the end of the given block
+    /// with their JMT proofs, which can be checked with [`verify_account_proof`].
+    pub fn get_proof(
+        &self,
+        address: Address,
+        storage_keys: Vec<U256>,
+        block_number: Option<BlockNumberOrTag>,
+        working_set: &mut WorkingSet<C::Storage>,
+    ) -> RpcResult<EvmAccountProof>
+    where
+        C::Storage: NativeStorage,
+    {
+        let block_number = self.block_number_for_id(
+            &block_number.unwrap_or(BlockNumberOrTag::Latest),
+            working_set,
+        )?;
+        // proofs are only served for blocks whose history is kept
+        self.get_unpruned_sealed_block(block_number, working_set)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        // genesis is committed at db version 1
+        // so every block is offset by 1
+        let version = block_number + 1;
+        // the root the proofs open against
+        let state_root: [u8; 32] = working_set
+            .get_root_hash(version)
+            .map_err(|e| internal_rpc_err(e.to_string()))?
+            .into();
+
+        let account_proof = working_set
+            .get_with_proof_at_version(
+                StorageKey::new::<Address, _, _>(self.accounts.prefix(), &address, &BcsCodec {}),
+                version,
+            )
+            .map_err(|e| internal_rpc_err(e.to_string()))?;
+        let account: AccountInfo = account_proof
+            .value
+            .as_ref()
+            .map(|value| BcsCodec {}.decode_value_unwrap(value.value()))
+            .unwrap_or_default();
+
+        let db_account = DbAccount::new(address);
+        let mut storage_proof = Vec::with_capacity(storage_keys.len());
+        for key in storage_keys {
+            let proof = working_set
+                .get_with_proof_at_version(
+                    StorageKey::new::<U256, _, _>(db_account.storage.prefix(), &key, &BcsCodec {}),
+                    version,
+                )
+                .map_err(|e| internal_rpc_err(e.to_string()))?;
+            let value: U256 = proof
+                .value
+                .as_ref()
+                .map(|value| BcsCodec {}.decode_value_unwrap(value.value()))
+                .unwrap_or_default();
+            storage_proof.push(EvmStorageProof {
+                key,
+                value,
+                proof: encode_proof(&proof.proof)?,
+            });
+        }
+
+        Ok(EvmAccountProof {
+            address,
+            balance: account.balance,
+            nonce: U64::from(account.nonce),
+            code_hash: account.code_hash,
+            block_number: U64::from(block_number),
+            state_root: B256::from(state_root),
+            account_proof: encode_proof(&account_proof.proof)?,
+            storage_proof,
+        })
+    }
+}
+
+#[cfg(feature = "native")]
+fn encode_proof<P: borsh::BorshSerialize>(proof: &P) -> RpcResult<Bytes> {
+    borsh::to_vec(proof)
+        .map(Bytes::from)
+        .map_err(|e| internal_rpc_err(e.to_string()))
+}
@@ -0,0 +1,44 @@
+use std::ops::RangeInclusive;
+
+use sov_modules_api::{StateVecAccessor, WorkingSet};
+
+use crate::Evm;
+
+impl<C: sov_modules_api::Context> Evm<C> {
+    /// Removes the RPC history of the given blocks from the accessory state.
+    /// That is the blocks, their transactions and receipts, which also hold the logs.
+    ///
+    /// Lengths of the accessory vecs are left untouched so that removed blocks
+    /// can be told apart from blocks which do not exist yet. For the same reason
+    /// block and transaction hashes are kept, so lookups by hash can report
+    /// pruned history instead of an unknown hash.
+    pub fn remove_history(
+        &self,
+        block_numbers: RangeInclusive<u64>,
+        working_set: &mut WorkingSet<C::Storage>,
+    ) {
+        let mut accessory_state = working_set.accessory_state();
+
+        for block_number in block_numbers {
+            // Genesis block is never removed
+            if block_number == 0 {
+                continue;
+            }
+
+            let Some(block) = self.blocks.get(block_number as usize, &mut accessory_state) else {
+                // Already removed
+                continue;
+            };
+
+            for tx_number in block.transactions.clone() {
+                self.transactions
+                    .delete(tx_number as usize, &mut accessory_state);
+                self.receipts
+                    .delete(tx_number as usize, &mut accessory_state);
+            }
+
+            self.blocks
+                .delete(block_number as usize, &mut accessory_state);
+        }
+    }
+}
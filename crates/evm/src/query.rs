@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use std::ops::{Range, RangeInclusive};
 
 use alloy_consensus::Eip658Value;
@@ -10,20 +10,26 @@ use citrea_primitives::forks::FORKS;
 use jsonrpsee::core::RpcResult;
 use reth_primitives::TxKind::{Call, Create};
 use reth_primitives::{
-    Block, BlockId, BlockNumberOrTag, SealedHeader, TransactionSignedEcRecovered, U256, U64,
+    Block, BlockId, BlockNumberOrTag, Bytes, SealedHeader, TransactionSigned,
+    TransactionSignedEcRecovered, TxHash, U256, U64,
 };
 use reth_provider::ProviderError;
 use reth_rpc_eth_types::error::{EthApiError, EthResult, RevertError, RpcInvalidTransactionError};
 use reth_rpc_types::state::StateOverride;
+use reth_rpc_types::trace::filter::TraceFilter;
 use reth_rpc_types::trace::geth::{GethDebugTracingOptions, GethTrace};
+use reth_rpc_types::trace::parity::{
+    LocalizedTransactionTrace, TraceResultsWithTransactionHash, TraceType,
+};
 use reth_rpc_types::{
     AnyReceiptEnvelope, AnyTransactionReceipt, BlockOverrides, Log, OtherFields, ReceiptWithBloom,
-    TransactionReceipt,
+    TransactionInfo, TransactionReceipt,
 };
 use reth_rpc_types_compat::block::from_primitive_with_hash;
+use reth_rpc_types_compat::transaction::transaction_to_call_request;
 use revm::primitives::{
-    BlobExcessGasAndPrice, BlockEnv, CfgEnvWithHandlerCfg, EVMError, ExecutionResult, HaltReason,
-    InvalidTransaction, SpecId, TransactTo,
+    BlobExcessGasAndPrice, BlockEnv, CfgEnvWithHandlerCfg, EVMError, EvmState, ExecutionResult,
+    HaltReason, InvalidTransaction, ResultAndState, SpecId, TransactTo, TxEnv,
 };
 use revm::{Database, DatabaseCommit};
 use revm_inspectors::access_list::AccessListInspector;
@@ -41,10 +47,11 @@ use crate::evm::call::{create_txn_env, prepare_call_env};
 use crate::evm::db::EvmDb;
 use crate::evm::primitive_types::{Receipt, SealedBlock, TransactionSignedAndRecovered};
 use crate::evm::DbAccount;
-use crate::handler::{diff_size_send_eth_eoa, TxInfo};
+use crate::handler::{calculate_l1_fee, diff_size_send_eth_eoa, TxInfo, L1_FEE_OVERHEAD};
 use crate::rpc_helpers::*;
 use crate::{
-    citrea_spec_id_to_evm_spec_id, BloomFilter, Evm, EvmChainConfig, FilterBlockOption, FilterError,
+    citrea_spec_id_to_evm_spec_id, BloomFilter, Evm, EvmChainConfig, FilterBlockOption,
+    FilterError, SYSTEM_SIGNER,
 };
 /// Gas per transaction not creating a contract.
 pub const MIN_TRANSACTION_GAS: u64 = 21_000u64;
@@ -55,6 +62,9 @@ pub const MIN_TRANSACTION_GAS: u64 = 21_000u64;
 /// <https://github.com/ethereum/go-ethereum/blob/a5a4fa7032bb248f5a7c40f4e8df2b131c4186a4/internal/ethapi/api.go#L56>
 const ESTIMATE_GAS_ERROR_RATIO: f64 = 0.015;
 
+/// Maximum number of blocks `trace_filter` traces in a single request.
+pub const MAX_TRACE_FILTER_BLOCK_RANGE: u64 = 100;
+
 /// The result of gas/diffsize estimation.
 /// This struct holds estimated gas and l1_fee_overhead.
 /// This is very useful for users to test their balance after calling to `eth_estimateGas`
@@ -90,6 +100,14 @@ pub struct EstimatedDiffSize {
     pub l1_diff_size: U64,
 }
 
+/// Transaction of `citrea_estimateL1Fee`, either RLP encoded and signed, or a call request.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum L1FeeEstimateRequest {
+    Signed(Bytes),
+    Unsigned(Box<reth_rpc_types::TransactionRequest>),
+}
+
 #[rpc_gen(client, server)]
 impl<C: sov_modules_api::Context> Evm<C> {
     /// Handler for `net_version`
@@ -164,9 +182,9 @@ impl<C: sov_modules_api::Context> Evm<C> {
             .map(|id| {
                 self.transactions
                     .get(id as usize, &mut working_set.accessory_state())
-                    .expect("Transaction must be set")
+                    .ok_or(EthApiError::PrunedHistoryUnavailable)
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
         let block = Block {
             header: sealed_block.header.header().clone(),
@@ -245,10 +263,10 @@ impl<C: sov_modules_api::Context> Evm<C> {
                     None => return Ok(None), // if hash is unknown, return None
                 };
 
-                // if hash is known, but we don't have the block, fail
+                // if hash is known, but we don't have the block, its history was pruned
                 self.blocks
                     .get(block_number as usize, &mut working_set.accessory_state())
-                    .expect("Block must be set")
+                    .ok_or(EthApiError::PrunedHistoryUnavailable)?
             }
             BlockId::Number(block_number) => {
                 match self.get_sealed_block_by_number(Some(block_number), working_set)? {
@@ -258,25 +276,25 @@ impl<C: sov_modules_api::Context> Evm<C> {
             }
         };
 
-        let receipts = &block
+        let receipts = block
             .transactions
             .clone()
             .map(|id| {
                 let tx = self
                     .transactions
                     .get(id as usize, &mut working_set.accessory_state())
-                    .expect("Transaction must be set");
+                    .ok_or(EthApiError::PrunedHistoryUnavailable)?;
 
                 let receipt = self
                     .receipts
                     .get(id as usize, &mut working_set.accessory_state())
-                    .expect("Receipt for known transaction must be set");
+                    .ok_or(EthApiError::PrunedHistoryUnavailable)?;
 
-                build_rpc_receipt(&block, tx, id, receipt)
+                Ok(build_rpc_receipt(&block, tx, id, receipt))
             })
-            .collect::<Vec<_>>();
+            .collect::<Result<Vec<_>, EthApiError>>()?;
 
-        Ok(Some(receipts.clone()))
+        Ok(Some(receipts))
     }
 
     /// Handler for: `eth_getBalance`
@@ -405,7 +423,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
         let block = self
             .blocks
             .get(block_number as usize, &mut accessory_state)
-            .expect("Block must be set");
+            .ok_or(EthApiError::PrunedHistoryUnavailable)?;
 
         match check_tx_range(&block.transactions, index) {
             Some(_) => (),
@@ -417,7 +435,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
         let tx = self
             .transactions
             .get(tx_number as usize, &mut accessory_state)
-            .expect("Transaction must be set");
+            .ok_or(EthApiError::PrunedHistoryUnavailable)?;
 
         let block = self
             .blocks
@@ -450,9 +468,8 @@ impl<C: sov_modules_api::Context> Evm<C> {
         };
 
         let block = self
-            .blocks
-            .get(block_number as usize, &mut working_set.accessory_state())
-            .expect("Block must be set");
+            .get_unpruned_sealed_block(block_number, working_set)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
 
         match check_tx_range(&block.transactions, index) {
             Some(_) => (),
@@ -464,7 +481,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
         let tx = self
             .transactions
             .get(tx_number as usize, &mut working_set.accessory_state())
-            .expect("Transaction must be set");
+            .ok_or(EthApiError::PrunedHistoryUnavailable)?;
 
         let block = self
             .blocks
@@ -491,27 +508,27 @@ impl<C: sov_modules_api::Context> Evm<C> {
     ) -> RpcResult<Option<AnyTransactionReceipt>> {
         let mut accessory_state = working_set.accessory_state();
 
-        let tx_number = self.transaction_hashes.get(&hash, &mut accessory_state);
-
-        let receipt = tx_number.map(|number| {
-            let tx = self
-                .transactions
-                .get(number as usize, &mut accessory_state)
-                .expect("Transaction with known hash must be set");
-            let block = self
-                .blocks
-                .get(tx.block_number as usize, &mut accessory_state)
-                .expect("Block number for known transaction must be set");
+        let Some(number) = self.transaction_hashes.get(&hash, &mut accessory_state) else {
+            return Ok(None);
+        };
 
-            let receipt = self
-                .receipts
-                .get(number as usize, &mut accessory_state)
-                .expect("Receipt for known transaction must be set");
+        // Hashes of pruned transactions are kept, so a known hash without
+        // a transaction means its history was pruned
+        let tx = self
+            .transactions
+            .get(number as usize, &mut accessory_state)
+            .ok_or(EthApiError::PrunedHistoryUnavailable)?;
+        let block = self
+            .blocks
+            .get(tx.block_number as usize, &mut accessory_state)
+            .ok_or(EthApiError::PrunedHistoryUnavailable)?;
 
-            build_rpc_receipt(&block, tx, number, receipt)
-        });
+        let receipt = self
+            .receipts
+            .get(number as usize, &mut accessory_state)
+            .ok_or(EthApiError::PrunedHistoryUnavailable)?;
 
-        Ok(receipt)
+        Ok(Some(build_rpc_receipt(&block, tx, number, receipt)))
     }
 
     /// Handler for: `eth_call`
@@ -743,6 +760,17 @@ impl<C: sov_modules_api::Context> Evm<C> {
         block_number: Option<BlockNumberOrTag>,
         working_set: &mut WorkingSet<C::Storage>,
     ) -> RpcResult<EstimatedTxExpenses> {
+        let (l1_fee_rate, block_env, cfg_env) = self.estimation_env(block_number, working_set)?;
+
+        self.estimate_gas_with_env(request, l1_fee_rate, block_env, cfg_env, working_set)
+    }
+
+    // Returns the L1 fee rate and the envs estimations on top of `block_number` run with.
+    fn estimation_env(
+        &self,
+        block_number: Option<BlockNumberOrTag>,
+        working_set: &mut WorkingSet<C::Storage>,
+    ) -> RpcResult<(u128, BlockEnv, CfgEnvWithHandlerCfg)> {
         let (l1_fee_rate, block_env, cfg_env) = {
             let (l1_fee_rate, block_env) = match block_number {
                 Some(BlockNumberOrTag::Pending) => {
@@ -777,7 +805,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
             (l1_fee_rate, block_env, cfg_env)
         };
 
-        self.estimate_gas_with_env(request, l1_fee_rate, block_env, cfg_env, working_set)
+        Ok((l1_fee_rate, block_env, cfg_env))
     }
 
     /// Handler for: `eth_estimateGas`
@@ -819,6 +847,286 @@ impl<C: sov_modules_api::Context> Evm<C> {
         })
     }
 
+    /// Handler for: `citrea_estimateL1Fee`
+    ///
+    /// Returns the L1 fee a transaction would pay on top of its gas, estimated like `eth_estimateGas`.
+    #[rpc_method(name = "citrea_estimateL1Fee", blocking)]
+    pub fn citrea_estimate_l1_fee(
+        &self,
+        request: L1FeeEstimateRequest,
+        block_number: Option<BlockNumberOrTag>,
+        working_set: &mut WorkingSet<C::Storage>,
+    ) -> RpcResult<L1FeeEstimate> {
+        let request = match request {
+            L1FeeEstimateRequest::Signed(rlp) => {
+                let tx = TransactionSigned::decode_enveloped(&mut rlp.as_ref())
+                    .map_err(|_| EthApiError::FailedToDecodeSignedTransaction)?
+                    .into_ecrecovered()
+                    .ok_or(EthApiError::InvalidTransactionSignature)?;
+                transaction_to_call_request(tx)
+            }
+            L1FeeEstimateRequest::Unsigned(request) => *request,
+        };
+
+        let (l1_fee_rate, block_env, cfg_env) = self.estimation_env(block_number, working_set)?;
+        let estimated =
+            self.estimate_gas_with_env(request, l1_fee_rate, block_env, cfg_env, working_set)?;
+
+        Ok(L1FeeEstimate {
+            l1_diff_size: U64::from(estimated.l1_diff_size),
+            l1_fee_rate: U256::from(l1_fee_rate),
+            l1_fee_overhead: U64::from(L1_FEE_OVERHEAD),
+            l1_fee: estimated.l1_fee,
+        })
+    }
+
+    /// Handler for: `eth_simulateV1`
+    ///
+    /// Simulates a sequence of blocks on top of `block_id`, each holding several calls.
+    /// Every call sees the state changes of the calls before it, also across blocks,
+    /// and reports its L1 diff size like `eth_estimateDiffSize` does.
+    #[rpc_method(name = "eth_simulateV1", blocking)]
+    pub fn eth_simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block_id: Option<BlockId>,
+        working_set: &mut WorkingSet<C::Storage>,
+    ) -> RpcResult<Vec<SimulatedBlock>> {
+        if payload.block_state_calls.len() > MAX_SIMULATE_BLOCKS {
+            return Err(EthApiError::InvalidParams(format!(
+                "Cannot simulate more than {} blocks",
+                MAX_SIMULATE_BLOCKS
+            ))
+            .into());
+        }
+
+        let block_number = match block_id {
+            Some(BlockId::Number(block_num)) => block_num,
+            Some(BlockId::Hash(block_hash)) => {
+                let block_number = self
+                    .get_block_number_by_block_hash(block_hash.block_hash, working_set)
+                    .ok_or_else(|| EthApiError::UnknownBlockOrTxIndex)?;
+                BlockNumberOrTag::Number(block_number)
+            }
+            None => BlockNumberOrTag::Latest,
+        };
+        let base_block = self
+            .get_sealed_block_by_number(Some(block_number), working_set)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        if block_number != BlockNumberOrTag::Latest {
+            set_state_to_end_of_evm_block::<C>(base_block.header.number, working_set);
+        }
+
+        let cfg = self
+            .cfg
+            .get(working_set)
+            .expect("EVM chain config should be set");
+        // Without validation calls are free, like in `eth_call`
+        let l1_fee_rate = if payload.validation {
+            base_block.l1_fee_rate
+        } else {
+            0
+        };
+
+        let mut parent = base_block.header.header().clone();
+        let mut parent_hash = base_block.header.hash();
+        let mut simulated_blocks = Vec::with_capacity(payload.block_state_calls.len());
+        for sim_block in payload.block_state_calls {
+            let number = sim_block
+                .block_overrides
+                .as_ref()
+                .and_then(|overrides| overrides.number)
+                .map_or(parent.number + 1, |number| number.saturating_to());
+            let mut block_env = BlockEnv {
+                number: U256::from(number),
+                coinbase: cfg.coinbase,
+                timestamp: U256::from(parent.timestamp + 1),
+                gas_limit: U256::from(cfg.block_gas_limit),
+                basefee: if payload.validation {
+                    U256::from(calculate_next_block_base_fee(
+                        parent.gas_used as u128,
+                        parent.gas_limit as u128,
+                        parent.base_fee_per_gas.unwrap_or_default(),
+                        cfg.base_fee_params,
+                    ))
+                } else {
+                    U256::ZERO
+                },
+                prevrandao: Some(parent.mix_hash),
+                ..Default::default()
+            };
+
+            let evm_spec_id =
+                citrea_spec_id_to_evm_spec_id(fork_from_block_number(FORKS, number).spec_id);
+            let mut cfg_env = get_cfg_env(cfg.clone(), evm_spec_id);
+            if evm_spec_id >= SpecId::CANCUN {
+                block_env.blob_excess_gas_and_price = Some(BlobExcessGasAndPrice::new(0));
+            }
+
+            let mut evm_db = self.get_db(working_set, evm_spec_id);
+            if let Some(mut block_overrides) = sim_block.block_overrides {
+                apply_block_overrides(&mut block_env, &mut block_overrides, &mut evm_db);
+            }
+            if let Some(state_overrides) = sim_block.state_overrides {
+                apply_state_overrides(state_overrides, &mut evm_db)?;
+            }
+
+            let timestamp: u64 = block_env.timestamp.saturating_to();
+            if number <= parent.number {
+                return Err(EthApiError::InvalidParams(format!(
+                    "Block number {} is not greater than its parent number {}",
+                    number, parent.number
+                ))
+                .into());
+            }
+            if timestamp <= parent.timestamp {
+                return Err(EthApiError::InvalidParams(format!(
+                    "Block timestamp {} is not greater than its parent timestamp {}",
+                    timestamp, parent.timestamp
+                ))
+                .into());
+            }
+
+            let block_gas_limit: u64 = block_env.gas_limit.saturating_to();
+            let mut gas_used = 0u64;
+            let mut calls = Vec::with_capacity(sim_block.calls.len());
+            let mut call_logs = Vec::with_capacity(sim_block.calls.len());
+            for mut request in sim_block.calls {
+                let remaining_gas = block_gas_limit - gas_used;
+                let gas = request.gas.unwrap_or(remaining_gas as u128);
+                if gas > remaining_gas as u128 {
+                    return Err(EthApiError::InvalidParams(format!(
+                        "Call gas {} exceeds the remaining block gas {}",
+                        gas, remaining_gas
+                    ))
+                    .into());
+                }
+                request.gas = Some(gas);
+
+                let balance = evm_db
+                    .basic(request.from.unwrap_or_default())
+                    .map_err(EthApiError::from)?
+                    .unwrap_or_default()
+                    .balance;
+                let tx_env = if payload.validation {
+                    create_txn_env(&block_env, request, Some(balance))?
+                } else {
+                    prepare_call_env(&block_env, &mut cfg_env, request, balance)?
+                };
+
+                let (result, tx_info, transfers) = inspect_with_transfers(
+                    &mut evm_db,
+                    cfg_env.clone(),
+                    block_env.clone(),
+                    tx_env,
+                    l1_fee_rate,
+                )
+                .map_err(EthApiError::from)?;
+                evm_db.commit(result.state);
+
+                let call_gas_used = result.result.gas_used();
+                gas_used += call_gas_used;
+
+                let mut logs = vec![];
+                let (status, return_data, error) = match result.result {
+                    ExecutionResult::Success {
+                        output,
+                        logs: evm_logs,
+                        ..
+                    } => {
+                        if payload.trace_transfers {
+                            logs.extend(
+                                transfers
+                                    .iter()
+                                    .filter(|transfer| !transfer.value.is_zero())
+                                    .map(transfer_log),
+                            );
+                        }
+                        logs.extend(evm_logs);
+                        (1, output.into_data(), None)
+                    }
+                    ExecutionResult::Revert { output, .. } => (
+                        0,
+                        output,
+                        Some(SimulateCallError {
+                            code: SIMULATE_REVERT_CODE,
+                            message: "execution reverted".to_string(),
+                        }),
+                    ),
+                    ExecutionResult::Halt { reason, .. } => (
+                        0,
+                        Default::default(),
+                        Some(SimulateCallError {
+                            code: SIMULATE_HALT_CODE,
+                            message: format!("{:?}", reason),
+                        }),
+                    ),
+                };
+                call_logs.push(logs);
+                calls.push(SimulatedCall {
+                    status: U64::from(status),
+                    return_data,
+                    gas_used: U64::from(call_gas_used),
+                    l1_diff_size: U64::from(tx_info.l1_diff_size),
+                    logs: vec![],
+                    error,
+                });
+            }
+
+            let header = reth_primitives::Header {
+                parent_hash,
+                beneficiary: block_env.coinbase,
+                number,
+                gas_limit: block_gas_limit,
+                gas_used,
+                timestamp,
+                mix_hash: block_env.prevrandao.unwrap_or_default(),
+                base_fee_per_gas: Some(block_env.basefee.saturating_to()),
+                ..Default::default()
+            };
+            let hash = header.hash_slow();
+            // Later blocks can read the hash of this one with BLOCKHASH
+            evm_db.override_block_hash(number, hash);
+
+            let mut log_index = 0;
+            for (transaction_index, (call, logs)) in calls.iter_mut().zip(call_logs).enumerate() {
+                call.logs = logs
+                    .into_iter()
+                    .map(|log| {
+                        let log = Log {
+                            inner: log,
+                            block_hash: Some(hash),
+                            block_number: Some(number),
+                            block_timestamp: Some(timestamp),
+                            transaction_hash: None,
+                            transaction_index: Some(transaction_index as u64),
+                            log_index: Some(log_index),
+                            removed: false,
+                        };
+                        log_index += 1;
+                        log
+                    })
+                    .collect();
+            }
+
+            simulated_blocks.push(SimulatedBlock {
+                number: U64::from(number),
+                hash,
+                parent_hash,
+                timestamp: U64::from(timestamp),
+                gas_limit: U64::from(block_gas_limit),
+                gas_used: U64::from(gas_used),
+                base_fee_per_gas: U64::from(header.base_fee_per_gas.unwrap_or_default()),
+                miner: block_env.coinbase,
+                calls,
+            });
+            parent = header;
+            parent_hash = hash;
+        }
+
+        Ok(simulated_blocks)
+    }
+
     /// Handler for: `eth_getBlockTransactionCountByHash`
     // https://github.com/paradigmxyz/reth/blob/main/crates/rpc/rpc/src/eth/api/call.rs#L172
     #[rpc_method(name = "eth_getBlockTransactionCountByHash")]
@@ -1137,34 +1445,31 @@ impl<C: sov_modules_api::Context> Evm<C> {
     ) -> RpcResult<Option<reth_rpc_types::Transaction>> {
         let mut accessory_state = working_set.accessory_state();
 
-        let tx_number = self.transaction_hashes.get(&hash, &mut accessory_state);
+        let Some(number) = self.transaction_hashes.get(&hash, &mut accessory_state) else {
+            return Ok(None);
+        };
 
-        let transaction = tx_number.map(|number| {
-            let tx = self
-                .transactions
-                .get(number as usize, &mut accessory_state)
-                .unwrap_or_else(|| panic!("Transaction with known hash {} and number {} must be set in all {} transaction",
-                hash,
-                number,
-                self.transactions.len(&mut accessory_state)));
+        // Hashes of pruned transactions are kept, so a known hash without
+        // a transaction means its history was pruned
+        let tx = self
+            .transactions
+            .get(number as usize, &mut accessory_state)
+            .ok_or(EthApiError::PrunedHistoryUnavailable)?;
 
-            let block = self
-                .blocks
-                .get(tx.block_number as usize, &mut accessory_state)
-                .unwrap_or_else(|| panic!("Block with number {} for known transaction {} must be set",
-                    tx.block_number,
-                    tx.signed_transaction.hash));
-
-            reth_rpc_types_compat::transaction::from_recovered_with_block_context(
-                tx.into(),
-                block.header.hash(),
-                block.header.number,
-                block.header.base_fee_per_gas,
-                (number - block.transactions.start) as usize,
-            )
-        });
+        let block = self
+            .blocks
+            .get(tx.block_number as usize, &mut accessory_state)
+            .ok_or(EthApiError::PrunedHistoryUnavailable)?;
+
+        let transaction = reth_rpc_types_compat::transaction::from_recovered_with_block_context(
+            tx.into(),
+            block.header.hash(),
+            block.header.number,
+            block.header.base_fee_per_gas,
+            (number - block.transactions.start) as usize,
+        );
 
-        Ok(transaction)
+        Ok(Some(transaction))
     }
 
     /// Traces the entire block txs and returns the traces
@@ -1175,6 +1480,188 @@ impl<C: sov_modules_api::Context> Evm<C> {
         stop_at: Option<usize>,
         working_set: &mut WorkingSet<C::Storage>,
     ) -> RpcResult<Vec<GethTrace>> {
+        self.replay_block_transactions(block_number, stop_at, working_set, |env, evm_db| {
+            trace_transaction(
+                opts.clone().unwrap_or_default(),
+                env.cfg_env,
+                env.block_env,
+                env.tx_env,
+                env.tx_info,
+                evm_db,
+                env.l1_fee_rate,
+            )
+        })
+    }
+
+    /// Handler for `trace_block`
+    /// Returns the parity style call traces of all transactions in the block
+    pub fn trace_block(
+        &self,
+        block_number: u64,
+        working_set: &mut WorkingSet<C::Storage>,
+    ) -> RpcResult<Vec<LocalizedTransactionTrace>> {
+        let traces = self.trace_block_transactions_parity(
+            block_number,
+            TracingInspectorConfig::default_parity(),
+            None,
+            working_set,
+            |tx_info, inspector, _, _| {
+                Ok(inspector
+                    .into_parity_builder()
+                    .into_localized_transaction_traces(tx_info))
+            },
+        )?;
+        Ok(traces.into_iter().flatten().collect())
+    }
+
+    /// Handler for `trace_transaction`
+    /// Returns the parity style call traces of the transaction, None if the transaction is not found
+    pub fn trace_transaction(
+        &self,
+        tx_hash: reth_primitives::B256,
+        working_set: &mut WorkingSet<C::Storage>,
+    ) -> RpcResult<Option<Vec<LocalizedTransactionTrace>>> {
+        let mut accessory_state = working_set.accessory_state();
+        let Some(tx_number) = self.transaction_hashes.get(&tx_hash, &mut accessory_state) else {
+            return Ok(None);
+        };
+        let tx = self
+            .transactions
+            .get(tx_number as usize, &mut accessory_state)
+            .ok_or(EthApiError::PrunedHistoryUnavailable)?;
+        let block = self
+            .get_unpruned_sealed_block(tx.block_number, working_set)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        let tx_index = (tx_number - block.transactions.start) as usize;
+
+        let mut traces = self.trace_block_transactions_parity(
+            tx.block_number,
+            TracingInspectorConfig::default_parity(),
+            Some(tx_index),
+            working_set,
+            |tx_info, inspector, _, _| {
+                Ok(inspector
+                    .into_parity_builder()
+                    .into_localized_transaction_traces(tx_info))
+            },
+        )?;
+        Ok(traces.pop())
+    }
+
+    /// Handler for `trace_replayBlockTransactions`
+    /// Replays all transactions in the block and returns the requested trace types,
+    /// `stateDiff` is built against the state before each transaction
+    pub fn trace_replay_block_transactions(
+        &self,
+        block_number: u64,
+        trace_types: HashSet<TraceType>,
+        working_set: &mut WorkingSet<C::Storage>,
+    ) -> RpcResult<Vec<TraceResultsWithTransactionHash>> {
+        self.trace_block_transactions_parity(
+            block_number,
+            TracingInspectorConfig::from_parity_config(&trace_types),
+            None,
+            working_set,
+            |tx_info, inspector, res, evm_db| {
+                let full_trace = inspector
+                    .into_parity_builder()
+                    .into_trace_results_with_state(
+                        res,
+                        &trace_types,
+                        DatabaseRefAdapter::new(evm_db),
+                    )?;
+                Ok(TraceResultsWithTransactionHash {
+                    full_trace,
+                    transaction_hash: tx_info.hash.expect("Transaction hash is set"),
+                })
+            },
+        )
+    }
+
+    /// Handler for `trace_filter`
+    /// Returns the parity style call traces in the block range matching the from/to addresses
+    pub fn trace_filter(
+        &self,
+        filter: TraceFilter,
+        working_set: &mut WorkingSet<C::Storage>,
+    ) -> RpcResult<Vec<LocalizedTransactionTrace>> {
+        let latest_block_number =
+            self.block_number_for_id(&BlockNumberOrTag::Latest, working_set)?;
+        let from_block = filter.from_block.unwrap_or(0);
+        let to_block = filter.to_block.unwrap_or(latest_block_number);
+        if from_block > to_block {
+            return Err(EthApiError::InvalidBlockRange.into());
+        }
+        if to_block > latest_block_number {
+            return Err(EthApiError::UnknownBlockNumber.into());
+        }
+        if to_block - from_block >= MAX_TRACE_FILTER_BLOCK_RANGE {
+            return Err(EthApiError::InvalidParams(format!(
+                "Block range too large; currently limited to {} blocks",
+                MAX_TRACE_FILTER_BLOCK_RANGE
+            ))
+            .into());
+        }
+
+        let matcher = filter.matcher();
+        let mut traces = Vec::new();
+        for block_number in from_block..=to_block {
+            // tracing a block moves the working set to the state of the previous block
+            working_set.unset_archival_version();
+            traces.extend(
+                self.trace_block(block_number, working_set)?
+                    .into_iter()
+                    .filter(|trace| matcher.matches(&trace.trace)),
+            );
+        }
+
+        Ok(traces
+            .into_iter()
+            .skip(filter.after.unwrap_or_default() as usize)
+            .take(filter.count.map_or(usize::MAX, |count| count as usize))
+            .collect())
+    }
+
+    /// Re-executes the transactions of the block up to and including `stop_at` with a parity
+    /// [`TracingInspector`] each, and builds their traces with `f` before committing their state changes
+    fn trace_block_transactions_parity<T>(
+        &self,
+        block_number: u64,
+        config: TracingInspectorConfig,
+        stop_at: Option<usize>,
+        working_set: &mut WorkingSet<C::Storage>,
+        mut f: impl FnMut(
+            TransactionInfo,
+            TracingInspector,
+            &ResultAndState,
+            &mut EvmDb<'_, C>,
+        ) -> EthResult<T>,
+    ) -> RpcResult<Vec<T>> {
+        self.replay_block_transactions(block_number, stop_at, working_set, |env, evm_db| {
+            let (inspector, res) = trace_transaction_parity(
+                config,
+                env.cfg_env,
+                env.block_env,
+                env.tx_env,
+                env.tx_hash,
+                evm_db,
+                env.l1_fee_rate,
+            )?;
+            let result = f(env.tx_info, inspector, &res, evm_db)?;
+            Ok((result, res.state))
+        })
+    }
+
+    /// Re-executes the transactions of the block up to and including `stop_at` on top of
+    /// the state of the previous block. Each transaction is executed by `replay`, whose
+    /// state changes are committed before the next transaction.
+    fn replay_block_transactions<T>(
+        &self,
+        block_number: u64,
+        stop_at: Option<usize>,
+        working_set: &mut WorkingSet<C::Storage>,
+        mut replay: impl FnMut(ReplayEnv, &mut EvmDb<'_, C>) -> EthResult<(T, EvmState)>,
+    ) -> RpcResult<Vec<T>> {
         let sealed_block = self
             .get_sealed_block_by_number(Some(BlockNumberOrTag::Number(block_number)), working_set)?
             .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
@@ -1184,14 +1671,13 @@ impl<C: sov_modules_api::Context> Evm<C> {
             return Ok(Vec::new());
         }
         let block_txs: Vec<TransactionSignedEcRecovered> = tx_range
-            .clone()
             .map(|id| {
                 self.transactions
                     .get(id as usize, &mut working_set.accessory_state())
-                    .expect("Transaction must be set")
-                    .into()
+                    .map(Into::into)
+                    .ok_or(EthApiError::PrunedHistoryUnavailable)
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
         // set state to end of the previous block
         set_state_to_end_of_evm_block::<C>(block_number - 1, working_set);
@@ -1214,20 +1700,20 @@ impl<C: sov_modules_api::Context> Evm<C> {
         let mut evm_db = self.get_db(working_set, current_spec);
 
         // TODO: Convert below steps to blocking task like in reth after implementing the semaphores
-        let mut traces = Vec::new();
+        let mut results = Vec::new();
         let mut transactions = block_txs.into_iter().enumerate().peekable();
         let limit = stop_at.unwrap_or(usize::MAX);
         while let Some((index, tx)) = transactions.next() {
-            let (trace, state_changes) = trace_transaction(
-                opts.clone().unwrap_or_default(),
-                cfg_env.clone(),
-                block_env.clone(),
-                create_tx_env(&tx, cfg_env.handler_cfg.spec_id),
-                tx.hash(),
-                &mut evm_db,
+            let env = ReplayEnv {
+                cfg_env: cfg_env.clone(),
+                block_env: block_env.clone(),
+                tx_env: create_tx_env(&tx, current_spec),
+                tx_hash: tx.hash(),
+                tx_info: transaction_info(&sealed_block, tx.hash(), index),
                 l1_fee_rate,
-            )?;
-            traces.push(trace);
+            };
+            let (result, state_changes) = replay(env, &mut evm_db)?;
+            results.push(result);
 
             if limit == index {
                 break;
@@ -1239,7 +1725,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
                 evm_db.commit(state_changes)
             }
         }
-        Ok(traces)
+        Ok(results)
     }
 
     // https://github.com/paradigmxyz/reth/blob/8892d04a88365ba507f28c3314d99a6b54735d3f/crates/rpc/rpc/src/eth/filter.rs#L349
@@ -1262,11 +1748,13 @@ impl<C: sov_modules_api::Context> Evm<C> {
                     }
                 };
 
-                // if we know the hash, but can't find the block, fail
+                // if we know the hash, but can't find the block, its history was pruned
                 let block = self
                     .blocks
                     .get(block_number as usize, &mut working_set.accessory_state())
-                    .expect("Block must be set");
+                    .ok_or(FilterError::EthAPIError(
+                        EthApiError::PrunedHistoryUnavailable,
+                    ))?;
 
                 // all of the logs we have in the block
                 let mut all_logs: Vec<LogResponse> = Vec::new();
@@ -1338,10 +1826,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
             BlockRangeInclusiveIter::new(from_block_number..=to_block_number, max_headers_range)
         {
             for idx in from..=to {
-                let block = match self
-                    .blocks
-                    .get((idx) as usize, &mut working_set.accessory_state())
-                {
+                let block = match self.get_unpruned_sealed_block(idx, working_set)? {
                     Some(block) => block,
                     None => {
                         return Err(FilterError::EthAPIError(
@@ -1494,9 +1979,9 @@ impl<C: sov_modules_api::Context> Evm<C> {
     ) -> Result<Option<SealedBlock>, EthApiError> {
         // safe, finalized, and pending are not supported
         match block_number {
-            Some(BlockNumberOrTag::Number(block_number)) => Ok(self
-                .blocks
-                .get(block_number as usize, &mut working_set.accessory_state())),
+            Some(BlockNumberOrTag::Number(block_number)) => {
+                self.get_unpruned_sealed_block(block_number, working_set)
+            }
             Some(BlockNumberOrTag::Earliest) => Ok(Some(
                 self.blocks
                     .get(0, &mut working_set.accessory_state())
@@ -1518,6 +2003,25 @@ impl<C: sov_modules_api::Context> Evm<C> {
         }
     }
 
+    /// Helper function to get sealed block by number
+    /// If returns None, block doesn't exist
+    /// Returns an error if the block exists but its history has been pruned
+    pub(crate) fn get_unpruned_sealed_block(
+        &self,
+        block_number: u64,
+        working_set: &mut WorkingSet<C::Storage>,
+    ) -> Result<Option<SealedBlock>, EthApiError> {
+        let mut accessory_state = working_set.accessory_state();
+        match self.blocks.get(block_number as usize, &mut accessory_state) {
+            Some(block) => Ok(Some(block)),
+            // Pruning removes blocks without changing the length of the vec
+            None if block_number < self.blocks.len(&mut accessory_state) as u64 => {
+                Err(EthApiError::PrunedHistoryUnavailable)
+            }
+            None => Ok(None),
+        }
+    }
+
     /// Returns the block number given block hash
     /// If block not found returns None
     pub fn get_block_number_by_block_hash(
@@ -1594,20 +2098,23 @@ pub(crate) fn build_rpc_receipt(
     let block_number = block.header.number;
     let block_timestamp = block.header.timestamp;
     let block_base_fee = block.header.base_fee_per_gas;
-    let other = OtherFields::new(
-        [
-            (
-                "l1FeeRate".into(),
-                format!("{:#x}", block.l1_fee_rate).into(),
-            ),
-            (
-                "l1DiffSize".into(),
-                format!("{:#x}", receipt.l1_diff_size).into(),
-            ),
-        ]
-        .into_iter()
-        .collect(),
-    );
+    // System transactions don't pay L1 fee.
+    let l1_fee = if transaction.signer() == SYSTEM_SIGNER {
+        U256::ZERO
+    } else {
+        calculate_l1_fee(block.l1_fee_rate, receipt.l1_diff_size)
+    };
+    // Receipts carry the same L1 fee fields `citrea_estimateL1Fee` returns
+    let l1_fee_fields = serde_json::json!(L1FeeEstimate {
+        l1_diff_size: U64::from(receipt.l1_diff_size),
+        l1_fee_rate: U256::from(block.l1_fee_rate),
+        l1_fee_overhead: U64::from(L1_FEE_OVERHEAD),
+        l1_fee,
+    });
+    let other = OtherFields::new(match l1_fee_fields {
+        serde_json::Value::Object(fields) => fields.into_iter().collect(),
+        _ => unreachable!("L1FeeEstimate serializes to a map"),
+    });
 
     let mut logs = Vec::with_capacity(receipt.receipt.logs.len());
     for (tx_log_idx, log) in receipt.receipt.logs.iter().enumerate() {
@@ -1757,6 +2264,27 @@ fn update_estimated_gas_range(
     Ok(())
 }
 
+/// Environment a transaction of a block is re-executed in
+struct ReplayEnv {
+    cfg_env: CfgEnvWithHandlerCfg,
+    block_env: BlockEnv,
+    tx_env: TxEnv,
+    tx_hash: TxHash,
+    tx_info: TransactionInfo,
+    l1_fee_rate: u128,
+}
+
+/// Context of a transaction in a block, used by the traces
+fn transaction_info(sealed_block: &SealedBlock, tx_hash: TxHash, index: usize) -> TransactionInfo {
+    TransactionInfo {
+        hash: Some(tx_hash),
+        index: Some(index as u64),
+        block_hash: Some(sealed_block.header.hash()),
+        block_number: Some(sealed_block.header.number),
+        base_fee: sealed_block.header.base_fee_per_gas.map(u128::from),
+    }
+}
+
 #[inline]
 fn set_state_to_end_of_evm_block<C: sov_modules_api::Context>(
     block_number: u64,
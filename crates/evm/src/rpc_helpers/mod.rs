@@ -8,14 +8,17 @@ use reth_rpc_eth_types::{EthApiError, EthResult};
 use reth_rpc_types::state::AccountOverride;
 use reth_rpc_types::BlockOverrides;
 use revm::Database;
+pub use simulate::*;
 
 mod filter;
 mod log_utils;
 mod responses;
+mod simulate;
 mod tracing_utils;
 
 #[cfg(feature = "native")]
 use revm::primitives::BlockEnv;
+pub use tracing_utils::FLAT_CALL_TRACER;
 pub(crate) use tracing_utils::*;
 
 use crate::db::EvmDb;
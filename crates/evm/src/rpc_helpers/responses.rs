@@ -1,7 +1,7 @@
 use std::hash::Hash;
 
 use alloy_primitives::Bytes;
-use reth_primitives::{Address, Log, U256};
+use reth_primitives::{Address, Log, U256, U64};
 use revm::primitives::B256;
 
 /// Ethereum Log emitted by a transaction
@@ -36,3 +36,18 @@ impl TryInto<Log> for LogResponse {
         Log::new(self.address, self.topics, self.data).ok_or("Invalid LogResponse")
     }
 }
+
+/// L1 fee of a transaction, returned by `citrea_estimateL1Fee`
+/// and flattened into transaction receipts
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1FeeEstimate {
+    /// Estimated size of the state diff of the transaction
+    pub l1_diff_size: U64,
+    /// L1 fee rate of the block the transaction is estimated in
+    pub l1_fee_rate: U256,
+    /// Bytes every transaction pays for besides its diff size
+    pub l1_fee_overhead: U64,
+    /// Total L1 fee, `l1FeeRate * (l1DiffSize + l1FeeOverhead)`
+    pub l1_fee: U256,
+}
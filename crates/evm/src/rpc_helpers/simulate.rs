@@ -0,0 +1,120 @@
+use alloy_primitives::{address, b256, Log as PrimitiveLog};
+use reth_primitives::{Address, Bytes, B256, U64};
+use reth_rpc_types::state::StateOverride;
+use reth_rpc_types::{BlockOverrides, Log, TransactionRequest};
+use revm_inspectors::transfer::TransferOperation;
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of blocks `eth_simulateV1` simulates in a single request.
+pub const MAX_SIMULATE_BLOCKS: usize = 256;
+
+/// Pseudo address emitting the ether transfer logs of `eth_simulateV1`, as in ERC-7528.
+pub const TRANSFER_LOG_ADDRESS: Address = address!("eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee");
+
+/// Topic of the ERC-20 `Transfer(address,address,uint256)` event.
+const TRANSFER_EVENT_TOPIC: B256 =
+    b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+/// Error code of a reverted simulated call.
+pub(crate) const SIMULATE_REVERT_CODE: i32 = 3;
+
+/// Error code of a simulated call which halted, e.g. ran out of gas.
+pub(crate) const SIMULATE_HALT_CODE: i32 = -32015;
+
+/// Request of `eth_simulateV1`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatePayload {
+    /// Blocks to simulate, in order, on top of the requested block.
+    pub block_state_calls: Vec<SimBlock>,
+    /// Adds a log for every ether transfer of a successful call.
+    /// The logs are emitted by [`TRANSFER_LOG_ADDRESS`] and come before the logs of the call.
+    #[serde(default)]
+    pub trace_transfers: bool,
+    /// Executes the calls like transactions: nonces, balances, the base fee
+    /// and the L1 fee are checked and charged.
+    #[serde(default)]
+    pub validation: bool,
+}
+
+/// A simulated block of `eth_simulateV1`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimBlock {
+    /// Overrides of the block environment. The number defaults to the parent number + 1,
+    /// the timestamp to the parent timestamp + 1.
+    #[serde(default)]
+    pub block_overrides: Option<BlockOverrides>,
+    /// Overrides applied to the state before the calls of the block.
+    #[serde(default)]
+    pub state_overrides: Option<StateOverride>,
+    /// Calls executed in the block, each seeing the state changes of the previous ones.
+    #[serde(default)]
+    pub calls: Vec<TransactionRequest>,
+}
+
+/// Result of a simulated block of `eth_simulateV1`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedBlock {
+    /// Block number.
+    pub number: U64,
+    /// Hash of the simulated header.
+    pub hash: B256,
+    /// Hash of the parent block.
+    pub parent_hash: B256,
+    /// Block timestamp.
+    pub timestamp: U64,
+    /// Gas limit of the block.
+    pub gas_limit: U64,
+    /// Gas used by all calls of the block.
+    pub gas_used: U64,
+    /// Base fee of the block.
+    pub base_fee_per_gas: U64,
+    /// Coinbase of the block.
+    pub miner: Address,
+    /// Results of the calls, in order.
+    pub calls: Vec<SimulatedCall>,
+}
+
+/// Result of a single call of `eth_simulateV1`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedCall {
+    /// 1 if the call succeeded, 0 otherwise.
+    pub status: U64,
+    /// Output of the call, the revert data if it reverted.
+    pub return_data: Bytes,
+    /// Gas used by the call.
+    pub gas_used: U64,
+    /// Size of the state diff the call would add to the L1 commitment,
+    /// as returned by `eth_estimateDiffSize`.
+    pub l1_diff_size: U64,
+    /// Logs emitted by the call.
+    pub logs: Vec<Log>,
+    /// Why the call failed, if it did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<SimulateCallError>,
+}
+
+/// Failure of a single call of `eth_simulateV1`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulateCallError {
+    /// JSON-RPC error code, 3 for reverts.
+    pub code: i32,
+    /// Error message.
+    pub message: String,
+}
+
+/// Builds the ERC-7528 log of an ether transfer.
+pub(crate) fn transfer_log(transfer: &TransferOperation) -> PrimitiveLog {
+    PrimitiveLog::new_unchecked(
+        TRANSFER_LOG_ADDRESS,
+        vec![
+            TRANSFER_EVENT_TOPIC,
+            transfer.from.into_word(),
+            transfer.to.into_word(),
+        ],
+        transfer.value.to_be_bytes::<32>().to_vec().into(),
+    )
+}
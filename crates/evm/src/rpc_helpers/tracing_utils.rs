@@ -1,3 +1,5 @@
+use std::cell::RefCell;
+
 use reth_primitives::revm_primitives::TxEnv;
 use reth_primitives::{TransactionSigned, TransactionSignedEcRecovered, TxHash, U256};
 use reth_rpc_eth_types::error::{EthApiError, EthResult, RpcInvalidTransactionError};
@@ -5,26 +7,35 @@ use reth_rpc_types::trace::geth::{
     FourByteFrame, GethDebugBuiltInTracerType, GethDebugTracerType, GethDebugTracingOptions,
     GethTrace, NoopFrame,
 };
+use reth_rpc_types::TransactionInfo;
 use revm::precompile::{PrecompileSpecId, Precompiles};
-use revm::primitives::db::Database;
-use revm::primitives::{Address, BlockEnv, CfgEnvWithHandlerCfg, EVMError, ResultAndState, SpecId};
+use revm::primitives::db::{Database, DatabaseRef};
+use revm::primitives::{
+    AccountInfo, Address, BlockEnv, Bytecode, CfgEnvWithHandlerCfg, EVMError, ResultAndState,
+    SpecId, B256,
+};
 use revm::{inspector_handle_register, Inspector};
 use revm_inspectors::tracing::{FourByteInspector, TracingInspector, TracingInspectorConfig};
+use revm_inspectors::transfer::{TransferInspector, TransferOperation};
 
 use crate::evm::db::EvmDb;
 use crate::handler::{
     citrea_handle_register, CitreaExternal, CitreaExternalExt, TracingCitreaExternal, TxInfo,
 };
 
+/// Name of geth's `flatCallTracer`, which returns parity style call traces.
+pub const FLAT_CALL_TRACER: &str = "flatCallTracer";
+
 pub(crate) fn trace_transaction<C: sov_modules_api::Context>(
     opts: GethDebugTracingOptions,
     config_env: CfgEnvWithHandlerCfg,
     block_env: BlockEnv,
     tx_env: TxEnv,
-    tx_hash: TxHash,
+    tx_info: TransactionInfo,
     db: &mut EvmDb<'_, C>,
     l1_fee_rate: u128,
 ) -> EthResult<(GethTrace, revm::primitives::state::EvmState)> {
+    let tx_hash = tx_info.hash.expect("Traced transactions have a hash");
     let GethDebugTracingOptions {
         config,
         tracer,
@@ -75,9 +86,27 @@ pub(crate) fn trace_transaction<C: sov_modules_api::Context>(
                     return Ok((frame.into(), res.state));
                 }
                 GethDebugBuiltInTracerType::PreStateTracer => {
-                    // Requires DatabaseRef trait
-                    // meaning we need a readonly state to implement this
-                    return Err(EthApiError::Unsupported("PreStateTracer"));
+                    let prestate_config = tracer_config
+                        .into_pre_state_config()
+                        .map_err(|_| EthApiError::InvalidTracerConfig)?;
+                    let inspector = TracingInspector::new(
+                        TracingInspectorConfig::from_geth_prestate_config(&prestate_config),
+                    );
+                    let mut citrea_inspector = TracingCitreaExternal::new(inspector, l1_fee_rate);
+                    let res = inspect_citrea(
+                        &mut *db,
+                        config_env,
+                        block_env,
+                        tx_env,
+                        tx_hash,
+                        &mut citrea_inspector,
+                    )?;
+                    // The state changes are not committed yet, so the db still holds the prestate
+                    let frame = citrea_inspector
+                        .inspector
+                        .into_geth_builder()
+                        .geth_prestate_traces(&res, prestate_config, DatabaseRefAdapter::new(db))?;
+                    return Ok((frame.into(), res.state));
                 }
                 GethDebugBuiltInTracerType::NoopTracer => {
                     Ok((NoopFrame::default().into(), Default::default()))
@@ -85,6 +114,26 @@ pub(crate) fn trace_transaction<C: sov_modules_api::Context>(
                 // TODO: either implement or return unsupported
                 GethDebugBuiltInTracerType::MuxTracer => todo!("MuxTracer"),
             },
+            // Built-in tracers unknown to `GethDebugBuiltInTracerType` are parsed as JS tracers
+            GethDebugTracerType::JsTracer(code) if code == FLAT_CALL_TRACER => {
+                let inspector = TracingInspector::new(TracingInspectorConfig::default_parity());
+                let mut citrea_inspector = TracingCitreaExternal::new(inspector, l1_fee_rate);
+                let res = inspect_citrea(
+                    db,
+                    config_env,
+                    block_env,
+                    tx_env,
+                    tx_hash,
+                    &mut citrea_inspector,
+                )?;
+                let traces = citrea_inspector
+                    .inspector
+                    .into_parity_builder()
+                    .into_localized_transaction_traces(tx_info);
+                let frame = serde_json::to_value(traces)
+                    .map_err(|e| EthApiError::InternalJsTracerError(e.to_string()))?;
+                Ok((GethTrace::JS(frame), res.state))
+            }
             GethDebugTracerType::JsTracer(_code) => {
                 // This also requires DatabaseRef trait
                 // Implement after readonly state is implemented
@@ -118,6 +167,62 @@ pub(crate) fn trace_transaction<C: sov_modules_api::Context>(
     Ok((frame.into(), res.state))
 }
 
+/// Traces a transaction with a [`TracingInspector`] for parity style traces.
+///
+/// The state changes are not committed, so the caller can still read the state
+/// before the transaction from `db` while building the traces, e.g. for `stateDiff`.
+pub(crate) fn trace_transaction_parity<C: sov_modules_api::Context>(
+    config: TracingInspectorConfig,
+    config_env: CfgEnvWithHandlerCfg,
+    block_env: BlockEnv,
+    tx_env: TxEnv,
+    tx_hash: TxHash,
+    db: &mut EvmDb<'_, C>,
+    l1_fee_rate: u128,
+) -> EthResult<(TracingInspector, ResultAndState)> {
+    let inspector = TracingInspector::new(config);
+    let mut citrea_inspector = TracingCitreaExternal::new(inspector, l1_fee_rate);
+    let res = inspect_citrea(
+        db,
+        config_env,
+        block_env,
+        tx_env,
+        tx_hash,
+        &mut citrea_inspector,
+    )?;
+    Ok((citrea_inspector.inspector, res))
+}
+
+/// Exposes a [Database] as a [DatabaseRef], which tracers need to look up the state
+/// before a transaction. [EvmDb] needs mutable access to the working set even for reads.
+pub(crate) struct DatabaseRefAdapter<'a, DB>(RefCell<&'a mut DB>);
+
+impl<'a, DB> DatabaseRefAdapter<'a, DB> {
+    pub(crate) fn new(db: &'a mut DB) -> Self {
+        Self(RefCell::new(db))
+    }
+}
+
+impl<'a, DB: Database> DatabaseRef for DatabaseRefAdapter<'a, DB> {
+    type Error = DB::Error;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.0.borrow_mut().basic(address)
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.0.borrow_mut().code_by_hash(code_hash)
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.0.borrow_mut().storage(address, index)
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        self.0.borrow_mut().block_hash(number)
+    }
+}
+
 /// Executes the [Env] against the given [Database] without committing state changes.
 fn inspect_citrea<DB, I>(
     db: DB,
@@ -204,6 +309,35 @@ where
     Ok((result_and_state, tx_info))
 }
 
+/// Executes a simulated call without committing state changes and records its ether transfers.
+/// Returns the transfers together with the [TxInfo] holding the L1 diff size of the call.
+pub(crate) fn inspect_with_transfers<DB>(
+    db: DB,
+    config_env: CfgEnvWithHandlerCfg,
+    block_env: BlockEnv,
+    tx_env: TxEnv,
+    l1_fee_rate: u128,
+) -> Result<(ResultAndState, TxInfo, Vec<TransferOperation>), EVMError<DB::Error>>
+where
+    DB: Database,
+    <DB as Database>::Error: Into<EthApiError>,
+{
+    let tmp_hash: TxHash = b"hash_of_an_ephemeral_transaction".into();
+    let mut citrea_inspector =
+        TracingCitreaExternal::new(TransferInspector::new(false), l1_fee_rate);
+    let res = inspect_citrea(
+        db,
+        config_env,
+        block_env,
+        tx_env,
+        tmp_hash,
+        &mut citrea_inspector,
+    )?;
+    // default 0 in case tx was unsuccessful
+    let tx_info = citrea_inspector.get_tx_info(tmp_hash).unwrap_or_default();
+    Ok((res, tx_info, citrea_inspector.inspector.into_transfers()))
+}
+
 /// Taken from reth
 /// https://github.com/paradigmxyz/reth/blob/606640285e763b64519213bad34c76fe4d24652f/crates/rpc/rpc/src/eth/revm_utils.rs#L69
 /// Helper type to work with different transaction types when configuring the EVM env.
@@ -1,9 +1,12 @@
+use alloy_primitives::FixedBytes;
+use alloy_sol_types::SolEvent;
+use citrea_primitives::forks::FORKS;
 use lazy_static::lazy_static;
 use rand::Rng;
 use reth_primitives::hex_literal::hex;
 use reth_primitives::{
-    Address, Bloom, Bytes, Header, Signature, TransactionSigned, B256, EMPTY_OMMER_ROOT_HASH,
-    KECCAK_EMPTY, U256,
+    Address, Bloom, Bytes, Header, Log, Signature, TransactionSigned, B256, EMPTY_OMMER_ROOT_HASH,
+    KECCAK_EMPTY, U256, U64,
 };
 use revm::primitives::BlockEnv;
 use sov_modules_api::hooks::HookSoftConfirmationInfo;
@@ -12,10 +15,15 @@ use sov_rollup_interface::spec::SpecId;
 
 use super::genesis_tests::GENESIS_DA_TXS_COMMITMENT;
 use crate::evm::primitive_types::{Block, Receipt, SealedBlock, TransactionSignedAndRecovered};
+use crate::evm::system_contracts::BridgeContract;
+use crate::evm::{
+    DbAccount, BRIDGE_CONTRACT_ADDRESS, HISTORY_SERVE_WINDOW, HISTORY_STORAGE_ADDRESS,
+    HISTORY_STORAGE_CODE,
+};
 use crate::tests::genesis_tests::BENEFICIARY;
 use crate::tests::utils::{get_evm, get_evm_test_config, GENESIS_STATE_ROOT};
 use crate::tests::DEFAULT_CHAIN_ID;
-use crate::PendingTransaction;
+use crate::{BridgeWithdrawal, PendingTransaction, MAX_WITHDRAWALS_PER_REQUEST};
 
 lazy_static! {
     pub(crate) static ref DA_ROOT_HASH: B256 = B256::from([5u8; 32]);
@@ -486,3 +494,142 @@ fn begin_soft_confirmation_hook_appends_last_block_hashes() {
         .get(&U256::from(2), &mut working_set)
         .is_some());
 }
+
+#[test]
+fn begin_soft_confirmation_hook_stores_historical_block_hashes_after_prague() {
+    let (mut evm, mut working_set) = get_evm(&get_evm_test_config());
+
+    let mut soft_confirmation_info = HookSoftConfirmationInfo {
+        l2_height: 2,
+        da_slot_hash: DA_ROOT_HASH.0,
+        da_slot_height: 1,
+        da_slot_txs_commitment: *GENESIS_DA_TXS_COMMITMENT,
+        pre_state_root: [10u8; 32].to_vec(),
+        current_spec: SpecId::Fork1,
+        pub_key: vec![],
+        deposit_data: vec![],
+        l1_fee_rate: 0,
+        timestamp: 54,
+    };
+    evm.begin_soft_confirmation_hook(&soft_confirmation_info, &mut working_set);
+    evm.end_soft_confirmation_hook(&soft_confirmation_info, &mut working_set);
+    evm.finalize_hook(&[11u8; 32].into(), &mut working_set.accessory_state());
+
+    // history contract is not deployed before prague
+    assert!(evm
+        .accounts
+        .get(&HISTORY_STORAGE_ADDRESS, &mut working_set)
+        .is_none());
+
+    soft_confirmation_info.l2_height = 3;
+    soft_confirmation_info.pre_state_root = [11u8; 32].to_vec();
+    soft_confirmation_info.current_spec = SpecId::Fork2;
+    evm.begin_soft_confirmation_hook(&soft_confirmation_info, &mut working_set);
+
+    // the contract is deployed by the migration of the fork, not by storing block hashes
+    assert!(evm
+        .accounts
+        .get(&HISTORY_STORAGE_ADDRESS, &mut working_set)
+        .is_none());
+
+    let fork2 = FORKS
+        .iter()
+        .find(|fork| fork.spec_id == SpecId::Fork2)
+        .unwrap();
+    evm.fork_activated(fork2, &mut working_set);
+
+    let account = evm
+        .accounts
+        .get(&HISTORY_STORAGE_ADDRESS, &mut working_set)
+        .unwrap();
+    assert_eq!(account.nonce, 1);
+    assert_eq!(
+        evm.offchain_code
+            .get(
+                &account.code_hash.unwrap(),
+                &mut working_set.offchain_state()
+            )
+            .unwrap()
+            .original_bytes()
+            .as_ref(),
+        HISTORY_STORAGE_CODE
+    );
+
+    let parent_hash = evm
+        .latest_block_hashes
+        .get(&U256::from(2), &mut working_set)
+        .unwrap();
+    let stored_hash = DbAccount::new(HISTORY_STORAGE_ADDRESS)
+        .storage
+        .get(&U256::from(2 % HISTORY_SERVE_WINDOW), &mut working_set)
+        .unwrap();
+    assert_eq!(stored_hash, U256::from_be_bytes(parent_hash.0));
+}
+
+#[test]
+fn end_soft_confirmation_hook_indexes_withdrawals() {
+    let (mut evm, mut working_set) = get_evm(&get_evm_test_config());
+
+    let soft_confirmation_info = HookSoftConfirmationInfo {
+        l2_height: 2,
+        da_slot_hash: DA_ROOT_HASH.0,
+        da_slot_height: 1,
+        da_slot_txs_commitment: *GENESIS_DA_TXS_COMMITMENT,
+        pre_state_root: [10u8; 32].to_vec(),
+        current_spec: SpecId::Genesis,
+        pub_key: vec![],
+        deposit_data: vec![],
+        l1_fee_rate: 0,
+        timestamp: 54,
+    };
+    evm.begin_soft_confirmation_hook(&soft_confirmation_info, &mut working_set);
+
+    let withdrawal_log = |index: u64| Log {
+        address: BRIDGE_CONTRACT_ADDRESS,
+        data: BridgeContract::Withdrawal {
+            utxo: BridgeContract::UTXO {
+                txId: B256::from([index as u8; 32]),
+                outputId: FixedBytes::from([0, 0, 0, index as u8]),
+            },
+            index: U256::from(index),
+            timestamp: U256::from(54),
+        }
+        .encode_log_data(),
+    };
+    let mut withdrawing_tx = create_pending_transaction(B256::from([1u8; 32]), 1);
+    // index 2 is missing, e.g. not indexed by a node that started after it was made
+    withdrawing_tx.receipt.receipt.logs =
+        vec![withdrawal_log(0), withdrawal_log(1), withdrawal_log(3)];
+    evm.pending_transactions.push(withdrawing_tx);
+
+    // same event from another contract is not a withdrawal
+    let mut other_tx = create_pending_transaction(B256::from([2u8; 32]), 2);
+    other_tx.receipt.receipt.logs = vec![Log {
+        address: Address::from([3u8; 20]),
+        ..withdrawal_log(2)
+    }];
+    evm.pending_transactions.push(other_tx);
+
+    evm.end_soft_confirmation_hook(&soft_confirmation_info, &mut working_set);
+
+    let withdrawals = evm.get_withdrawals(0, 5, &mut working_set).unwrap();
+    assert_eq!(
+        withdrawals,
+        [0u64, 1, 3]
+            .into_iter()
+            .map(|index| BridgeWithdrawal {
+                index: U64::from(index),
+                utxo_tx_id: B256::from([index as u8; 32]),
+                utxo_output_id: FixedBytes::from([0, 0, 0, index as u8]),
+                timestamp: U256::from(54),
+                block_number: U64::from(2),
+                transaction_hash: B256::from([1u8; 32]),
+            })
+            .collect::<Vec<_>>()
+    );
+
+    assert!(evm.get_withdrawals(1, 0, &mut working_set).is_err());
+    assert!(evm
+        .get_withdrawals(0, MAX_WITHDRAWALS_PER_REQUEST, &mut working_set)
+        .is_err());
+}
@@ -528,6 +528,8 @@ fn check_against_third_block_receipts(receipts: Vec<AnyTransactionReceipt>) {
         "gasUsed": "0x13aec",
         "l1DiffSize": "0x231",
         "l1FeeRate": "0x1",
+        "l1Fee": "0x0",
+        "l1FeeOverhead": "0x3",
         "logs": [
             {
                 "address": "0x3100000000000000000000000000000000000001",
@@ -561,6 +563,8 @@ fn check_against_third_block_receipts(receipts: Vec<AnyTransactionReceipt>) {
         "gasUsed": "0x6720",
         "l1DiffSize": "0x60",
         "l1FeeRate": "0x1",
+        "l1Fee": "0x63",
+        "l1FeeOverhead": "0x3",
         "logs": [
             {
                 "address": "0x819c5497b157177315e1204f52e588b393771719",
@@ -612,6 +616,8 @@ fn check_against_third_block_receipts(receipts: Vec<AnyTransactionReceipt>) {
         "gasUsed": "0x66fc",
         "l1DiffSize": "0x60",
         "l1FeeRate": "0x1",
+        "l1Fee": "0x63",
+        "l1FeeOverhead": "0x3",
         "logs": [
             {
                 "address": "0x819c5497b157177315e1204f52e588b393771719",
@@ -663,6 +669,8 @@ fn check_against_third_block_receipts(receipts: Vec<AnyTransactionReceipt>) {
         "gasUsed": "0x66fc",
         "l1DiffSize": "0x60",
         "l1FeeRate": "0x1",
+        "l1Fee": "0x63",
+        "l1FeeOverhead": "0x3",
         "logs": [
             {
                 "address": "0x819c5497b157177315e1204f52e588b393771719",
@@ -714,6 +722,8 @@ fn check_against_third_block_receipts(receipts: Vec<AnyTransactionReceipt>) {
         "gasUsed": "0x66fc",
         "l1DiffSize": "0x60",
         "l1FeeRate": "0x1",
+        "l1Fee": "0x63",
+        "l1FeeOverhead": "0x3",
         "logs": [
             {
                 "address": "0x819c5497b157177315e1204f52e588b393771719",
@@ -15,7 +15,7 @@ use crate::query::MIN_TRANSACTION_GAS;
 use crate::smart_contracts::{CallerContract, SimpleStorageContract};
 use crate::tests::queries::{init_evm, init_evm_single_block, init_evm_with_caller_contract};
 use crate::tests::test_signer::TestSigner;
-use crate::{EstimatedDiffSize, Evm};
+use crate::{EstimatedDiffSize, Evm, L1FeeEstimate, L1FeeEstimateRequest};
 
 type C = DefaultContext;
 
@@ -113,6 +113,22 @@ fn test_tx_request_fields_gas() {
             .unwrap()
     );
 
+    let l1_fee = evm.citrea_estimate_l1_fee(
+        L1FeeEstimateRequest::Unsigned(Box::new(tx_req_contract_call.clone())),
+        Some(BlockNumberOrTag::Latest),
+        &mut working_set,
+    );
+    assert_eq!(
+        l1_fee.unwrap(),
+        serde_json::from_value::<L1FeeEstimate>(json![{
+            "l1DiffSize": "0x60",
+            "l1FeeRate": "0x1",
+            "l1FeeOverhead": "0x3",
+            "l1Fee": "0x63"
+        }])
+        .unwrap()
+    );
+
     let tx_req_no_sender = TransactionRequest {
         from: None,
         nonce: None,
@@ -2,6 +2,9 @@ mod basic_queries;
 mod estimate_gas_tests;
 mod evm_call_tests;
 mod log_tests;
+mod proof_tests;
+mod pruning_tests;
+mod simulate_tests;
 
 use std::str::FromStr;
 
@@ -0,0 +1,85 @@
+use reth_primitives::{Address, BlockNumberOrTag, B256, U64};
+use revm::primitives::U256;
+use sov_modules_api::default_context::DefaultContext;
+use sov_state::storage::NativeStorage;
+
+use crate::tests::queries::init_evm;
+use crate::{verify_account_proof, ProofVerificationError};
+
+type C = DefaultContext;
+
+#[test]
+fn get_proof_test() {
+    let (evm, mut working_set, prover_storage, dev_signer, _) = init_evm();
+
+    let contract_addr: Address = Address::from_slice(
+        hex::decode("eeb03d20dae810f52111b853b31c8be6f30f4cd3")
+            .unwrap()
+            .as_slice(),
+    );
+
+    let proof = evm
+        .get_proof(
+            contract_addr,
+            vec![U256::from(0), U256::from(1)],
+            Some(BlockNumberOrTag::Latest),
+            &mut working_set,
+        )
+        .unwrap();
+
+    assert_eq!(proof.block_number, U64::from(3));
+    assert!(proof.code_hash.is_some());
+    assert_eq!(proof.storage_proof[0].value, U256::from(478));
+    assert_eq!(proof.storage_proof[1].value, U256::ZERO);
+
+    // state of block 3 is committed at version 4
+    let state_root = prover_storage.get_root_hash(4).unwrap().0;
+    assert_eq!(proof.state_root, B256::from(state_root));
+    verify_account_proof::<C>(state_root, &proof).unwrap();
+
+    // proof is bound to the state root
+    let old_state_root = prover_storage.get_root_hash(3).unwrap().0;
+    assert!(matches!(
+        verify_account_proof::<C>(old_state_root, &proof),
+        Err(ProofVerificationError::InvalidAccountProof(_))
+    ));
+
+    // proven values can not be altered
+    let mut tampered = proof.clone();
+    tampered.storage_proof[0].value = U256::from(479);
+    assert!(matches!(
+        verify_account_proof::<C>(state_root, &tampered),
+        Err(ProofVerificationError::InvalidStorageProof { .. })
+    ));
+
+    let mut tampered = proof.clone();
+    tampered.balance += U256::from(1);
+    assert!(matches!(
+        verify_account_proof::<C>(state_root, &tampered),
+        Err(ProofVerificationError::InvalidAccountProof(_))
+    ));
+
+    // nonce of the sender at the end of the first block
+    let proof = evm
+        .get_proof(
+            dev_signer.address(),
+            vec![],
+            Some(BlockNumberOrTag::Number(1)),
+            &mut working_set,
+        )
+        .unwrap();
+    assert_eq!(proof.nonce, U64::from(3));
+    let state_root = prover_storage.get_root_hash(2).unwrap().0;
+    assert_eq!(proof.state_root, B256::from(state_root));
+    verify_account_proof::<C>(state_root, &proof).unwrap();
+
+    // accounts which do not exist are proven to be absent
+    let proof = evm
+        .get_proof(Address::repeat_byte(0x42), vec![], None, &mut working_set)
+        .unwrap();
+    assert_eq!(proof.balance, U256::ZERO);
+    assert_eq!(proof.nonce, U64::ZERO);
+    let state_root = prover_storage.get_root_hash(4).unwrap().0;
+    assert_eq!(proof.state_root, B256::from(state_root));
+    verify_account_proof::<C>(state_root, &proof).unwrap();
+}
@@ -0,0 +1,117 @@
+use jsonrpsee::types::ErrorObjectOwned;
+use reth_primitives::{BlockId, BlockNumberOrTag, U64};
+use reth_rpc_eth_types::EthApiError;
+
+use crate::tests::queries::init_evm;
+
+#[test]
+fn pruned_history_is_reported_by_number_and_hash() {
+    let (evm, mut working_set, _, _, _) = init_evm();
+
+    let block_hash = evm
+        .get_block_by_number(
+            Some(BlockNumberOrTag::Number(1)),
+            Some(false),
+            &mut working_set,
+        )
+        .unwrap()
+        .unwrap()
+        .header
+        .hash
+        .unwrap();
+    let tx_hash = evm
+        .get_transaction_by_block_number_and_index(
+            BlockNumberOrTag::Number(1),
+            U64::from(0),
+            &mut working_set,
+        )
+        .unwrap()
+        .unwrap()
+        .hash;
+
+    evm.remove_history(1..=1, &mut working_set);
+
+    let pruned: ErrorObjectOwned = EthApiError::PrunedHistoryUnavailable.into();
+
+    assert_eq!(
+        evm.get_block_by_number(
+            Some(BlockNumberOrTag::Number(1)),
+            Some(false),
+            &mut working_set,
+        )
+        .unwrap_err(),
+        pruned
+    );
+    assert_eq!(
+        evm.get_block_by_hash(block_hash, Some(false), &mut working_set)
+            .unwrap_err(),
+        pruned
+    );
+    assert_eq!(
+        evm.get_block_receipts(BlockId::Hash(block_hash.into()), &mut working_set)
+            .unwrap_err(),
+        pruned
+    );
+    assert_eq!(
+        evm.get_transaction_by_block_hash_and_index(block_hash, U64::from(0), &mut working_set)
+            .unwrap_err(),
+        pruned
+    );
+    assert_eq!(
+        evm.get_transaction_by_hash(tx_hash, &mut working_set)
+            .unwrap_err(),
+        pruned
+    );
+    assert_eq!(
+        evm.get_transaction_receipt(tx_hash, &mut working_set)
+            .unwrap_err(),
+        pruned
+    );
+    assert_eq!(
+        evm.trace_block_transactions_by_number(1, None, None, &mut working_set)
+            .unwrap_err(),
+        pruned
+    );
+
+    // Unknown hashes are still unknown
+    assert_eq!(
+        evm.get_block_by_hash([5u8; 32].into(), Some(false), &mut working_set),
+        Ok(None)
+    );
+    assert!(evm
+        .get_transaction_by_hash([5u8; 32].into(), &mut working_set)
+        .unwrap()
+        .is_none());
+
+    // Blocks after the pruned range are untouched
+    assert!(evm
+        .get_block_by_number(
+            Some(BlockNumberOrTag::Number(2)),
+            Some(true),
+            &mut working_set,
+        )
+        .unwrap()
+        .is_some());
+    assert_eq!(
+        evm.trace_block_transactions_by_number(2, None, None, &mut working_set)
+            .unwrap()
+            .len(),
+        4
+    );
+}
+
+#[test]
+fn genesis_is_never_pruned() {
+    let (evm, mut working_set, _, _, _) = init_evm();
+
+    evm.remove_history(0..=0, &mut working_set);
+
+    assert!(evm
+        .get_block_by_number(
+            Some(BlockNumberOrTag::Number(0)),
+            Some(false),
+            &mut working_set,
+        )
+        .unwrap()
+        .is_some());
+}
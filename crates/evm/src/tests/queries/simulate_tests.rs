@@ -0,0 +1,147 @@
+use std::str::FromStr;
+
+use reth_primitives::{Address, BlockNumberOrTag, Bytes, TxKind, B256};
+use reth_rpc_types::request::{TransactionInput, TransactionRequest};
+use reth_rpc_types::{BlockId, BlockOverrides};
+use revm::primitives::U256;
+
+use crate::smart_contracts::SimpleStorageContract;
+use crate::tests::queries::init_evm;
+use crate::{SimBlock, SimulatePayload, TRANSFER_LOG_ADDRESS};
+
+#[test]
+fn simulate_calls_see_previous_blocks() {
+    let (evm, mut working_set, _, signer, _) = init_evm();
+
+    let contract = SimpleStorageContract::default();
+    let contract_address = Address::from_str("0xeeb03d20dae810f52111b853b31c8be6f30f4cd3").unwrap();
+    let call = |input: Bytes| TransactionRequest {
+        from: Some(signer.address()),
+        to: Some(TxKind::Call(contract_address)),
+        input: TransactionInput::new(input),
+        ..Default::default()
+    };
+
+    let latest = evm
+        .get_block_by_number(Some(BlockNumberOrTag::Latest), None, &mut working_set)
+        .unwrap()
+        .unwrap();
+
+    let blocks = evm
+        .eth_simulate_v1(
+            SimulatePayload {
+                block_state_calls: vec![
+                    SimBlock {
+                        calls: vec![call(contract.set_call_data(5).into())],
+                        ..Default::default()
+                    },
+                    SimBlock {
+                        calls: vec![call(contract.get_call_data().into())],
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            },
+            Some(BlockId::Number(BlockNumberOrTag::Latest)),
+            &mut working_set,
+        )
+        .unwrap();
+
+    assert_eq!(blocks.len(), 2);
+    assert_eq!(
+        blocks[0].number.to::<u64>(),
+        latest.header.number.unwrap() + 1
+    );
+    assert_eq!(blocks[0].parent_hash, latest.header.hash.unwrap());
+    assert_eq!(
+        blocks[1].number.to::<u64>(),
+        latest.header.number.unwrap() + 2
+    );
+    assert_eq!(blocks[1].parent_hash, blocks[0].hash);
+
+    let set_call = &blocks[0].calls[0];
+    assert_eq!(set_call.status.to::<u64>(), 1);
+    assert!(set_call.l1_diff_size.to::<u64>() > 0);
+    assert_eq!(blocks[0].gas_used, set_call.gas_used);
+
+    let get_call = &blocks[1].calls[0];
+    assert_eq!(get_call.status.to::<u64>(), 1);
+    assert_eq!(
+        get_call.return_data,
+        Bytes::from(U256::from(5).to_be_bytes::<32>().to_vec())
+    );
+}
+
+#[test]
+fn simulate_traces_transfers() {
+    let (evm, mut working_set, _, signer, _) = init_evm();
+
+    let receiver = Address::from_str("0x000000000000000000000000000000000000dead").unwrap();
+    let blocks = evm
+        .eth_simulate_v1(
+            SimulatePayload {
+                block_state_calls: vec![SimBlock {
+                    calls: vec![TransactionRequest {
+                        from: Some(signer.address()),
+                        to: Some(TxKind::Call(receiver)),
+                        value: Some(U256::from(1000)),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                trace_transfers: true,
+                ..Default::default()
+            },
+            None,
+            &mut working_set,
+        )
+        .unwrap();
+
+    let logs = &blocks[0].calls[0].logs;
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].address(), TRANSFER_LOG_ADDRESS);
+    assert_eq!(logs[0].topics()[1], signer.address().into_word());
+    assert_eq!(logs[0].topics()[2], receiver.into_word());
+    assert_eq!(
+        logs[0].data().data,
+        Bytes::from(U256::from(1000).to_be_bytes::<32>().to_vec())
+    );
+    assert_eq!(logs[0].block_hash, Some(blocks[0].hash));
+    assert_eq!(logs[0].log_index, Some(0));
+}
+
+#[test]
+fn simulate_rejects_decreasing_block_numbers() {
+    let (evm, mut working_set, _, _, _) = init_evm();
+
+    let result = evm.eth_simulate_v1(
+        SimulatePayload {
+            block_state_calls: vec![SimBlock {
+                block_overrides: Some(BlockOverrides {
+                    number: Some(U256::from(1)),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+        None,
+        &mut working_set,
+    );
+
+    assert!(result.is_err());
+
+    // Blocks without calls are still simulated
+    let blocks = evm
+        .eth_simulate_v1(
+            SimulatePayload {
+                block_state_calls: vec![SimBlock::default()],
+                ..Default::default()
+            },
+            None,
+            &mut working_set,
+        )
+        .unwrap();
+    assert!(blocks[0].calls.is_empty());
+    assert_ne!(blocks[0].hash, B256::ZERO);
+}
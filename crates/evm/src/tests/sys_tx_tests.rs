@@ -1,9 +1,10 @@
 use std::collections::HashMap;
 use std::str::FromStr;
 
-use alloy_primitives::LogData;
+use alloy_primitives::{FixedBytes, LogData};
+use alloy_sol_types::SolCall;
 use reth_primitives::constants::ETHEREUM_BLOCK_GAS_LIMIT;
-use reth_primitives::{address, b256, hex, BlockNumberOrTag, Log, TxKind, U64};
+use reth_primitives::{address, b256, hex, BlockNumberOrTag, Log, TxKind, B256, U64};
 use reth_rpc_types::{TransactionInput, TransactionRequest};
 use revm::primitives::{Bytes, KECCAK_EMPTY, U256};
 use sov_modules_api::default_context::DefaultContext;
@@ -16,7 +17,8 @@ use sov_rollup_interface::spec::SpecId;
 
 use crate::call::CallMessage;
 use crate::evm::primitive_types::Receipt;
-use crate::evm::system_contracts::BitcoinLightClient;
+use crate::evm::system_contracts::{BitcoinLightClient, BridgeContract};
+use crate::evm::DbAccount;
 use crate::handler::L1_FEE_OVERHEAD;
 use crate::smart_contracts::{BlockHashContract, LogsContract};
 use crate::system_contracts::{Bridge, ProxyAdmin};
@@ -871,3 +873,86 @@ fn test_change_upgrade_owner() {
         .unwrap()
     );
 }
+
+#[test]
+fn test_bridge_withdrawal_utxo_slots() {
+    let (config, dev_signer, _) =
+        get_evm_config_starting_base_fee(U256::from_str("100000000000000000000").unwrap(), None, 1);
+
+    let (mut evm, mut working_set) = get_evm(&config);
+
+    let l1_fee_rate = 1;
+    let l2_height = 2;
+
+    let sender_address = generate_address::<C>("sender");
+    let context = C::new(sender_address, l2_height, SpecId::Genesis, l1_fee_rate);
+
+    let soft_confirmation_info = HookSoftConfirmationInfo {
+        l2_height,
+        da_slot_hash: [5u8; 32],
+        da_slot_height: 1,
+        da_slot_txs_commitment: [42u8; 32],
+        pre_state_root: [10u8; 32].to_vec(),
+        current_spec: SpecId::Genesis,
+        pub_key: vec![],
+        deposit_data: vec![],
+        l1_fee_rate,
+        timestamp: 0,
+    };
+
+    evm.begin_soft_confirmation_hook(&soft_confirmation_info, &mut working_set);
+
+    let utxos = [
+        (B256::from([1u8; 32]), FixedBytes::from([0, 0, 0, 1])),
+        (
+            B256::from([2u8; 32]),
+            FixedBytes::from([0xaa, 0xbb, 0xcc, 0xdd]),
+        ),
+    ];
+    let withdraw_txs = utxos
+        .iter()
+        .enumerate()
+        .map(|(nonce, (tx_id, output_id))| {
+            dev_signer
+                .sign_default_transaction(
+                    TxKind::Call(Bridge::address()),
+                    BridgeContract::withdrawCall {
+                        txId: *tx_id,
+                        outputId: *output_id,
+                    }
+                    .abi_encode(),
+                    nonce as u64,
+                    // deposit amount set in `Bridge::initialize`
+                    10_000_000_000_000_000_000,
+                )
+                .unwrap()
+        })
+        .collect();
+    evm.call(
+        CallMessage { txs: withdraw_txs },
+        &context,
+        &mut working_set,
+    )
+    .unwrap();
+
+    evm.end_soft_confirmation_hook(&soft_confirmation_info, &mut working_set);
+    evm.finalize_hook(&[99u8; 32].into(), &mut working_set.accessory_state());
+
+    let bridge_storage = DbAccount::new(Bridge::address()).storage;
+    for (index, (tx_id, output_id)) in utxos.iter().enumerate() {
+        let [tx_id_slot, output_id_slot] = Bridge::withdrawal_utxo_slots(index as u64);
+        assert_eq!(
+            bridge_storage.get(&tx_id_slot, &mut working_set),
+            Some(U256::from_be_bytes(tx_id.0))
+        );
+        // `bytes4` is stored in the low-order bytes of its slot
+        assert_eq!(
+            bridge_storage.get(&output_id_slot, &mut working_set),
+            Some(U256::from(u32::from_be_bytes(output_id.0)))
+        );
+    }
+    // the withdrawals are indexed under the same indices
+    let withdrawals = evm.get_withdrawals(0, 1, &mut working_set).unwrap();
+    assert_eq!(withdrawals.len(), 2);
+    assert_eq!(withdrawals[1].utxo_tx_id, utxos[1].0);
+}
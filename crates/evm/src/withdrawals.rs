@@ -0,0 +1,87 @@
+use alloy_primitives::FixedBytes;
+use alloy_sol_types::SolEvent;
+use jsonrpsee::core::RpcResult;
+use reth_primitives::{Log, B256, U256, U64};
+use reth_rpc_eth_types::error::EthApiError;
+use serde::{Deserialize, Serialize};
+use sov_modules_api::{StateMapAccessor, WorkingSet};
+
+use crate::system_contracts::BridgeContract;
+use crate::{Evm, BRIDGE_CONTRACT_ADDRESS};
+
+/// Maximum number of withdrawals `citrea_getWithdrawals` returns in a single request.
+pub const MAX_WITHDRAWALS_PER_REQUEST: u64 = 100;
+
+/// Withdrawal from the `Bridge` system contract.
+///
+/// Indexed from the `Withdrawal` events of every L2 block in `end_soft_confirmation_hook`.
+/// The index is not backfilled, so withdrawals made in L2 blocks a node processed before it
+/// was upgraded to a version with the index are never indexed on that node.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BridgeWithdrawal {
+    /// Index of the withdrawal in `withdrawalUTXOs`.
+    pub index: U64,
+    /// Txid of the UTXO to be spent on Bitcoin.
+    pub utxo_tx_id: B256,
+    /// Output index of the UTXO to be spent on Bitcoin.
+    pub utxo_output_id: FixedBytes<4>,
+    /// Timestamp of the L2 block the withdrawal was made in.
+    pub timestamp: U256,
+    /// Number of the L2 block the withdrawal was made in.
+    pub block_number: U64,
+    /// Hash of the transaction that made the withdrawal.
+    pub transaction_hash: B256,
+}
+
+impl BridgeWithdrawal {
+    /// Decodes a withdrawal from a log, `None` if it is not a `Withdrawal` event of the bridge.
+    pub(crate) fn from_log(log: &Log, block_number: u64, transaction_hash: B256) -> Option<Self> {
+        if log.address != BRIDGE_CONTRACT_ADDRESS
+            || log.topics().first() != Some(&BridgeContract::Withdrawal::SIGNATURE_HASH)
+        {
+            return None;
+        }
+        let event = BridgeContract::Withdrawal::decode_log_data(&log.data, true).ok()?;
+        Some(Self {
+            index: U64::from(event.index.saturating_to::<u64>()),
+            utxo_tx_id: event.utxo.txId,
+            utxo_output_id: event.utxo.outputId,
+            timestamp: event.timestamp,
+            block_number: U64::from(block_number),
+            transaction_hash,
+        })
+    }
+}
+
+impl<C: sov_modules_api::Context> Evm<C> {
+    /// Returns the indexed withdrawals in `from_index..=to_index`.
+    /// Indices without an indexed withdrawal are skipped rather than ending the range.
+    /// This includes the withdrawals a node processed before the index existed, see
+    /// [`BridgeWithdrawal`].
+    pub fn get_withdrawals(
+        &self,
+        from_index: u64,
+        to_index: u64,
+        working_set: &mut WorkingSet<C::Storage>,
+    ) -> RpcResult<Vec<BridgeWithdrawal>> {
+        if from_index > to_index {
+            return Err(EthApiError::InvalidParams(
+                "from_index must not be greater than to_index".to_string(),
+            )
+            .into());
+        }
+        if to_index - from_index >= MAX_WITHDRAWALS_PER_REQUEST {
+            return Err(EthApiError::InvalidParams(format!(
+                "Withdrawal range too large; currently limited to {} withdrawals",
+                MAX_WITHDRAWALS_PER_REQUEST
+            ))
+            .into());
+        }
+
+        let mut accessory_state = working_set.accessory_state();
+        Ok((from_index..=to_index)
+            .filter_map(|index| self.withdrawals.get(&index, &mut accessory_state))
+            .collect())
+    }
+}
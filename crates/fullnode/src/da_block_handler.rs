@@ -6,7 +6,9 @@ use std::sync::Arc;
 use anyhow::anyhow;
 use borsh::{BorshDeserialize, BorshSerialize};
 use citrea_common::cache::L1BlockCache;
-use citrea_common::da::{extract_sequencer_commitments, extract_zk_proofs, get_da_block_at_height};
+use citrea_common::da::{
+    expect_l1_rollback, extract_sequencer_commitments, extract_zk_proofs, sync_l1, L1SyncEvent,
+};
 use citrea_common::error::SyncError;
 use citrea_common::utils::check_l2_range_exists;
 use citrea_primitives::forks::FORKS;
@@ -27,9 +29,9 @@ use sov_rollup_interface::spec::SpecId;
 use sov_rollup_interface::zk::{BatchProofCircuitOutput, Proof, ZkvmHost};
 use tokio::select;
 use tokio::sync::{mpsc, Mutex};
-use tokio::time::{sleep, Duration};
+use tokio::time::Duration;
 use tokio_util::sync::CancellationToken;
-use tracing::{error, info, warn};
+use tracing::{error, warn};
 
 pub(crate) struct L1BlockHandler<C, Vm, Da, StateRoot, DB>
 where
@@ -115,8 +117,12 @@ where
                     return;
                 }
                 _ = &mut l1_sync_worker => {},
-                Some(l1_block) = l1_rx.recv() => {
-                    self.pending_l1_blocks.push_back(l1_block);
+                Some(event) = l1_rx.recv() => match event {
+                    L1SyncEvent::Block(l1_block) => self.pending_l1_blocks.push_back(l1_block),
+                    L1SyncEvent::Reorg { fork_height, orphaned_hashes } => expect_l1_rollback(
+                        fork_height,
+                        self.handle_l1_reorg(fork_height, &orphaned_hashes),
+                    ),
                 },
                 _ = interval.tick() => {
                     self.process_l1_block().await
@@ -212,6 +218,42 @@ where
         self.pending_l1_blocks.pop_front();
     }
 
+    /// Forgets the pending L1 blocks and the sequencer commitments and proofs
+    /// recorded from the L1 blocks orphaned by a reorg.
+    /// The soft confirmations of the rolled back commitments are trusted again.
+    fn handle_l1_reorg(
+        &mut self,
+        fork_height: u64,
+        orphaned_hashes: &[[u8; 32]],
+    ) -> anyhow::Result<()> {
+        self.pending_l1_blocks
+            .retain(|l1_block| l1_block.header().height() <= fork_height);
+
+        let commitments = self
+            .ledger_db
+            .rollback_l1_blocks(fork_height, orphaned_hashes)?;
+
+        for commitment in &commitments {
+            for l2_height in commitment.l2_start_block_number..=commitment.l2_end_block_number {
+                self.ledger_db.put_soft_confirmation_status(
+                    BatchNumber(l2_height),
+                    SoftConfirmationStatus::Trusted,
+                )?;
+            }
+        }
+
+        if let Some(first_l2_height) = commitments
+            .iter()
+            .map(|commitment| commitment.l2_start_block_number)
+            .min()
+        {
+            self.ledger_db
+                .set_last_commitment_l2_height(BatchNumber(first_l2_height.saturating_sub(1)))?;
+        }
+
+        Ok(())
+    }
+
     async fn process_sequencer_commitment(
         &self,
         l1_block: &Da::FilteredBlock,
@@ -416,55 +458,3 @@ where
         Ok(())
     }
 }
-
-async fn sync_l1<Da>(
-    start_l1_height: u64,
-    da_service: Arc<Da>,
-    sender: mpsc::Sender<Da::FilteredBlock>,
-    l1_block_cache: Arc<Mutex<L1BlockCache<Da>>>,
-) where
-    Da: DaService,
-{
-    let mut l1_height = start_l1_height;
-    info!("Starting to sync from L1 height {}", l1_height);
-
-    'block_sync: loop {
-        // TODO: for a node, the da block at slot_height might not have been finalized yet
-        // should wait for it to be finalized
-        let last_finalized_l1_block_header =
-            match da_service.get_last_finalized_block_header().await {
-                Ok(header) => header,
-                Err(e) => {
-                    error!("Could not fetch last finalized L1 block header: {}", e);
-                    sleep(Duration::from_secs(2)).await;
-                    continue;
-                }
-            };
-
-        let new_l1_height = last_finalized_l1_block_header.height();
-
-        for block_number in l1_height + 1..=new_l1_height {
-            let l1_block =
-                match get_da_block_at_height(&da_service, block_number, l1_block_cache.clone())
-                    .await
-                {
-                    Ok(block) => block,
-                    Err(e) => {
-                        error!("Could not fetch last finalized L1 block: {}", e);
-                        sleep(Duration::from_secs(2)).await;
-                        continue 'block_sync;
-                    }
-                };
-
-            if block_number > l1_height {
-                l1_height = block_number;
-                if let Err(e) = sender.send(l1_block).await {
-                    error!("Could not notify about L1 block: {}", e);
-                    continue 'block_sync;
-                }
-            }
-        }
-
-        sleep(Duration::from_secs(2)).await;
-    }
-}
@@ -17,7 +17,7 @@ use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
 use jsonrpsee::server::{BatchRequestConfig, RpcServiceBuilder, ServerBuilder};
 use jsonrpsee::RpcModule;
 use reth_primitives::U64;
-use sov_db::ledger_db::NodeLedgerOps;
+use sov_db::ledger_db::{NodeLedgerOps, SharedLedgerOps};
 use sov_db::schema::types::{BatchNumber, SlotNumber};
 use sov_ledger_rpc::LedgerRpcClient;
 use sov_modules_api::{Context, SignedSoftConfirmation, Spec};
@@ -25,7 +25,7 @@ use sov_modules_stf_blueprint::{Runtime, StfBlueprint};
 use sov_prover_storage_manager::{ProverStorage, ProverStorageManager, SnapshotManager};
 use sov_rollup_interface::da::BlockHeaderTrait;
 use sov_rollup_interface::fork::ForkManager;
-use sov_rollup_interface::rpc::SoftConfirmationResponse;
+use sov_rollup_interface::rpc::{LedgerRpcProvider, SoftConfirmationResponse};
 use sov_rollup_interface::services::da::{DaService, SlotData};
 use sov_rollup_interface::spec::SpecId;
 use sov_rollup_interface::stf::StateTransitionFunction;
@@ -80,7 +80,7 @@ where
     Vm: ZkvmHost + Zkvm,
     <Vm as Zkvm>::CodeCommitment: Send,
     C: Context + Spec<Storage = ProverStorage<SnapshotManager>> + Send + Sync,
-    DB: NodeLedgerOps + Clone + Send + Sync + 'static,
+    DB: NodeLedgerOps + LedgerRpcProvider + Clone + Send + Sync + 'static,
     RT: Runtime<C, Da::Spec>,
 {
     /// Creates a new `StateTransitionRunner`.
@@ -315,10 +315,8 @@ where
     pub async fn run(&mut self) -> Result<(), anyhow::Error> {
         // Last L1/L2 height before shutdown.
         let start_l1_height = {
-            let last_scanned_l1_height = self
-                .ledger_db
-                .get_last_scanned_l1_height()
-                .unwrap_or_else(|_| {
+            let last_scanned_l1_height =
+                SharedLedgerOps::get_last_scanned_l1_height(&self.ledger_db).unwrap_or_else(|_| {
                     panic!("Failed to get last scanned l1 height from the ledger db")
                 });
 
@@ -334,6 +332,8 @@ where
                 self.ledger_db.get_last_pruned_l2_height()?.unwrap_or(0),
                 self.soft_confirmation_tx.subscribe(),
                 self.ledger_db.clone(),
+                self.storage_manager.create_finalized_storage()?,
+                self.storage_manager.get_native_db_handle(),
             );
 
             self.task_manager
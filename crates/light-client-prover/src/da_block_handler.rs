@@ -4,7 +4,7 @@ use std::sync::Arc;
 use anyhow::anyhow;
 use borsh::BorshDeserialize;
 use citrea_common::cache::L1BlockCache;
-use citrea_common::da::get_da_block_at_height;
+use citrea_common::da::{expect_l1_rollback, sync_l1, L1SyncEvent};
 use citrea_common::LightClientProverConfig;
 use citrea_primitives::forks::FORKS;
 use jsonrpsee::http_client::HttpClient;
@@ -23,9 +23,9 @@ use sov_rollup_interface::zk::{
 use sov_stf_runner::ProverService;
 use tokio::select;
 use tokio::sync::{mpsc, Mutex};
-use tokio::time::{sleep, Duration};
+use tokio::time::Duration;
 use tokio_util::sync::CancellationToken;
-use tracing::{error, info};
+use tracing::error;
 
 pub(crate) struct L1BlockHandler<Vm, Da, Ps, DB>
 where
@@ -111,8 +111,12 @@ where
                     return;
                 }
                 _ = &mut l1_sync_worker => {},
-                Some(l1_block) = l1_rx.recv() => {
-                    self.queued_l1_blocks.push_back(l1_block);
+                Some(event) = l1_rx.recv() => match event {
+                    L1SyncEvent::Block(l1_block) => self.queued_l1_blocks.push_back(l1_block),
+                    L1SyncEvent::Reorg { fork_height, orphaned_hashes } => expect_l1_rollback(
+                        fork_height,
+                        self.handle_l1_reorg(fork_height, &orphaned_hashes),
+                    ),
                 },
                 _ = interval.tick() => {
                     if let Err(e) = self.process_queued_l1_blocks().await {
@@ -138,6 +142,21 @@ where
         Ok(())
     }
 
+    /// Forgets the queued L1 blocks and the light client proofs of the L1 blocks orphaned
+    /// by a reorg, so proving continues from the fork point.
+    fn handle_l1_reorg(
+        &mut self,
+        fork_height: u64,
+        orphaned_hashes: &[[u8; 32]],
+    ) -> anyhow::Result<()> {
+        self.queued_l1_blocks
+            .retain(|l1_block| l1_block.header().height() <= fork_height);
+
+        self.ledger_db
+            .rollback_l1_blocks(fork_height, orphaned_hashes)?;
+        Ok(())
+    }
+
     async fn process_l1_block(&self, l1_block: &Da::FilteredBlock) -> anyhow::Result<()> {
         let l1_hash = l1_block.header().hash().into();
         let l1_height = l1_block.header().height();
@@ -341,54 +360,3 @@ where
         Ok(proofs[0].clone())
     }
 }
-
-async fn sync_l1<Da>(
-    start_l1_height: u64,
-    da_service: Arc<Da>,
-    sender: mpsc::Sender<Da::FilteredBlock>,
-    l1_block_cache: Arc<Mutex<L1BlockCache<Da>>>,
-) where
-    Da: DaService,
-{
-    let mut l1_height = start_l1_height;
-    info!("Starting to sync from L1 height {}", l1_height);
-
-    'block_sync: loop {
-        // TODO: for a node, the da block at slot_height might not have been finalized yet
-        // should wait for it to be finalized
-        let last_finalized_l1_block_header =
-            match da_service.get_last_finalized_block_header().await {
-                Ok(header) => header,
-                Err(e) => {
-                    error!("Could not fetch last finalized L1 block header: {}", e);
-                    sleep(Duration::from_secs(2)).await;
-                    continue;
-                }
-            };
-
-        let new_l1_height = last_finalized_l1_block_header.height();
-
-        for block_number in l1_height + 1..=new_l1_height {
-            let l1_block =
-                match get_da_block_at_height(&da_service, block_number, l1_block_cache.clone())
-                    .await
-                {
-                    Ok(block) => block,
-                    Err(e) => {
-                        error!("Could not fetch last finalized L1 block: {}", e);
-                        sleep(Duration::from_secs(2)).await;
-                        continue 'block_sync;
-                    }
-                };
-            if block_number > l1_height {
-                l1_height = block_number;
-                if let Err(e) = sender.send(l1_block).await {
-                    error!("Could not notify about L1 block: {}", e);
-                    continue 'block_sync;
-                }
-            }
-        }
-
-        sleep(Duration::from_secs(2)).await;
-    }
-}
@@ -1,15 +1,174 @@
-use std::io::Write;
+//! Compression of blobs written to the DA layer.
+//!
+//! Blobs are wrapped in a versioned envelope: the [`ENVELOPE_V1`] byte, the [`CompressionCodec`]
+//! byte and the compressed payload. Blobs written before envelopes were introduced are bare
+//! brotli streams. Those always start with a byte whose low nibble is `0xB`, because they were
+//! written with a 22 bit window, so [`decompress_blob`] can tell both formats apart.
+
+/// First byte of a version 1 compression envelope.
+pub const ENVELOPE_V1: u8 = 0xC1;
+
+/// Zstd dictionary used by [`CompressionCodec::ZstdStateDiff`].
+///
+/// Trained with `zstd --train --maxdict=16384 --dictID=1` on borsh encoded state diffs.
+/// Blobs reference it by its id, so it must never change. Train a new dictionary under a new
+/// codec instead.
+pub const STATE_DIFF_DICTIONARY: &[u8] = include_bytes!("compression/state_diff.dict");
+
+/// Brotli quality used for compression.
+#[cfg(feature = "native")]
+const BROTLI_QUALITY: u32 = 11;
+/// Brotli window size in bits, see the module docs before changing it.
+#[cfg(feature = "native")]
+const BROTLI_WINDOW: u32 = 22;
+/// Zstd level used for compression.
+#[cfg(feature = "native")]
+const ZSTD_LEVEL: i32 = 19;
+
+/// Codec of a compression envelope. The discriminant is written to the envelope.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CompressionCodec {
+    /// The payload is not compressed.
+    None = 0,
+    /// Brotli with quality 11 and a 22 bit window.
+    Brotli = 1,
+    /// Zstd with [`STATE_DIFF_DICTIONARY`].
+    ZstdStateDiff = 2,
+}
+
+impl TryFrom<u8> for CompressionCodec {
+    type Error = DecompressionError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::Brotli),
+            2 => Ok(Self::ZstdStateDiff),
+            codec => Err(DecompressionError::UnknownCodec(codec)),
+        }
+    }
+}
+
+/// How blobs are compressed, chosen per fork by [`crate::forks::blob_compression`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlobCompression {
+    /// Bare brotli stream without an envelope, as written before envelopes were introduced.
+    Legacy,
+    /// Version 1 envelope with a payload compressed by the codec.
+    Envelope(CompressionCodec),
+}
+
+/// Errors returned when a blob can not be decompressed.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DecompressionError {
+    /// The blob is empty.
+    Empty,
+    /// The envelope names an unknown codec.
+    UnknownCodec(u8),
+    /// The payload is not valid for its codec.
+    Malformed(CompressionCodec, String),
+}
+
+impl core::fmt::Display for DecompressionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Blob is empty"),
+            Self::UnknownCodec(codec) => write!(f, "Unknown compression codec {}", codec),
+            Self::Malformed(codec, reason) => {
+                write!(f, "Malformed {:?} payload: {}", codec, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecompressionError {}
+
+/// Compresses `blob` as described by `compression`.
+#[cfg(feature = "native")]
+pub fn compress_blob(blob: &[u8], compression: BlobCompression) -> Vec<u8> {
+    let codec = match compression {
+        BlobCompression::Legacy => return compress_brotli(blob),
+        BlobCompression::Envelope(codec) => codec,
+    };
+
+    let mut compressed = vec![ENVELOPE_V1, codec as u8];
+    match codec {
+        CompressionCodec::None => compressed.extend_from_slice(blob),
+        CompressionCodec::Brotli => compressed.extend(compress_brotli(blob)),
+        CompressionCodec::ZstdStateDiff => {
+            let mut compressor =
+                zstd::bulk::Compressor::with_dictionary(ZSTD_LEVEL, STATE_DIFF_DICTIONARY)
+                    .expect("State diff dictionary must be valid");
+            compressed.extend(
+                compressor
+                    .compress(blob)
+                    .expect("Zstd compression to memory can not fail"),
+            );
+        }
+    }
+    compressed
+}
+
+#[cfg(feature = "native")]
+fn compress_brotli(blob: &[u8]) -> Vec<u8> {
+    use std::io::Write;
 
-pub fn compress_blob(blob: &[u8]) -> Vec<u8> {
     use brotli::CompressorWriter;
-    let mut writer = CompressorWriter::new(Vec::new(), 4096, 11, 22);
+    let mut writer = CompressorWriter::new(Vec::new(), 4096, BROTLI_QUALITY, BROTLI_WINDOW);
     writer.write_all(blob).unwrap();
     writer.into_inner()
 }
 
-pub fn decompress_blob(blob: &[u8]) -> Vec<u8> {
+/// Decompresses a blob written by [`compress_blob`] with any [`BlobCompression`].
+pub fn decompress_blob(blob: &[u8]) -> Result<Vec<u8>, DecompressionError> {
+    match blob {
+        [] => Err(DecompressionError::Empty),
+        [ENVELOPE_V1, codec, payload @ ..] => {
+            let codec = CompressionCodec::try_from(*codec)?;
+            match codec {
+                CompressionCodec::None => Ok(payload.to_vec()),
+                CompressionCodec::Brotli => decompress_brotli(payload),
+                CompressionCodec::ZstdStateDiff => decompress_zstd(payload),
+            }
+            .map_err(|reason| DecompressionError::Malformed(codec, reason))
+        }
+        _ => decompress_brotli(blob)
+            .map_err(|reason| DecompressionError::Malformed(CompressionCodec::Brotli, reason)),
+    }
+}
+
+/// Decompresses a bare brotli stream, the only format read from L1 blocks before
+/// [`crate::forks::FORK2_DA_ACTIVATION_HEIGHT`].
+///
+/// Panics if the stream is malformed, as readers did before then.
+pub fn decompress_legacy_blob(blob: &[u8]) -> Vec<u8> {
+    decompress_brotli(blob).expect("decompression failed")
+}
+
+fn decompress_brotli(payload: &[u8]) -> Result<Vec<u8>, String> {
+    use std::io::Write;
+
     use brotli::DecompressorWriter;
     let mut writer = DecompressorWriter::new(Vec::new(), 4096);
-    writer.write_all(blob).unwrap();
-    writer.into_inner().expect("decompression failed")
+    writer.write_all(payload).map_err(|e| e.to_string())?;
+    writer
+        .into_inner()
+        .map_err(|_| "Incomplete brotli stream".to_string())
+}
+
+fn decompress_zstd(payload: &[u8]) -> Result<Vec<u8>, String> {
+    use std::io::Read;
+
+    let mut frame_decoder = ruzstd::FrameDecoder::new();
+    frame_decoder
+        .add_dict(STATE_DIFF_DICTIONARY)
+        .map_err(|e| e.to_string())?;
+    let mut decoder = ruzstd::StreamingDecoder::new_with_decoder(payload, frame_decoder)
+        .map_err(|e| e.to_string())?;
+    let mut decompressed = Vec::new();
+    decoder
+        .read_to_end(&mut decompressed)
+        .map_err(|e| e.to_string())?;
+    Ok(decompressed)
 }
@@ -1,6 +1,8 @@
 use sov_rollup_interface::fork::Fork;
 use sov_rollup_interface::spec::SpecId;
 
+use crate::compression::{BlobCompression, CompressionCodec};
+
 /// This defines the list of forks which will be activated
 /// at specific heights.
 #[cfg(not(feature = "testing"))]
@@ -13,8 +15,10 @@ pub const FORKS: &[Fork] = &[
         spec_id: SpecId::Fork1,
         activation_height: 99999999999, // TODO: change this to the correct height once decided
     },
-    // Examples of how we can define further forks
-    // Fork { spec_id: SpecId::Fork2, activation_height: 1000 },
+    Fork {
+        spec_id: SpecId::Fork2,
+        activation_height: 199999999999, // TODO: change this to the correct height once decided
+    },
 ];
 
 #[cfg(feature = "testing")]
@@ -33,6 +37,16 @@ pub const FORKS: &[Fork] = &[
     },
 ];
 
+/// L1 height from which DA transactions are read with the rules of [`SpecId::Fork2`].
+///
+/// DA transactions are verified against their L1 block alone, so these rules activate at an
+/// L1 height. It must be reached before Fork2 activates on L2, which starts producing them.
+#[cfg(not(feature = "testing"))]
+pub const FORK2_DA_ACTIVATION_HEIGHT: u64 = 199999999999; // TODO: change this to the correct height once decided
+
+#[cfg(feature = "testing")]
+pub const FORK2_DA_ACTIVATION_HEIGHT: u64 = 0;
+
 const _CHECK_FORKS_ARE_SORTED: () = {
     const fn check_forks_are_sorted() {
         let mut height = FORKS[0].activation_height;
@@ -47,3 +61,14 @@ const _CHECK_FORKS_ARE_SORTED: () = {
     }
     check_forks_are_sorted()
 };
+
+/// Compression of DA blobs produced while the fork with `spec_id` is active.
+///
+/// Blobs carry their codec, so nodes can read blobs of every fork regardless of this choice.
+/// Envelopes are only read from L1 blocks from [`FORK2_DA_ACTIVATION_HEIGHT`] on.
+pub const fn blob_compression(spec_id: SpecId) -> BlobCompression {
+    match spec_id {
+        SpecId::Genesis | SpecId::Fork1 => BlobCompression::Legacy,
+        _ => BlobCompression::Envelope(CompressionCodec::ZstdStateDiff),
+    }
+}
@@ -2,10 +2,13 @@ use std::ops::DerefMut;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use citrea_common::da::{store_publication_receipt, PublishedData};
 use futures::future;
+use sha2::{Digest, Sha256};
 use sov_db::ledger_db::LedgerDB;
 use sov_rollup_interface::da::DaData;
 use sov_rollup_interface::services::da::DaService;
+use sov_rollup_interface::spec::SpecId;
 use sov_rollup_interface::stf::StateTransitionFunction;
 use sov_rollup_interface::zk::{Proof, ZkvmHost};
 use sov_stf_runner::ProverService;
@@ -33,7 +36,7 @@ where
     da_service: Arc<Da>,
     vm: Vm,
     zk_storage: Stf::PreState,
-    _ledger_db: LedgerDB,
+    ledger_db: LedgerDB,
 
     proof_queue: Arc<Mutex<Vec<ProofData>>>,
 }
@@ -52,7 +55,7 @@ where
         proof_mode: ProofGenMode<Da, Vm, Stf>,
         zk_storage: Stf::PreState,
         thread_pool_size: usize,
-        _ledger_db: LedgerDB,
+        ledger_db: LedgerDB,
     ) -> anyhow::Result<Self> {
         assert!(
             thread_pool_size > 0,
@@ -85,7 +88,7 @@ where
             da_service,
             vm,
             zk_storage,
-            _ledger_db,
+            ledger_db,
             proof_queue: Arc::new(Mutex::new(vec![])),
         })
     }
@@ -97,7 +100,7 @@ where
         vm: Vm,
         proof_mode: ProofGenMode<Da, Vm, Stf>,
         zk_storage: Stf::PreState,
-        _ledger_db: LedgerDB,
+        ledger_db: LedgerDB,
     ) -> anyhow::Result<Self> {
         let thread_pool_size = std::env::var("PARALLEL_PROOF_LIMIT")
             .expect("PARALLEL_PROOF_LIMIT must be set")
@@ -110,7 +113,7 @@ where
             proof_mode,
             zk_storage,
             thread_pool_size,
-            _ledger_db,
+            ledger_db,
         )
     }
 
@@ -177,12 +180,26 @@ where
         rx.await.expect("Should not have channel errors")
     }
 
-    async fn submit_proof(&self, proof: Proof) -> anyhow::Result<<Da as DaService>::TransactionId> {
-        let da_data = DaData::ZKProof(proof);
-        self.da_service
+    async fn submit_proof(
+        &self,
+        proof: Proof,
+        spec_id: SpecId,
+    ) -> anyhow::Result<<Da as DaService>::TransactionId> {
+        let proof_hash: [u8; 32] = Sha256::digest(&proof).into();
+        let da_data = DaData::ZKProof(proof, spec_id);
+        let tx_id = self
+            .da_service
             .send_transaction(da_data)
             .await
-            .map_err(|e| anyhow::anyhow!(e))
+            .map_err(|e| anyhow::anyhow!(e))?;
+
+        tokio::spawn(store_publication_receipt(
+            self.da_service.clone(),
+            self.ledger_db.clone(),
+            tx_id.clone().into(),
+            PublishedData::BatchProof(proof_hash),
+        ));
+        Ok(tx_id)
     }
 }
 
@@ -224,10 +241,11 @@ where
     async fn submit_proofs(
         &self,
         proofs: Vec<Proof>,
+        spec_id: SpecId,
     ) -> anyhow::Result<Vec<(<Da as DaService>::TransactionId, Proof)>> {
         let mut tx_and_proof = Vec::with_capacity(proofs.len());
         for proof in proofs {
-            let tx_id = self.submit_proof(proof.clone()).await?;
+            let tx_id = self.submit_proof(proof.clone(), spec_id).await?;
             tx_and_proof.push((tx_id, proof));
         }
         Ok(tx_and_proof)
@@ -235,11 +253,12 @@ where
 
     async fn recover_and_submit_proving_sessions(
         &self,
+        spec_id: SpecId,
     ) -> anyhow::Result<Vec<(<Da as DaService>::TransactionId, Proof)>> {
         let vm = self.vm.clone();
         let proofs = vm.recover_proving_sessions()?;
 
-        self.submit_proofs(proofs).await
+        self.submit_proofs(proofs, spec_id).await
     }
 }
 
@@ -1,3 +1,33 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sov_db::ledger_db::SharedLedgerOps;
+use sov_db::schema::tables::NATIVE_TABLES;
+use sov_db::schema::types::BatchNumber;
+use sov_rollup_interface::rpc::LedgerRpcProvider;
+use tracing::error;
+
+/// Selects the criteria deciding up to which block the pruner removes data.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PruningCriteria {
+    /// Keeps the latest `distance` blocks of [`crate::PruningConfig`].
+    Distance,
+    /// Keeps blocks which are newer than `max_age_secs` seconds.
+    Age { max_age_secs: u64 },
+    /// Keeps blocks which are not yet covered by a verified batch proof.
+    VerifiedBatchProof,
+    /// Once the ledger and accessory databases grow beyond `max_size_bytes`,
+    /// prunes everything but the latest `distance` blocks of [`crate::PruningConfig`].
+    DbSize { max_size_bytes: u64 },
+    /// Prunes only when all of the criteria agree, up to the lowest of their blocks.
+    And { criteria: Vec<PruningCriteria> },
+    /// Prunes when any of the criteria agrees, up to the highest of their blocks.
+    Or { criteria: Vec<PruningCriteria> },
+}
+
 /// This defines the interface of a pruning criteria.
 pub(crate) trait Criteria {
     /// Decides whether pruning should be done or not.
@@ -7,6 +37,47 @@ pub(crate) trait Criteria {
     fn should_prune(&self, last_pruned_block: u64, current_block_number: u64) -> Option<u64>;
 }
 
+/// Builds the criteria described by `criteria`.
+pub(crate) fn build_criteria<DB>(
+    criteria: &PruningCriteria,
+    distance: u64,
+    ledger_db: &DB,
+    native_db: &Arc<sov_schema_db::DB>,
+) -> Box<dyn Criteria + Send + Sync>
+where
+    DB: SharedLedgerOps + LedgerRpcProvider + Send + Sync + Clone + 'static,
+{
+    match criteria {
+        PruningCriteria::Distance => Box::new(DistanceCriteria { distance }),
+        PruningCriteria::Age { max_age_secs } => Box::new(AgeCriteria {
+            max_age_secs: *max_age_secs,
+            ledger_db: ledger_db.clone(),
+            last_boundary: AtomicU64::new(0),
+        }),
+        PruningCriteria::VerifiedBatchProof => Box::new(VerifiedBatchProofCriteria {
+            ledger_db: ledger_db.clone(),
+        }),
+        PruningCriteria::DbSize { max_size_bytes } => Box::new(DbSizeCriteria {
+            max_size_bytes: *max_size_bytes,
+            distance,
+            ledger_db: ledger_db.clone(),
+            native_db: native_db.clone(),
+        }),
+        PruningCriteria::And { criteria } => Box::new(AndCriteria {
+            criteria: criteria
+                .iter()
+                .map(|c| build_criteria(c, distance, ledger_db, native_db))
+                .collect(),
+        }),
+        PruningCriteria::Or { criteria } => Box::new(OrCriteria {
+            criteria: criteria
+                .iter()
+                .map(|c| build_criteria(c, distance, ledger_db, native_db))
+                .collect(),
+        }),
+    }
+}
+
 /// This distance criteria prunes blocks up to `last_pruned_block + distance`.
 /// However, to keep `distance` amount of blocks, we have to wait for at least twice
 /// the `distance` value to prune up to that point.
@@ -23,3 +94,179 @@ impl Criteria for DistanceCriteria {
         None
     }
 }
+
+/// This age criteria prunes blocks whose timestamp is older than `max_age_secs`
+/// seconds from now.
+pub(crate) struct AgeCriteria<DB> {
+    pub(crate) max_age_secs: u64,
+    pub(crate) ledger_db: DB,
+    /// Highest block found older than a previous cutoff, 0 if none yet.
+    /// The cutoff only moves forward, so the search resumes after it.
+    pub(crate) last_boundary: AtomicU64,
+}
+
+impl<DB: SharedLedgerOps> AgeCriteria<DB> {
+    fn block_timestamp(&self, block_number: u64) -> anyhow::Result<u64> {
+        SharedLedgerOps::get_soft_confirmation_by_number(
+            &self.ledger_db,
+            &BatchNumber(block_number),
+        )?
+        .map(|soft_confirmation| soft_confirmation.timestamp)
+        .ok_or_else(|| anyhow::anyhow!("Soft confirmation {} not found", block_number))
+    }
+
+    /// Finds the highest block in `(after_block, current_block_number]` which
+    /// is not newer than `cutoff`. Timestamps are monotonic, so a binary search suffices.
+    fn last_block_before(
+        &self,
+        cutoff: u64,
+        after_block: u64,
+        current_block_number: u64,
+    ) -> anyhow::Result<Option<u64>> {
+        let mut low = after_block + 1;
+        // Most calls find that not even the next block is old enough yet
+        if low > current_block_number || self.block_timestamp(low)? > cutoff {
+            return Ok(None);
+        }
+        let mut found = Some(low);
+        low += 1;
+        let mut high = current_block_number;
+        while low <= high {
+            let mid = low + (high - low) / 2;
+            if self.block_timestamp(mid)? <= cutoff {
+                found = Some(mid);
+                low = mid + 1;
+            } else {
+                high = mid - 1;
+            }
+        }
+        Ok(found)
+    }
+}
+
+impl<DB: SharedLedgerOps> Criteria for AgeCriteria<DB> {
+    fn should_prune(&self, last_pruned_block: u64, current_block_number: u64) -> Option<u64> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs();
+        let cutoff = now.checked_sub(self.max_age_secs)?;
+
+        let last_boundary = self.last_boundary.load(Ordering::Relaxed);
+        match self.last_block_before(
+            cutoff,
+            last_boundary.max(last_pruned_block),
+            current_block_number,
+        ) {
+            Ok(Some(up_to_block)) => {
+                self.last_boundary.store(up_to_block, Ordering::Relaxed);
+                Some(up_to_block)
+            }
+            Ok(None) => Some(last_boundary.min(current_block_number))
+                .filter(|block| *block > last_pruned_block),
+            Err(e) => {
+                error!("Failed to check block ages for pruning: {:?}", e);
+                None
+            }
+        }
+    }
+}
+
+/// This criteria only prunes blocks which are covered by the last verified batch proof,
+/// so data a prover or challenger may still need is kept.
+pub(crate) struct VerifiedBatchProofCriteria<DB> {
+    pub(crate) ledger_db: DB,
+}
+
+impl<DB: LedgerRpcProvider> VerifiedBatchProofCriteria<DB> {
+    fn last_verified_block(&self) -> anyhow::Result<Option<u64>> {
+        Ok(self
+            .ledger_db
+            .get_last_verified_batch_proof()?
+            .map(|last_proof| last_proof.proof.proof_output.last_l2_height))
+    }
+}
+
+impl<DB: LedgerRpcProvider> Criteria for VerifiedBatchProofCriteria<DB> {
+    fn should_prune(&self, last_pruned_block: u64, current_block_number: u64) -> Option<u64> {
+        match self.last_verified_block() {
+            Ok(last_verified_block) => last_verified_block
+                .map(|block| block.min(current_block_number))
+                .filter(|block| *block > last_pruned_block),
+            Err(e) => {
+                error!(
+                    "Failed to get last verified batch proof for pruning: {:?}",
+                    e
+                );
+                None
+            }
+        }
+    }
+}
+
+/// This criteria prunes everything but the latest `distance` blocks once the
+/// ledger and accessory databases exceed `max_size_bytes`.
+pub(crate) struct DbSizeCriteria<DB> {
+    pub(crate) max_size_bytes: u64,
+    pub(crate) distance: u64,
+    pub(crate) ledger_db: DB,
+    pub(crate) native_db: Arc<sov_schema_db::DB>,
+}
+
+impl<DB: SharedLedgerOps> DbSizeCriteria<DB> {
+    fn db_size(&self) -> anyhow::Result<u64> {
+        let native_db_size = NATIVE_TABLES
+            .iter()
+            .map(|table| {
+                self.native_db
+                    .get_property(table, "rocksdb.total-sst-files-size")
+            })
+            .sum::<anyhow::Result<u64>>()?;
+        Ok(self.ledger_db.get_db_size()? + native_db_size)
+    }
+}
+
+impl<DB: SharedLedgerOps> Criteria for DbSizeCriteria<DB> {
+    fn should_prune(&self, last_pruned_block: u64, current_block_number: u64) -> Option<u64> {
+        match self.db_size() {
+            Ok(size) if size > self.max_size_bytes => current_block_number
+                .checked_sub(self.distance)
+                .filter(|block| *block > last_pruned_block),
+            Ok(_) => None,
+            Err(e) => {
+                error!("Failed to get DB size for pruning: {:?}", e);
+                None
+            }
+        }
+    }
+}
+
+/// Prunes only if all criteria agree, up to the lowest block any of them allows.
+pub(crate) struct AndCriteria {
+    pub(crate) criteria: Vec<Box<dyn Criteria + Send + Sync>>,
+}
+
+impl Criteria for AndCriteria {
+    fn should_prune(&self, last_pruned_block: u64, current_block_number: u64) -> Option<u64> {
+        let mut up_to_block: Option<u64> = None;
+        for criteria in &self.criteria {
+            let block = criteria.should_prune(last_pruned_block, current_block_number)?;
+            up_to_block = Some(up_to_block.map_or(block, |b| b.min(block)));
+        }
+        up_to_block
+    }
+}
+
+/// Prunes if any of the criteria agrees, up to the highest block one of them allows.
+pub(crate) struct OrCriteria {
+    pub(crate) criteria: Vec<Box<dyn Criteria + Send + Sync>>,
+}
+
+impl Criteria for OrCriteria {
+    fn should_prune(&self, last_pruned_block: u64, current_block_number: u64) -> Option<u64> {
+        self.criteria
+            .iter()
+            .filter_map(|criteria| criteria.should_prune(last_pruned_block, current_block_number))
+            .max()
+    }
+}
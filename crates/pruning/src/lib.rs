@@ -1,13 +1,18 @@
-use criteria::DistanceCriteria;
+use std::sync::Arc;
+
+use anyhow::Context;
 use futures::future;
 use serde::{Deserialize, Serialize};
 use sov_db::ledger_db::SharedLedgerOps;
+use sov_prover_storage_manager::{ProverStorage, SnapshotManager};
+use sov_rollup_interface::rpc::LedgerRpcProvider;
 use tokio::select;
 use tokio::sync::broadcast;
 use tokio_util::sync::CancellationToken;
 use tracing::{error, info};
 
-use crate::criteria::Criteria;
+pub use crate::criteria::PruningCriteria;
+use crate::criteria::{build_criteria, Criteria};
 use crate::pruners::{prune_evm, prune_ledger};
 
 mod criteria;
@@ -20,11 +25,18 @@ mod tests;
 pub struct PruningConfig {
     /// Defines the number of blocks from the tip of the chain to remove.
     pub distance: u64,
+    /// Defines the criteria to decide up to which block to prune.
+    /// Pruning is done by `distance` if not set.
+    #[serde(default)]
+    pub criteria: Option<PruningCriteria>,
 }
 
 impl Default for PruningConfig {
     fn default() -> Self {
-        Self { distance: 256 }
+        Self {
+            distance: 256,
+            criteria: None,
+        }
     }
 }
 
@@ -38,41 +50,65 @@ where
     l2_receiver: broadcast::Receiver<u64>,
     /// Access to ledger tables.
     ledger_db: DB,
+    /// Finalized storage to read EVM accessory state from.
+    storage: ProverStorage<SnapshotManager>,
+    /// Handle to the native DB to remove EVM accessory state from.
+    native_db: Arc<sov_schema_db::DB>,
     /// Criteria to decide pruning
     criteria: Box<dyn Criteria + Send + Sync>,
 }
 
 impl<DB> Pruner<DB>
 where
-    DB: SharedLedgerOps + Send + Sync + Clone + 'static,
+    DB: SharedLedgerOps + LedgerRpcProvider + Send + Sync + Clone + 'static,
 {
     pub fn new(
         config: PruningConfig,
         last_pruned_block: u64,
         l2_receiver: broadcast::Receiver<u64>,
         ledger_db: DB,
+        storage: ProverStorage<SnapshotManager>,
+        native_db: Arc<sov_schema_db::DB>,
     ) -> Self {
-        // distance is the only criteria implemented at the moment.
-        let criteria = Box::new(DistanceCriteria {
-            distance: config.distance,
-        });
+        let criteria = build_criteria(
+            config
+                .criteria
+                .as_ref()
+                .unwrap_or(&PruningCriteria::Distance),
+            config.distance,
+            &ledger_db,
+            &native_db,
+        );
         Self {
             last_pruned_block,
             l2_receiver,
             ledger_db,
+            storage,
+            native_db,
             criteria,
         }
     }
 
     /// Prune everything
-    pub async fn prune(&self, up_to_block: u64) {
+    pub async fn prune(&self, up_to_block: u64) -> anyhow::Result<()> {
         info!("Pruning up to L2 block: {}", up_to_block);
         let ledger_db = self.ledger_db.clone();
-        let ledger_pruning_handle =
-            tokio::task::spawn_blocking(move || prune_ledger(ledger_db, up_to_block));
-        let evm_pruning_handle = tokio::task::spawn_blocking(move || prune_evm(up_to_block));
+        let last_pruned_block = self.last_pruned_block;
+        let ledger_pruning_handle = tokio::task::spawn_blocking(move || {
+            prune_ledger(ledger_db, last_pruned_block, up_to_block)
+        });
+        let storage = self.storage.clone();
+        let native_db = self.native_db.clone();
+        let evm_pruning_handle = tokio::task::spawn_blocking(move || {
+            prune_evm(storage, native_db, last_pruned_block, up_to_block)
+        });
+
+        let (ledger_pruning_result, evm_pruning_result) =
+            future::join(ledger_pruning_handle, evm_pruning_handle).await;
 
-        future::join_all([ledger_pruning_handle, evm_pruning_handle]).await;
+        let ledger_pruning_result = ledger_pruning_result?;
+        evm_pruning_result?.context("Failed to prune EVM history")?;
+        ledger_pruning_result
     }
 
     pub async fn run(mut self, cancellation_token: CancellationToken) {
@@ -89,8 +125,17 @@ where
                 current_l2_block = self.l2_receiver.recv() => {
                     if let Ok(current_l2_block) = current_l2_block {
                         if let Some(up_to_block) = self.criteria.should_prune(self.last_pruned_block, current_l2_block) {
-                            self.prune(up_to_block).await;
-                            self.last_pruned_block = up_to_block;
+                            match self.prune(up_to_block).await {
+                                Ok(()) => self.last_pruned_block = up_to_block,
+                                Err(e) => {
+                                    error!("Failed to prune up to L2 block {}: {:?}", up_to_block, e);
+                                    // Ledger pruning records its progress with every batch it writes,
+                                    // move it back so both pruners retry from where they both got.
+                                    if let Err(e) = self.ledger_db.set_last_pruned_l2_height(self.last_pruned_block) {
+                                        error!("Failed to store last pruned L2 height {}: {:?}", self.last_pruned_block, e);
+                                    }
+                                }
+                            }
                         }
                     }
                 },
@@ -1,10 +1,48 @@
+use std::sync::Arc;
+
 use citrea_evm::Evm;
+use sov_db::native_db::prune_accessory_keys;
 use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::WorkingSet;
+use sov_prover_storage_manager::{ProverStorage, SnapshotManager};
 use tracing::debug;
 
+/// Number of L2 blocks whose EVM history is removed per write.
+const EVM_PRUNING_BATCH_SIZE: u64 = 1000;
+
 /// Prune evm
-pub(crate) fn prune_evm(up_to_block: u64) {
+///
+/// Removes blocks, transactions and receipts of L2 blocks
+/// in `(last_pruned_block, up_to_block]` from the accessory state.
+pub(crate) fn prune_evm(
+    storage: ProverStorage<SnapshotManager>,
+    native_db: Arc<sov_schema_db::DB>,
+    last_pruned_block: u64,
+    up_to_block: u64,
+) -> anyhow::Result<()> {
     debug!("Pruning EVM, up to L2 block {}", up_to_block);
-    let _evm = Evm::<DefaultContext>::default();
-    // unimplemented!()
+    let evm = Evm::<DefaultContext>::default();
+
+    let mut start = last_pruned_block + 1;
+    while start <= up_to_block {
+        let end = std::cmp::min(start + EVM_PRUNING_BATCH_SIZE - 1, up_to_block);
+
+        let mut working_set = WorkingSet::new(storage.clone());
+        evm.remove_history(start..=end, &mut working_set);
+
+        // Deletions are only collected by the working set, the accessory keys
+        // they touched are removed with all of their versions from the native DB.
+        let keys = working_set
+            .checkpoint()
+            .freeze_non_provable()
+            .ordered_writes
+            .into_iter()
+            .map(|(key, _)| key.key.to_vec());
+        prune_accessory_keys(&native_db, keys)?;
+
+        debug!("Pruned EVM history of L2 blocks {} to {}", start, end);
+        start = end + 1;
+    }
+
+    Ok(())
 }
@@ -1,8 +1,29 @@
 use sov_db::ledger_db::SharedLedgerOps;
+use sov_db::schema::types::BatchNumber;
 use tracing::debug;
 
+/// The number of L2 blocks pruned in a single DB write.
+const LEDGER_PRUNING_BATCH_SIZE: u64 = 1000;
+
 /// Prune ledger
-pub(crate) fn prune_ledger<DB: SharedLedgerOps>(_ledger_db: DB, up_to_block: u64) {
+///
+/// Soft confirmations are removed in batches, each of which also records the last
+/// pruned L2 height. Therefore, an interrupted run leaves the ledger in a consistent
+/// state and the next run continues from where it left off.
+pub(crate) fn prune_ledger<DB: SharedLedgerOps>(
+    ledger_db: DB,
+    last_pruned_block: u64,
+    up_to_block: u64,
+) -> anyhow::Result<()> {
     debug!("Pruning Ledger, up to L2 block {}", up_to_block);
-    // unimplemented!()
+
+    let mut start = last_pruned_block + 1;
+    while start <= up_to_block {
+        let end = up_to_block.min(start + LEDGER_PRUNING_BATCH_SIZE - 1);
+        ledger_db.prune_soft_confirmations(&(BatchNumber(start)..=BatchNumber(end)))?;
+        debug!("Pruned Ledger from L2 block {} to {}", start, end);
+        start = end + 1;
+    }
+
+    Ok(())
 }
@@ -1,12 +1,21 @@
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::thread::sleep;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use sov_db::ledger_db::LedgerDB;
+use sov_db::ledger_db::{LedgerDB, NodeLedgerOps, SharedLedgerOps};
 use sov_db::rocks_db_config::RocksdbConfig;
+use sov_db::schema::tables::NATIVE_TABLES;
+use sov_db::schema::types::StoredBatchProofOutput;
+use sov_mock_da::{MockDaSpec, MockHash};
+use sov_prover_storage_manager::ProverStorageManager;
+use sov_rollup_interface::stf::SoftConfirmationReceipt;
 use tokio::sync::broadcast;
 use tokio_util::sync::CancellationToken;
 
-use crate::criteria::{Criteria, DistanceCriteria};
+use crate::criteria::{
+    AgeCriteria, AndCriteria, Criteria, DbSizeCriteria, DistanceCriteria, OrCriteria,
+    VerifiedBatchProofCriteria,
+};
 use crate::{Pruner, PruningConfig};
 
 #[tokio::test(flavor = "multi_thread")]
@@ -16,7 +25,23 @@ async fn test_pruner_simple_run() {
     let cancellation_token = CancellationToken::new();
 
     let ledger_db = LedgerDB::with_config(&RocksdbConfig::new(tmpdir.path(), None, None)).unwrap();
-    let pruner = Pruner::new(PruningConfig { distance: 5 }, 0, receiver, ledger_db);
+    let storage_dir = tempfile::tempdir().unwrap();
+    let mut storage_manager = ProverStorageManager::<MockDaSpec>::new(sov_state::config::Config {
+        path: storage_dir.path().to_path_buf(),
+        db_max_open_files: None,
+    })
+    .unwrap();
+    let pruner = Pruner::new(
+        PruningConfig {
+            distance: 5,
+            criteria: None,
+        },
+        0,
+        receiver,
+        ledger_db,
+        storage_manager.create_finalized_storage().unwrap(),
+        storage_manager.get_native_db_handle(),
+    );
 
     tokio::spawn(pruner.run(cancellation_token.clone()));
 
@@ -42,3 +67,170 @@ pub fn test_should_prune() {
     assert_eq!(criteria.should_prune(1000, 3000), None);
     assert_eq!(criteria.should_prune(1000, 3001), Some(2000));
 }
+
+#[test]
+pub fn test_combined_criteria() {
+    let and_criteria = AndCriteria {
+        criteria: vec![
+            Box::new(DistanceCriteria { distance: 1000 }),
+            Box::new(DistanceCriteria { distance: 500 }),
+        ],
+    };
+    assert_eq!(and_criteria.should_prune(0, 1500), None);
+    assert_eq!(and_criteria.should_prune(0, 2001), Some(500));
+
+    let or_criteria = OrCriteria {
+        criteria: vec![
+            Box::new(DistanceCriteria { distance: 1000 }),
+            Box::new(DistanceCriteria { distance: 500 }),
+        ],
+    };
+    assert_eq!(or_criteria.should_prune(0, 1000), None);
+    assert_eq!(or_criteria.should_prune(0, 1001), Some(500));
+    assert_eq!(or_criteria.should_prune(0, 2001), Some(1000));
+}
+
+/// Commits a soft confirmation per timestamp, block `n` has hash `[n; 32]`.
+fn commit_soft_confirmations(ledger_db: &LedgerDB, timestamps: &[u64]) {
+    for (index, timestamp) in timestamps.iter().enumerate() {
+        let l2_height = index as u64 + 1;
+        ledger_db
+            .commit_soft_confirmation(
+                &[0u8; 32],
+                SoftConfirmationReceipt::<MockDaSpec> {
+                    l2_height,
+                    da_slot_height: 1,
+                    da_slot_hash: MockHash([1u8; 32]),
+                    da_slot_txs_commitment: MockHash([2u8; 32]),
+                    hash: [l2_height as u8; 32],
+                    prev_hash: [index as u8; 32],
+                    tx_hashes: vec![],
+                    soft_confirmation_signature: vec![],
+                    pub_key: vec![],
+                    deposit_data: vec![],
+                    l1_fee_rate: 0,
+                    timestamp: *timestamp,
+                },
+                None,
+            )
+            .unwrap();
+    }
+}
+
+#[test]
+pub fn test_age_criteria() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let ledger_db = LedgerDB::with_config(&RocksdbConfig::new(tmpdir.path(), None, None)).unwrap();
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    // blocks 1 to 5 are two hours old, 6 to 10 are fresh
+    let mut timestamps = vec![now - 7200; 5];
+    timestamps.extend([now; 5]);
+    commit_soft_confirmations(&ledger_db, &timestamps);
+
+    let criteria = AgeCriteria {
+        max_age_secs: 3600,
+        ledger_db,
+        last_boundary: AtomicU64::new(0),
+    };
+    assert_eq!(criteria.should_prune(0, 10), Some(5));
+    assert_eq!(criteria.last_boundary.load(Ordering::Relaxed), 5);
+    // nothing newer is old enough yet
+    assert_eq!(criteria.should_prune(5, 10), None);
+    // the boundary is kept while pruning is held back, e.g. by `AndCriteria`
+    assert_eq!(criteria.should_prune(0, 10), Some(5));
+    assert_eq!(criteria.should_prune(0, 3), Some(3));
+    // missing soft confirmations do not allow pruning
+    assert_eq!(criteria.should_prune(10, 12), None);
+
+    let criteria = AgeCriteria {
+        max_age_secs: 3 * 3600,
+        ledger_db: criteria.ledger_db,
+        last_boundary: AtomicU64::new(0),
+    };
+    assert_eq!(criteria.should_prune(0, 10), None);
+}
+
+#[test]
+pub fn test_verified_batch_proof_criteria() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let ledger_db = LedgerDB::with_config(&RocksdbConfig::new(tmpdir.path(), None, None)).unwrap();
+
+    let criteria = VerifiedBatchProofCriteria {
+        ledger_db: ledger_db.clone(),
+    };
+    assert_eq!(criteria.should_prune(0, 10), None);
+
+    let proof_output = |last_l2_height: u64| StoredBatchProofOutput {
+        initial_state_root: vec![],
+        final_state_root: vec![],
+        prev_soft_confirmation_hash: [0u8; 32],
+        final_soft_confirmation_hash: [last_l2_height as u8; 32],
+        state_diff: Default::default(),
+        da_slot_hash: [0u8; 32],
+        sequencer_commitments_range: (0, 0),
+        sequencer_public_key: vec![],
+        sequencer_da_public_key: vec![],
+        preproven_commitments: vec![],
+        last_l2_height,
+    };
+
+    // proof up to block 4
+    ledger_db
+        .update_verified_proof_data(1, vec![], proof_output(4))
+        .unwrap();
+    assert_eq!(criteria.should_prune(0, 10), Some(4));
+    assert_eq!(criteria.should_prune(0, 3), Some(3));
+    assert_eq!(criteria.should_prune(4, 10), None);
+
+    // the latest verified proof decides
+    ledger_db
+        .update_verified_proof_data(2, vec![], proof_output(8))
+        .unwrap();
+    assert_eq!(criteria.should_prune(4, 10), Some(8));
+}
+
+#[test]
+pub fn test_db_size_criteria() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let ledger_db = LedgerDB::with_config(&RocksdbConfig::new(tmpdir.path(), None, None)).unwrap();
+    let storage_dir = tempfile::tempdir().unwrap();
+    let storage_manager = ProverStorageManager::<MockDaSpec>::new(sov_state::config::Config {
+        path: storage_dir.path().to_path_buf(),
+        db_max_open_files: None,
+    })
+    .unwrap();
+    let native_db = storage_manager.get_native_db_handle();
+
+    // write some accessory state to disk, so the size is not zero
+    let table = NATIVE_TABLES[0];
+    native_db
+        .put_cf(
+            native_db.get_cf_handle(table).unwrap(),
+            b"key",
+            &[1u8; 1024],
+        )
+        .unwrap();
+    native_db.flush_cf(table).unwrap();
+
+    let criteria = DbSizeCriteria {
+        max_size_bytes: u64::MAX,
+        distance: 5,
+        ledger_db: ledger_db.clone(),
+        native_db: native_db.clone(),
+    };
+    assert_eq!(criteria.should_prune(0, 10), None);
+
+    let criteria = DbSizeCriteria {
+        max_size_bytes: 0,
+        distance: 5,
+        ledger_db,
+        native_db,
+    };
+    assert_eq!(criteria.should_prune(0, 10), Some(5));
+    assert_eq!(criteria.should_prune(5, 10), None);
+    assert_eq!(criteria.should_prune(0, 4), None);
+}
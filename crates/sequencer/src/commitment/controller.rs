@@ -2,19 +2,16 @@ use std::cmp;
 
 use citrea_common::utils::merge_state_diffs;
 use citrea_primitives::compression::compress_blob;
+use citrea_primitives::forks::{blob_compression, FORKS};
 use citrea_primitives::MAX_TXBODY_SIZE;
 use sov_db::ledger_db::SequencerLedgerOps;
 use sov_db::schema::types::BatchNumber;
+use sov_modules_api::fork::fork_from_block_number;
 use sov_modules_api::StateDiff;
 use tracing::{debug, warn};
 
 use super::CommitmentInfo;
 
-// Based on the test runs, brotli is able to compress the state diff 58% to 70%,
-// with an average of 66% for both empty and full blocks. This is a super safe
-// estimation of 33% compression.
-const SAFE_MAX_UNCOMPRESSED_TXBODY_SIZE: usize = MAX_TXBODY_SIZE * 3 / 2;
-
 pub struct CommitmentController<Db>
 where
     Db: SequencerLedgerOps,
@@ -137,12 +134,14 @@ where
 
         let uncompressed_state_diff =
             borsh::to_vec(state_diff).expect("State diff serialization can not fail");
-        // Early return if uncompressed state diff doesn't exceed limit
-        if uncompressed_state_diff.len() <= SAFE_MAX_UNCOMPRESSED_TXBODY_SIZE {
+        // Early return if even the uncompressed state diff fits
+        if uncompressed_state_diff.len() <= MAX_TXBODY_SIZE {
             return None;
         }
 
-        let compressed_state_diff = compress_blob(&uncompressed_state_diff);
+        let spec_id = fork_from_block_number(FORKS, current_l2_height).spec_id;
+        let compressed_state_diff =
+            compress_blob(&uncompressed_state_diff, blob_compression(spec_id));
         if compressed_state_diff.len() <= MAX_TXBODY_SIZE {
             return None;
         }
@@ -2,6 +2,7 @@ use std::ops::RangeInclusive;
 use std::sync::Arc;
 
 use anyhow::anyhow;
+use citrea_common::da::{store_publication_receipt, PublishedData};
 use futures::channel::mpsc::UnboundedReceiver;
 use futures::StreamExt;
 use parking_lot::RwLock;
@@ -132,6 +133,7 @@ where
 
         debug!("Sequencer: submitting commitment: {:?}", commitment);
 
+        let merkle_root = commitment.merkle_root;
         let da_data = DaData::SequencerCommitment(commitment);
         let (notify, rx) = oneshot::channel();
         let request = SenderWithNotifier { da_data, notify };
@@ -146,9 +148,10 @@ where
         );
 
         let ledger_db = self.ledger_db.clone();
+        let da_service = self.da_service.clone();
         let handle_da_response = async move {
             let result: anyhow::Result<()> = async move {
-                let _tx_id = rx
+                let tx_id = rx
                     .await
                     .map_err(|_| anyhow!("DA service is dead!"))?
                     .map_err(|_| anyhow!("Send transaction cannot fail"))?;
@@ -162,6 +165,13 @@ where
                 ledger_db.delete_pending_commitment_l2_range(&(l2_start, l2_end))?;
 
                 info!("New commitment. L2 range: #{}-{}", l2_start.0, l2_end.0);
+
+                tokio::spawn(store_publication_receipt(
+                    da_service,
+                    ledger_db,
+                    tx_id.into(),
+                    PublishedData::SequencerCommitment(merkle_root),
+                ));
                 Ok(())
             }
             .await;
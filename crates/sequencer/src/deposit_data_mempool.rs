@@ -8,13 +8,24 @@ use tracing::instrument;
 
 #[derive(Clone, Debug)]
 pub struct DepositDataMempool {
-    accepted_deposit_txs: VecDeque<Vec<u8>>,
+    /// Accepted deposits with their arrival index, which is also their key in the ledger db
+    accepted_deposit_txs: VecDeque<(u64, Vec<u8>)>,
+    next_index: u64,
 }
 
 impl DepositDataMempool {
     pub fn new() -> Self {
         Self {
             accepted_deposit_txs: VecDeque::new(),
+            next_index: 0,
+        }
+    }
+
+    /// Restores the deposits persisted in the ledger db, ordered by their arrival index.
+    pub fn restore_deposits(&mut self, deposits: Vec<(u64, Vec<u8>)>) {
+        for (index, deposit) in deposits {
+            self.next_index = self.next_index.max(index + 1);
+            self.accepted_deposit_txs.push_back((index, deposit));
         }
     }
 
@@ -32,15 +43,35 @@ impl DepositDataMempool {
     // (i.e. if you have 500 dep tx, due to gas, they may not be included, so it panics - we don't want that)
 
     // Considering the deposit amounts to be allowed, and the block count, a limit per block is convenient
-    pub fn fetch_deposits(&mut self, limit_per_block: usize) -> Vec<Vec<u8>> {
+    //
+    // Returns the deposits with their arrival index, so they can be removed from the ledger db
+    // once included in a soft confirmation.
+    pub fn fetch_deposits(&mut self, limit_per_block: usize) -> Vec<(u64, Vec<u8>)> {
         let number_of_deposits = self.accepted_deposit_txs.len().min(limit_per_block);
         self.accepted_deposit_txs
             .drain(..number_of_deposits)
             .collect()
     }
 
+    /// Arrival index the next accepted deposit gets.
+    pub fn next_index(&self) -> u64 {
+        self.next_index
+    }
+
+    /// Adds a deposit and returns its arrival index.
     #[instrument(level = "trace", skip_all, ret)]
-    pub fn add_deposit_tx(&mut self, req: Vec<u8>) {
-        self.accepted_deposit_txs.push_back(req);
+    pub fn add_deposit_tx(&mut self, req: Vec<u8>) -> u64 {
+        let index = self.next_index;
+        self.next_index += 1;
+        self.accepted_deposit_txs.push_back((index, req));
+        index
+    }
+
+    /// Deposits waiting to be included in a soft confirmation, in order.
+    pub fn pending_deposits(&self) -> Vec<Vec<u8>> {
+        self.accepted_deposit_txs
+            .iter()
+            .map(|(_, deposit)| deposit.clone())
+            .collect()
     }
 }
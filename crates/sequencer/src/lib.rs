@@ -3,10 +3,12 @@ pub mod db_migrations;
 mod db_provider;
 mod deposit_data_mempool;
 mod mempool;
+mod metrics;
+mod ordering;
 mod rpc;
 mod runner;
 mod utils;
 
 pub use citrea_common::{SequencerConfig, SequencerMempoolConfig};
-pub use rpc::SequencerRpcClient;
+pub use rpc::{L1DiffBudgetStatus, SequencerRpcClient};
 pub use runner::CitreaSequencer;
@@ -1,18 +1,22 @@
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use anyhow::{anyhow, bail};
 use citrea_common::SequencerMempoolConfig;
 use citrea_evm::SYSTEM_SIGNER;
 use reth_chainspec::{Chain, ChainSpecBuilder};
-use reth_primitives::{Genesis, TxHash};
+use reth_primitives::{Genesis, InvalidTransactionError, TxHash, TxType};
 use reth_tasks::TokioTaskExecutor;
 use reth_transaction_pool::blobstore::NoopBlobStore;
-use reth_transaction_pool::error::PoolError;
+use reth_transaction_pool::error::{InvalidPoolTransactionError, PoolError, PoolErrorKind};
 use reth_transaction_pool::{
-    BestTransactions, BestTransactionsAttributes, ChangedAccount, CoinbaseTipOrdering,
-    EthPooledTransaction, EthTransactionValidator, Pool, PoolConfig, PoolResult, SubPoolLimit,
-    TransactionPool, TransactionPoolExt, TransactionValidationTaskExecutor, ValidPoolTransaction,
+    AllPoolTransactions, BestTransactions, BestTransactionsAttributes, ChangedAccount,
+    CoinbaseTipOrdering, EthPooledTransaction, EthTransactionValidator, Pool, PoolConfig,
+    PoolResult, SubPoolLimit, TransactionPool, TransactionPoolExt,
+    TransactionValidationTaskExecutor, ValidPoolTransaction,
 };
+use sov_rollup_interface::fork::{Fork, ForkMigration};
+use sov_rollup_interface::spec::SpecId;
 
 pub use crate::db_provider::DbProvider;
 
@@ -24,12 +28,39 @@ type CitreaMempoolImpl<C> = Pool<
 
 type Transaction<C> = <CitreaMempoolImpl<C> as TransactionPool>::Transaction;
 
-pub(crate) struct CitreaMempool<C: sov_modules_api::Context>(CitreaMempoolImpl<C>);
+pub(crate) struct CitreaMempool<C: sov_modules_api::Context> {
+    pool: CitreaMempoolImpl<C>,
+    eip7702_activation: Eip7702Activation,
+}
+
+/// Tracks whether the active fork accepts EIP-7702 transactions,
+/// registered with the fork manager to flip on prague activation.
+#[derive(Clone)]
+pub(crate) struct Eip7702Activation(Arc<AtomicBool>);
+
+impl Eip7702Activation {
+    fn new(spec_id: SpecId) -> Self {
+        Self(Arc::new(AtomicBool::new(spec_id >= SpecId::Fork2)))
+    }
+
+    fn is_active(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+impl ForkMigration for Eip7702Activation {
+    fn fork_activated(&self, fork: &Fork) -> anyhow::Result<()> {
+        self.0
+            .store(fork.spec_id >= SpecId::Fork2, Ordering::Relaxed);
+        Ok(())
+    }
+}
 
 impl<C: sov_modules_api::Context> CitreaMempool<C> {
     pub(crate) fn new(
         client: DbProvider<C>,
         mempool_conf: SequencerMempoolConfig,
+        active_spec: SpecId,
     ) -> anyhow::Result<Self> {
         let blob_store = NoopBlobStore::default();
         let genesis_block = client
@@ -89,10 +120,21 @@ impl<C: sov_modules_api::Context> CitreaMempool<C> {
             // somewhere else
             .set_block_gas_limit(evm_config.block_gas_limit)
             .set_shanghai(true)
+            // eip7702 txs are rejected in `add_external_transaction` until prague is activated
+            .set_prague(true)
             .with_additional_tasks(0)
             .build_with_tasks(client, TokioTaskExecutor::default(), blob_store);
 
-        Ok(Self(Pool::eth_pool(validator, blob_store, pool_config)))
+        Ok(Self {
+            pool: Pool::eth_pool(validator, blob_store, pool_config),
+            eip7702_activation: Eip7702Activation::new(active_spec),
+        })
+    }
+
+    /// Handle to register with the fork manager, so that EIP-7702 transactions
+    /// are accepted once prague is activated.
+    pub(crate) fn eip7702_activation(&self) -> Eip7702Activation {
+        self.eip7702_activation.clone()
     }
 
     pub(crate) async fn add_external_transaction(
@@ -105,29 +147,43 @@ impl<C: sov_modules_api::Context> CitreaMempool<C> {
                 "system transactions from rpc are not allowed",
             ));
         }
-        self.0.add_external_transaction(transaction).await
+        if transaction.transaction().tx_type() == TxType::Eip7702
+            && !self.eip7702_activation.is_active()
+        {
+            return Err(PoolError::new(
+                transaction.transaction().hash(),
+                PoolErrorKind::InvalidTransaction(InvalidPoolTransactionError::Consensus(
+                    InvalidTransactionError::Eip7702Disabled,
+                )),
+            ));
+        }
+        self.pool.add_external_transaction(transaction).await
     }
 
     pub(crate) fn get(&self, hash: &TxHash) -> Option<Arc<ValidPoolTransaction<Transaction<C>>>> {
-        self.0.get(hash)
+        self.pool.get(hash)
+    }
+
+    pub(crate) fn all_transactions(&self) -> AllPoolTransactions<Transaction<C>> {
+        self.pool.all_transactions()
     }
 
     pub(crate) fn remove_transactions(
         &self,
         tx_hashes: Vec<TxHash>,
     ) -> Vec<Arc<ValidPoolTransaction<Transaction<C>>>> {
-        self.0.remove_transactions(tx_hashes)
+        self.pool.remove_transactions(tx_hashes)
     }
 
     pub(crate) fn update_accounts(&self, account_updates: Vec<ChangedAccount>) {
-        self.0.update_accounts(account_updates);
+        self.pool.update_accounts(account_updates);
     }
 
     pub(crate) fn best_transactions_with_attributes(
         &self,
         best_transactions_attributes: BestTransactionsAttributes,
     ) -> Box<dyn BestTransactions<Item = Arc<ValidPoolTransaction<Transaction<C>>>>> {
-        self.0
+        self.pool
             .best_transactions_with_attributes(best_transactions_attributes)
     }
 }
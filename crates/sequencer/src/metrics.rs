@@ -0,0 +1,34 @@
+use once_cell::sync::Lazy;
+use prometheus::{register_int_counter_vec, register_int_gauge, IntCounterVec, IntGauge};
+
+pub static SEQUENCER_L1_DIFF_BUDGET_BYTES: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        // metric name
+        "sequencer_l1_diff_budget_bytes",
+        // metric description
+        "Max total estimated state diff size of the transactions in an L2 block"
+    )
+    .unwrap()
+});
+
+pub static SEQUENCER_BLOCK_L1_DIFF_SIZE_BYTES: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        // metric name
+        "sequencer_block_l1_diff_size_bytes",
+        // metric description
+        "Total estimated state diff size of the transactions in the latest L2 block"
+    )
+    .unwrap()
+});
+
+pub static SEQUENCER_SKIPPED_TXS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        // metric name
+        "sequencer_skipped_txs",
+        // metric description
+        "Transactions left out of L2 blocks by the sequencer",
+        // labels
+        &["reason"]
+    )
+    .unwrap()
+});
@@ -0,0 +1,626 @@
+use std::cmp;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Instant;
+
+use citrea_common::TxOrderingPolicyConfig;
+use reth_primitives::{Address, TxHash};
+use reth_transaction_pool::{BestTransactions, EthPooledTransaction, ValidPoolTransaction};
+
+pub(crate) type PooledTx = Arc<ValidPoolTransaction<EthPooledTransaction>>;
+
+/// Decides the order in which mempool transactions are tried for an L2 block
+/// and which of them are left in the mempool for later blocks.
+pub(crate) trait TxOrderingPolicy: Send + Sync {
+    /// Orders the executable transactions of the mempool, which reth yields by priority fee,
+    /// for a block of `block_gas_limit`.
+    fn order(
+        &self,
+        best_transactions: Box<dyn BestTransactions<Item = PooledTx>>,
+        block_gas_limit: u64,
+    ) -> Box<dyn Iterator<Item = PooledTx> + Send>;
+
+    /// Checks whether a transaction with `gas_limit` can still be included,
+    /// given what its sender already got in the block.
+    fn check(&self, _gas_limit: u64, _sender_usage: SenderUsage) -> Result<(), SkipReason> {
+        Ok(())
+    }
+}
+
+/// Builds the policy described by `config`.
+pub(crate) fn create_ordering_policy(config: &TxOrderingPolicyConfig) -> Arc<dyn TxOrderingPolicy> {
+    match config {
+        TxOrderingPolicyConfig::PriorityFee => Arc::new(PriorityFee),
+        TxOrderingPolicyConfig::FirstComeFirstServed => Arc::new(FirstComeFirstServed),
+        TxOrderingPolicyConfig::FairShare {
+            max_txs_per_sender,
+            max_gas_per_sender,
+        } => Arc::new(FairShare {
+            max_txs_per_sender: *max_txs_per_sender,
+            max_gas_per_sender: *max_gas_per_sender,
+        }),
+    }
+}
+
+/// Highest priority fee first, the order reth's pool already yields.
+struct PriorityFee;
+
+impl TxOrderingPolicy for PriorityFee {
+    fn order(
+        &self,
+        best_transactions: Box<dyn BestTransactions<Item = PooledTx>>,
+        _block_gas_limit: u64,
+    ) -> Box<dyn Iterator<Item = PooledTx> + Send> {
+        Box::new(best_transactions)
+    }
+}
+
+/// Earliest arrival in the mempool first.
+struct FirstComeFirstServed;
+
+impl TxOrderingPolicy for FirstComeFirstServed {
+    fn order(
+        &self,
+        best_transactions: Box<dyn BestTransactions<Item = PooledTx>>,
+        block_gas_limit: u64,
+    ) -> Box<dyn Iterator<Item = PooledTx> + Send> {
+        Box::new(
+            order_by_arrival(best_transactions, block_gas_limit, |tx| {
+                (tx.sender(), tx.timestamp, tx.gas_limit())
+            })
+            .into_iter(),
+        )
+    }
+}
+
+/// Orders `txs` by arrival, keeping the nonce order of every sender, which `txs` must yield
+/// every sender's transactions in.
+///
+/// A transaction can't run before the lower nonces of its sender, so it is
+/// served no earlier than the latest of those arrived.
+///
+/// Only the earliest served transactions whose gas limits add up to `block_gas_limit`
+/// are kept, as the rest can't make it into the block anyway.
+fn order_by_arrival<T>(
+    txs: impl Iterator<Item = T>,
+    block_gas_limit: u64,
+    key: impl Fn(&T) -> (Address, Instant, u64),
+) -> Vec<T> {
+    let mut served_at_by_sender: HashMap<Address, Instant> = HashMap::new();
+    // latest served on top, to be dropped first once the block is covered
+    let mut served = BinaryHeap::new();
+    let mut total_gas: u64 = 0;
+
+    for (seq, tx) in txs.enumerate() {
+        let (sender, arrival, gas_limit) = key(&tx);
+        let served_at = served_at_by_sender
+            .entry(sender)
+            .and_modify(|previous_at| *previous_at = (*previous_at).max(arrival))
+            .or_insert(arrival);
+
+        served.push(Served {
+            served_at: *served_at,
+            seq,
+            gas_limit,
+            tx,
+        });
+        total_gas = total_gas.saturating_add(gas_limit);
+
+        while let Some(latest) = served.peek() {
+            if total_gas - latest.gas_limit < block_gas_limit {
+                break;
+            }
+            total_gas -= latest.gas_limit;
+            served.pop();
+        }
+    }
+
+    // a sender's transactions served at the same time keep their nonce order through `seq`
+    served
+        .into_sorted_vec()
+        .into_iter()
+        .map(|served| served.tx)
+        .collect()
+}
+
+struct Served<T> {
+    served_at: Instant,
+    seq: usize,
+    gas_limit: u64,
+    tx: T,
+}
+
+impl<T> Served<T> {
+    fn key(&self) -> (Instant, usize) {
+        (self.served_at, self.seq)
+    }
+}
+
+impl<T> PartialEq for Served<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl<T> Eq for Served<T> {}
+
+impl<T> PartialOrd for Served<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Served<T> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+/// Highest priority fee first, capping what a single sender gets in every L2 block.
+struct FairShare {
+    max_txs_per_sender: Option<u64>,
+    max_gas_per_sender: Option<u64>,
+}
+
+impl TxOrderingPolicy for FairShare {
+    fn order(
+        &self,
+        best_transactions: Box<dyn BestTransactions<Item = PooledTx>>,
+        _block_gas_limit: u64,
+    ) -> Box<dyn Iterator<Item = PooledTx> + Send> {
+        Box::new(best_transactions)
+    }
+
+    fn check(&self, gas_limit: u64, sender_usage: SenderUsage) -> Result<(), SkipReason> {
+        if let Some(limit) = self.max_txs_per_sender {
+            if sender_usage.txs >= limit {
+                return Err(SkipReason::SenderTxLimit { limit });
+            }
+        }
+        if let Some(limit) = self.max_gas_per_sender {
+            if sender_usage.gas.saturating_add(gas_limit) > limit {
+                return Err(SkipReason::SenderGasLimit { limit, gas_limit });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What a sender already got in the L2 block being built.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SenderUsage {
+    /// Number of included transactions
+    pub(crate) txs: u64,
+    /// Total gas limit of the included transactions
+    pub(crate) gas: u64,
+}
+
+/// Why a transaction was left out of an L2 block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SkipReason {
+    /// The sender already has `limit` transactions in the block.
+    SenderTxLimit { limit: u64 },
+    /// The gas limit of the transaction would take the sender over `limit` gas in the block.
+    SenderGasLimit { limit: u64, gas_limit: u64 },
+    /// A lower nonce of the sender was skipped, so the transaction can't be executed.
+    PreviousTransactionSkipped,
+    /// The state diff of the transaction doesn't fit in what is `remaining` of the block's budget.
+    L1DiffBudgetExceeded { l1_diff_size: u64, remaining: u64 },
+    /// The state diff of the transaction alone is larger than the `budget` of a block.
+    /// The state diff depends on the state the transaction runs against, so it is retried
+    /// in the next block.
+    L1DiffSizeOverBudget { l1_diff_size: u64, budget: u64 },
+    /// The evm of the active fork doesn't support the type of the transaction.
+    TxTypeNotSupported(String),
+}
+
+impl SkipReason {
+    /// Whether the transaction can never be included and should be dropped from the mempool.
+    pub(crate) fn is_permanent(&self) -> bool {
+        matches!(self, SkipReason::TxTypeNotSupported(_))
+    }
+
+    /// Name of the reason, without its details.
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            SkipReason::SenderTxLimit { .. } => "sender_tx_limit",
+            SkipReason::SenderGasLimit { .. } => "sender_gas_limit",
+            SkipReason::PreviousTransactionSkipped => "previous_transaction_skipped",
+            SkipReason::L1DiffBudgetExceeded { .. } => "l1_diff_budget_exceeded",
+            SkipReason::L1DiffSizeOverBudget { .. } => "l1_diff_size_over_budget",
+            SkipReason::TxTypeNotSupported(_) => "tx_type_not_supported",
+        }
+    }
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipReason::SenderTxLimit { limit } => {
+                write!(
+                    f,
+                    "sender reached the limit of {} transactions per block",
+                    limit
+                )
+            }
+            SkipReason::SenderGasLimit { limit, gas_limit } => write!(
+                f,
+                "gas limit {} would take sender over the limit of {} gas per block",
+                gas_limit, limit
+            ),
+            SkipReason::PreviousTransactionSkipped => {
+                write!(f, "a previous transaction of sender was skipped")
+            }
+            SkipReason::L1DiffBudgetExceeded {
+                l1_diff_size,
+                remaining,
+            } => write!(
+                f,
+                "l1 diff size {} exceeds the remaining {} bytes of the block",
+                l1_diff_size, remaining
+            ),
+            SkipReason::L1DiffSizeOverBudget {
+                l1_diff_size,
+                budget,
+            } => write!(
+                f,
+                "l1 diff size {} exceeds the budget of {} bytes per block",
+                l1_diff_size, budget
+            ),
+            SkipReason::TxTypeNotSupported(tx_type) => {
+                write!(f, "{} transactions are not supported", tx_type)
+            }
+        }
+    }
+}
+
+/// Transaction left out of an L2 block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SkippedTransaction {
+    pub(crate) hash: TxHash,
+    pub(crate) sender: Address,
+    pub(crate) reason: SkipReason,
+}
+
+/// Keeps track of what every sender got while transactions are selected for an L2 block,
+/// and of how much of the block's state diff budget is used.
+///
+/// The budget is checked against the sum of the `l1_diff_size` of the transactions,
+/// which overestimates the state diff of the block when transactions touch the same slots.
+pub(crate) struct BlockSelection {
+    policy: Arc<dyn TxOrderingPolicy>,
+    l1_diff_budget: Option<u64>,
+    l1_diff_size: u64,
+    usage: HashMap<Address, SenderUsage>,
+    skipped_senders: HashSet<Address>,
+    skipped: Vec<SkippedTransaction>,
+}
+
+impl BlockSelection {
+    pub(crate) fn new(policy: Arc<dyn TxOrderingPolicy>, l1_diff_budget: Option<u64>) -> Self {
+        Self {
+            policy,
+            l1_diff_budget,
+            l1_diff_size: 0,
+            usage: HashMap::new(),
+            skipped_senders: HashSet::new(),
+            skipped: vec![],
+        }
+    }
+
+    /// Returns whether the transaction should be tried, recording why if it shouldn't.
+    pub(crate) fn try_select(&mut self, hash: TxHash, sender: Address, gas_limit: u64) -> bool {
+        let result = if self.skipped_senders.contains(&sender) {
+            Err(SkipReason::PreviousTransactionSkipped)
+        } else {
+            let sender_usage = self.usage.get(&sender).copied().unwrap_or_default();
+            self.policy.check(gas_limit, sender_usage)
+        };
+
+        self.record(hash, sender, result)
+    }
+
+    /// Returns whether an executed transaction fits in the rest of the block's state diff budget,
+    /// recording why if it doesn't.
+    pub(crate) fn fits_l1_diff_budget(
+        &mut self,
+        hash: TxHash,
+        sender: Address,
+        l1_diff_size: u64,
+    ) -> bool {
+        let result = match self.l1_diff_budget {
+            Some(budget) if l1_diff_size > budget => Err(SkipReason::L1DiffSizeOverBudget {
+                l1_diff_size,
+                budget,
+            }),
+            Some(budget) if self.l1_diff_size.saturating_add(l1_diff_size) > budget => {
+                Err(SkipReason::L1DiffBudgetExceeded {
+                    l1_diff_size,
+                    remaining: budget - self.l1_diff_size,
+                })
+            }
+            _ => Ok(()),
+        };
+
+        self.record(hash, sender, result)
+    }
+
+    /// Records that an executed transaction was left out of the block for `reason`.
+    pub(crate) fn skip(&mut self, hash: TxHash, sender: Address, reason: SkipReason) {
+        self.record(hash, sender, Err(reason));
+    }
+
+    fn record(&mut self, hash: TxHash, sender: Address, result: Result<(), SkipReason>) -> bool {
+        match result {
+            Ok(()) => true,
+            Err(reason) => {
+                self.skipped_senders.insert(sender);
+                self.skipped.push(SkippedTransaction {
+                    hash,
+                    sender,
+                    reason,
+                });
+                false
+            }
+        }
+    }
+
+    /// Records that a selected transaction made it into the block.
+    pub(crate) fn include(&mut self, sender: Address, gas_limit: u64, l1_diff_size: u64) {
+        let sender_usage = self.usage.entry(sender).or_default();
+        sender_usage.txs += 1;
+        sender_usage.gas = sender_usage.gas.saturating_add(gas_limit);
+        self.l1_diff_size = self.l1_diff_size.saturating_add(l1_diff_size);
+    }
+
+    /// Total `l1_diff_size` of the transactions included so far.
+    pub(crate) fn l1_diff_size(&self) -> u64 {
+        self.l1_diff_size
+    }
+
+    pub(crate) fn into_skipped(self) -> Vec<SkippedTransaction> {
+        self.skipped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn fair_share(
+        max_txs_per_sender: Option<u64>,
+        max_gas_per_sender: Option<u64>,
+    ) -> Arc<dyn TxOrderingPolicy> {
+        create_ordering_policy(&TxOrderingPolicyConfig::FairShare {
+            max_txs_per_sender,
+            max_gas_per_sender,
+        })
+    }
+
+    #[test]
+    fn order_by_arrival_keeps_nonce_order_of_senders() {
+        let alice = Address::with_last_byte(1);
+        let bob = Address::with_last_byte(2);
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+
+        // alice's nonce 1 arrived before her nonce 0, so it waits for it
+        let txs = vec![
+            (alice, 0, at(30)),
+            (bob, 0, at(20)),
+            (alice, 1, at(10)),
+            (bob, 1, at(40)),
+            (alice, 2, at(50)),
+        ];
+
+        let ordered = order_by_arrival(txs.into_iter(), u64::MAX, |&(sender, _, arrival)| {
+            (sender, arrival, 21_000)
+        })
+        .into_iter()
+        .map(|(sender, nonce, _)| (sender, nonce))
+        .collect::<Vec<_>>();
+
+        assert_eq!(
+            ordered,
+            vec![(bob, 0), (alice, 0), (alice, 1), (bob, 1), (alice, 2)]
+        );
+    }
+
+    #[test]
+    fn order_by_arrival_keeps_what_covers_the_block() {
+        let alice = Address::with_last_byte(1);
+        let bob = Address::with_last_byte(2);
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+
+        let txs = vec![
+            (alice, 0, at(50)),
+            (bob, 0, at(10)),
+            (alice, 1, at(20)),
+            (bob, 1, at(40)),
+            (bob, 2, at(30)),
+        ];
+
+        let ordered = order_by_arrival(txs.into_iter(), 50_000, |&(sender, _, arrival)| {
+            (sender, arrival, 21_000)
+        })
+        .into_iter()
+        .map(|(sender, nonce, _)| (sender, nonce))
+        .collect::<Vec<_>>();
+
+        assert_eq!(ordered, vec![(bob, 0), (bob, 1), (bob, 2)]);
+    }
+
+    #[test]
+    fn priority_fee_policy_never_skips() {
+        let policy = create_ordering_policy(&TxOrderingPolicyConfig::PriorityFee);
+        let mut selection = BlockSelection::new(policy, None);
+        let sender = Address::with_last_byte(1);
+
+        for i in 0..100 {
+            assert!(selection.try_select(TxHash::with_last_byte(i), sender, 1_000_000));
+            selection.include(sender, 1_000_000, 0);
+        }
+        assert!(selection.into_skipped().is_empty());
+    }
+
+    #[test]
+    fn fair_share_caps_transactions_per_sender() {
+        let policy = fair_share(Some(2), None);
+        let mut selection = BlockSelection::new(policy, None);
+        let alice = Address::with_last_byte(1);
+        let bob = Address::with_last_byte(2);
+
+        for i in 0..2 {
+            assert!(selection.try_select(TxHash::with_last_byte(i), alice, 21_000));
+            selection.include(alice, 21_000, 0);
+        }
+        assert!(!selection.try_select(TxHash::with_last_byte(2), alice, 21_000));
+        assert!(!selection.try_select(TxHash::with_last_byte(3), alice, 21_000));
+        assert!(selection.try_select(TxHash::with_last_byte(4), bob, 21_000));
+
+        assert_eq!(
+            selection.into_skipped(),
+            vec![
+                SkippedTransaction {
+                    hash: TxHash::with_last_byte(2),
+                    sender: alice,
+                    reason: SkipReason::SenderTxLimit { limit: 2 },
+                },
+                SkippedTransaction {
+                    hash: TxHash::with_last_byte(3),
+                    sender: alice,
+                    reason: SkipReason::PreviousTransactionSkipped,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn fair_share_caps_gas_per_sender() {
+        let policy = fair_share(None, Some(50_000));
+        let mut selection = BlockSelection::new(policy, None);
+        let alice = Address::with_last_byte(1);
+
+        assert!(selection.try_select(TxHash::with_last_byte(0), alice, 21_000));
+        selection.include(alice, 21_000, 0);
+        assert!(!selection.try_select(TxHash::with_last_byte(1), alice, 30_000));
+
+        assert_eq!(
+            selection.into_skipped(),
+            vec![SkippedTransaction {
+                hash: TxHash::with_last_byte(1),
+                sender: alice,
+                reason: SkipReason::SenderGasLimit {
+                    limit: 50_000,
+                    gas_limit: 30_000,
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn failed_transactions_do_not_count_towards_caps() {
+        let policy = fair_share(Some(1), None);
+        let mut selection = BlockSelection::new(policy, None);
+        let alice = Address::with_last_byte(1);
+
+        // selected but failed to execute, so never included
+        assert!(selection.try_select(TxHash::with_last_byte(0), alice, 21_000));
+        assert!(selection.try_select(TxHash::with_last_byte(1), alice, 21_000));
+        selection.include(alice, 21_000, 0);
+        assert!(!selection.try_select(TxHash::with_last_byte(2), alice, 21_000));
+    }
+
+    #[test]
+    fn l1_diff_budget_defers_transactions_over_the_rest_of_the_block() {
+        let policy = create_ordering_policy(&TxOrderingPolicyConfig::PriorityFee);
+        let mut selection = BlockSelection::new(policy, Some(1_000));
+        let alice = Address::with_last_byte(1);
+        let bob = Address::with_last_byte(2);
+        let carol = Address::with_last_byte(3);
+
+        assert!(selection.fits_l1_diff_budget(TxHash::with_last_byte(0), alice, 700));
+        selection.include(alice, 21_000, 700);
+        assert!(!selection.fits_l1_diff_budget(TxHash::with_last_byte(1), bob, 400));
+        // smaller transactions still fit
+        assert!(selection.fits_l1_diff_budget(TxHash::with_last_byte(2), carol, 300));
+        selection.include(carol, 21_000, 300);
+        // bob's following transactions can't be executed
+        assert!(!selection.try_select(TxHash::with_last_byte(3), bob, 21_000));
+
+        assert_eq!(selection.l1_diff_size(), 1_000);
+        let skipped = selection.into_skipped();
+        assert_eq!(
+            skipped,
+            vec![
+                SkippedTransaction {
+                    hash: TxHash::with_last_byte(1),
+                    sender: bob,
+                    reason: SkipReason::L1DiffBudgetExceeded {
+                        l1_diff_size: 400,
+                        remaining: 300,
+                    },
+                },
+                SkippedTransaction {
+                    hash: TxHash::with_last_byte(3),
+                    sender: bob,
+                    reason: SkipReason::PreviousTransactionSkipped,
+                },
+            ]
+        );
+        assert!(skipped.iter().all(|tx| !tx.reason.is_permanent()));
+    }
+
+    #[test]
+    fn l1_diff_budget_retries_transactions_larger_than_a_block() {
+        let policy = create_ordering_policy(&TxOrderingPolicyConfig::PriorityFee);
+        let mut selection = BlockSelection::new(policy, Some(1_000));
+        let alice = Address::with_last_byte(1);
+
+        assert!(!selection.fits_l1_diff_budget(TxHash::with_last_byte(0), alice, 5_000));
+
+        let skipped = selection.into_skipped();
+        assert_eq!(
+            skipped[0].reason,
+            SkipReason::L1DiffSizeOverBudget {
+                l1_diff_size: 5_000,
+                budget: 1_000,
+            }
+        );
+        assert!(!skipped[0].reason.is_permanent());
+    }
+
+    #[test]
+    fn unsupported_transactions_are_dropped() {
+        let policy = create_ordering_policy(&TxOrderingPolicyConfig::PriorityFee);
+        let mut selection = BlockSelection::new(policy, None);
+        let alice = Address::with_last_byte(1);
+
+        selection.skip(
+            TxHash::with_last_byte(0),
+            alice,
+            SkipReason::TxTypeNotSupported("EIP-7702".to_string()),
+        );
+        assert!(!selection.try_select(TxHash::with_last_byte(1), alice, 21_000));
+
+        let skipped = selection.into_skipped();
+        assert!(skipped[0].reason.is_permanent());
+        assert_eq!(skipped[1].reason, SkipReason::PreviousTransactionSkipped);
+        assert!(!skipped[1].reason.is_permanent());
+    }
+
+    #[test]
+    fn no_l1_diff_budget_fits_everything() {
+        let policy = create_ordering_policy(&TxOrderingPolicyConfig::PriorityFee);
+        let mut selection = BlockSelection::new(policy, None);
+        let alice = Address::with_last_byte(1);
+
+        assert!(selection.fits_l1_diff_budget(TxHash::with_last_byte(0), alice, u64::MAX));
+        selection.include(alice, 21_000, u64::MAX);
+        assert!(selection.fits_l1_diff_budget(TxHash::with_last_byte(1), alice, u64::MAX));
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use citrea_evm::Evm;
@@ -7,10 +8,16 @@ use jsonrpsee::proc_macros::rpc;
 use jsonrpsee::types::error::{INTERNAL_ERROR_CODE, INTERNAL_ERROR_MSG};
 use jsonrpsee::types::{ErrorCode, ErrorObject, ErrorObjectOwned};
 use parking_lot::Mutex;
-use reth_primitives::{Bytes, IntoRecoveredTransaction, B256};
+use reth_primitives::{Address, Bytes, IntoRecoveredTransaction, B256, U64};
 use reth_rpc_eth_types::error::EthApiError;
+use reth_rpc_types::txpool::{
+    TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolInspectSummary, TxpoolStatus,
+};
 use reth_rpc_types_compat::transaction::from_recovered;
-use reth_transaction_pool::{EthPooledTransaction, PoolTransaction};
+use reth_transaction_pool::{
+    AllPoolTransactions, EthPooledTransaction, PoolTransaction, ValidPoolTransaction,
+};
+use serde::{Deserialize, Serialize};
 use sov_db::ledger_db::SequencerLedgerOps;
 use sov_modules_api::WorkingSet;
 use tracing::{debug, error};
@@ -26,6 +33,19 @@ pub(crate) struct RpcContext<C: sov_modules_api::Context, DB: SequencerLedgerOps
     pub storage: C::Storage,
     pub ledger: DB,
     pub test_mode: bool,
+    pub l1_diff_budget: Arc<Mutex<L1DiffBudgetStatus>>,
+}
+
+/// State diff budget of L2 blocks, returned by `citrea_getL1DiffBudget`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1DiffBudgetStatus {
+    /// Max total estimated state diff size of the transactions in an L2 block, `None` if unlimited
+    pub budget: Option<U64>,
+    /// Latest L2 block produced by the sequencer
+    pub l2_height: U64,
+    /// Total estimated state diff size of the transactions in the latest L2 block
+    pub l1_diff_size: U64,
 }
 
 #[rpc(client, server)]
@@ -45,8 +65,32 @@ pub trait SequencerRpc {
     #[blocking]
     fn send_raw_deposit_transaction(&self, deposit: Bytes) -> RpcResult<()>;
 
+    #[method(name = "citrea_getPendingDeposits")]
+    #[blocking]
+    fn get_pending_deposits(&self) -> RpcResult<Vec<Bytes>>;
+
     #[method(name = "citrea_testPublishBlock")]
     async fn publish_test_block(&self) -> RpcResult<()>;
+
+    #[method(name = "citrea_getL1DiffBudget")]
+    #[blocking]
+    fn get_l1_diff_budget(&self) -> RpcResult<L1DiffBudgetStatus>;
+
+    #[method(name = "txpool_content")]
+    #[blocking]
+    fn txpool_content(&self) -> RpcResult<TxpoolContent>;
+
+    #[method(name = "txpool_contentFrom")]
+    #[blocking]
+    fn txpool_content_from(&self, from: Address) -> RpcResult<TxpoolContentFrom>;
+
+    #[method(name = "txpool_status")]
+    #[blocking]
+    fn txpool_status(&self) -> RpcResult<TxpoolStatus>;
+
+    #[method(name = "txpool_inspect")]
+    #[blocking]
+    fn txpool_inspect(&self) -> RpcResult<TxpoolInspect>;
 }
 
 pub struct SequencerRpcServerImpl<
@@ -150,10 +194,22 @@ impl<C: sov_modules_api::Context, DB: SequencerLedgerOps + Send + Sync + 'static
         match tx_res {
             Ok(hex_res) => {
                 tracing::debug!("Deposit tx processed successfully {}", hex_res);
+                let mut deposit_mempool = self.context.deposit_mempool.lock();
+
+                // Persist before accepting, so an accepted deposit is never lost on restart
+                let index = deposit_mempool.next_index();
                 self.context
-                    .deposit_mempool
-                    .lock()
-                    .add_deposit_tx(deposit.to_vec());
+                    .ledger
+                    .insert_mempool_deposit(index, deposit.to_vec())
+                    .map_err(|e| {
+                        error!("Failed to insert mempool deposit into db: {:?}", e);
+                        ErrorObjectOwned::owned(
+                            INTERNAL_ERROR_CODE,
+                            INTERNAL_ERROR_MSG,
+                            Some(format!("Could not persist deposit tx: {e}")),
+                        )
+                    })?;
+                deposit_mempool.add_deposit_tx(deposit.to_vec());
                 Ok(())
             }
             Err(e) => {
@@ -163,6 +219,19 @@ impl<C: sov_modules_api::Context, DB: SequencerLedgerOps + Send + Sync + 'static
         }
     }
 
+    fn get_pending_deposits(&self) -> RpcResult<Vec<Bytes>> {
+        debug!("Sequencer: citrea_getPendingDeposits");
+
+        Ok(self
+            .context
+            .deposit_mempool
+            .lock()
+            .pending_deposits()
+            .into_iter()
+            .map(Bytes::from)
+            .collect())
+    }
+
     async fn publish_test_block(&self) -> RpcResult<()> {
         if !self.context.test_mode {
             return Err(ErrorObject::from(ErrorCode::MethodNotFound).to_owned());
@@ -180,6 +249,98 @@ impl<C: sov_modules_api::Context, DB: SequencerLedgerOps + Send + Sync + 'static
                 )
             })
     }
+
+    fn get_l1_diff_budget(&self) -> RpcResult<L1DiffBudgetStatus> {
+        debug!("Sequencer: citrea_getL1DiffBudget");
+
+        Ok(self.context.l1_diff_budget.lock().clone())
+    }
+
+    fn txpool_content(&self) -> RpcResult<TxpoolContent> {
+        debug!("Sequencer: txpool_content");
+
+        let AllPoolTransactions { pending, queued } = self.context.mempool.all_transactions();
+
+        Ok(TxpoolContent {
+            pending: group_by_sender(pending, to_rpc_transaction),
+            queued: group_by_sender(queued, to_rpc_transaction),
+        })
+    }
+
+    fn txpool_content_from(&self, from: Address) -> RpcResult<TxpoolContentFrom> {
+        debug!("Sequencer: txpool_contentFrom({})", from);
+
+        let AllPoolTransactions { pending, queued } = self.context.mempool.all_transactions();
+        Ok(TxpoolContentFrom {
+            pending: filter_by_sender(pending, from),
+            queued: filter_by_sender(queued, from),
+        })
+    }
+
+    fn txpool_status(&self) -> RpcResult<TxpoolStatus> {
+        debug!("Sequencer: txpool_status");
+
+        let AllPoolTransactions { pending, queued } = self.context.mempool.all_transactions();
+
+        Ok(TxpoolStatus {
+            pending: pending.len() as u64,
+            queued: queued.len() as u64,
+        })
+    }
+
+    fn txpool_inspect(&self) -> RpcResult<TxpoolInspect> {
+        debug!("Sequencer: txpool_inspect");
+
+        let AllPoolTransactions { pending, queued } = self.context.mempool.all_transactions();
+
+        Ok(TxpoolInspect {
+            pending: group_by_sender(pending, to_inspect_summary),
+            queued: group_by_sender(queued, to_inspect_summary),
+        })
+    }
+}
+
+/// Groups mempool transactions by their sender and nonce, as returned by the `txpool_*` methods.
+fn group_by_sender<T>(
+    transactions: Vec<Arc<ValidPoolTransaction<EthPooledTransaction>>>,
+    f: impl Fn(&ValidPoolTransaction<EthPooledTransaction>) -> T,
+) -> BTreeMap<Address, BTreeMap<String, T>> {
+    let mut grouped: BTreeMap<Address, BTreeMap<String, T>> = BTreeMap::new();
+    for tx in transactions {
+        grouped
+            .entry(tx.sender())
+            .or_default()
+            .insert(tx.nonce().to_string(), f(&tx));
+    }
+    grouped
+}
+
+/// Keeps only the mempool transactions of `sender`, keyed by their nonce.
+fn filter_by_sender(
+    transactions: Vec<Arc<ValidPoolTransaction<EthPooledTransaction>>>,
+    sender: Address,
+) -> BTreeMap<String, reth_rpc_types::Transaction> {
+    transactions
+        .into_iter()
+        .filter(|tx| tx.sender() == sender)
+        .map(|tx| (tx.nonce().to_string(), to_rpc_transaction(&tx)))
+        .collect()
+}
+
+fn to_rpc_transaction(
+    tx: &ValidPoolTransaction<EthPooledTransaction>,
+) -> reth_rpc_types::Transaction {
+    from_recovered(tx.to_recovered_transaction())
+}
+
+fn to_inspect_summary(tx: &ValidPoolTransaction<EthPooledTransaction>) -> TxpoolInspectSummary {
+    let tx = tx.to_recovered_transaction();
+    TxpoolInspectSummary {
+        to: tx.to(),
+        value: tx.value(),
+        gas: tx.gas_limit() as u128,
+        gas_price: tx.max_fee_per_gas(),
+    }
 }
 
 pub fn create_rpc_module<
@@ -7,6 +7,7 @@ use std::vec;
 use anyhow::{anyhow, bail};
 use backoff::future::retry as retry_backoff;
 use backoff::ExponentialBackoffBuilder;
+use citrea_common::da::resume_publication_receipts;
 use citrea_common::tasks::manager::TaskManager;
 use citrea_common::utils::soft_confirmation_to_receipt;
 use citrea_common::{RollupPublicKeys, RpcConfig, SequencerConfig};
@@ -19,11 +20,10 @@ use futures::StreamExt;
 use jsonrpsee::server::{BatchRequestConfig, RpcServiceBuilder, ServerBuilder};
 use jsonrpsee::RpcModule;
 use parking_lot::Mutex;
-use reth_primitives::{Address, IntoRecoveredTransaction, TxHash};
+use reth_primitives::{Address, IntoRecoveredTransaction, TxHash, U64};
 use reth_provider::{AccountReader, BlockReaderIdExt};
 use reth_transaction_pool::{
-    BestTransactions, BestTransactionsAttributes, ChangedAccount, EthPooledTransaction,
-    PoolTransaction, ValidPoolTransaction,
+    BestTransactionsAttributes, ChangedAccount, EthPooledTransaction, PoolTransaction,
 };
 use sov_accounts::Accounts;
 use sov_accounts::Response::{AccountEmpty, AccountExists};
@@ -55,7 +55,14 @@ use crate::commitment::CommitmentService;
 use crate::db_provider::DbProvider;
 use crate::deposit_data_mempool::DepositDataMempool;
 use crate::mempool::CitreaMempool;
-use crate::rpc::{create_rpc_module, RpcContext};
+use crate::metrics::{
+    SEQUENCER_BLOCK_L1_DIFF_SIZE_BYTES, SEQUENCER_L1_DIFF_BUDGET_BYTES, SEQUENCER_SKIPPED_TXS,
+};
+use crate::ordering::{
+    create_ordering_policy, BlockSelection, PooledTx, SkipReason, SkippedTransaction,
+    TxOrderingPolicy,
+};
+use crate::rpc::{create_rpc_module, L1DiffBudgetStatus, RpcContext};
 use crate::utils::recover_raw_transaction;
 
 type StateRoot<C, Da, RT> = <StfBlueprint<C, Da, RT> as StateTransitionFunction<Da>>::StateRoot;
@@ -76,6 +83,8 @@ where
 {
     da_service: Arc<Da>,
     mempool: Arc<CitreaMempool<C>>,
+    ordering_policy: Arc<dyn TxOrderingPolicy>,
+    l1_diff_budget: Arc<Mutex<L1DiffBudgetStatus>>,
     sov_tx_signer_priv_key: C::PrivateKey,
     l2_force_block_tx: UnboundedSender<()>,
     l2_force_block_rx: UnboundedReceiver<()>,
@@ -119,7 +128,7 @@ where
         public_keys: RollupPublicKeys,
         ledger_db: DB,
         rpc_config: RpcConfig,
-        fork_manager: ForkManager,
+        mut fork_manager: ForkManager,
         soft_confirmation_tx: broadcast::Sender<u64>,
         task_manager: TaskManager<()>,
     ) -> anyhow::Result<Self> {
@@ -148,7 +157,22 @@ where
         // used as client of reth's mempool
         let db_provider = DbProvider::new(storage.clone());
 
-        let pool = CitreaMempool::new(db_provider.clone(), config.mempool_conf.clone())?;
+        let pool = CitreaMempool::new(
+            db_provider.clone(),
+            config.mempool_conf.clone(),
+            fork_manager.active_fork().spec_id,
+        )?;
+        fork_manager.register_handler(Box::new(pool.eip7702_activation()));
+
+        let ordering_policy = create_ordering_policy(&config.ordering_policy);
+
+        if let Some(budget) = config.max_l1_diff_bytes_per_block {
+            SEQUENCER_L1_DIFF_BUDGET_BYTES.set(budget as i64);
+        }
+        let l1_diff_budget = Arc::new(Mutex::new(L1DiffBudgetStatus {
+            budget: config.max_l1_diff_bytes_per_block.map(U64::from),
+            ..Default::default()
+        }));
 
         let deposit_mempool = Arc::new(Mutex::new(DepositDataMempool::new()));
 
@@ -157,6 +181,8 @@ where
         Ok(Self {
             da_service,
             mempool: Arc::new(pool),
+            ordering_policy,
+            l1_diff_budget,
             sov_tx_signer_priv_key,
             l2_force_block_tx,
             l2_force_block_rx,
@@ -246,15 +272,18 @@ where
     #[allow(clippy::too_many_arguments)]
     async fn dry_run_transactions(
         &mut self,
-        transactions: Box<
-            dyn BestTransactions<Item = Arc<ValidPoolTransaction<EthPooledTransaction>>>,
-        >,
+        transactions: Box<dyn Iterator<Item = PooledTx> + Send>,
         pub_key: &[u8],
         prestate: ProverStorage<SnapshotManager>,
         da_block_header: <<Da as DaService>::Spec as DaSpec>::BlockHeader,
         soft_confirmation_info: HookSoftConfirmationInfo,
         l2_block_mode: L2BlockMode,
-    ) -> anyhow::Result<(Vec<RlpEvmTransaction>, Vec<TxHash>)> {
+    ) -> anyhow::Result<(
+        Vec<RlpEvmTransaction>,
+        Vec<TxHash>,
+        Vec<SkippedTransaction>,
+        u64,
+    )> {
         let silent_subscriber = tracing_subscriber::registry().with(LevelFilter::OFF);
 
         tracing::subscriber::with_default(silent_subscriber, || {
@@ -276,8 +305,20 @@ where
                         L2BlockMode::NotEmpty => {
                             let mut all_txs = vec![];
                             let mut l1_fee_failed_txs = vec![];
+                            let mut selection = BlockSelection::new(
+                                self.ordering_policy.clone(),
+                                self.config.max_l1_diff_bytes_per_block,
+                            );
 
                             for evm_tx in transactions {
+                                if !selection.try_select(
+                                    *evm_tx.hash(),
+                                    evm_tx.sender(),
+                                    evm_tx.gas_limit(),
+                                ) {
+                                    continue;
+                                }
+
                                 let rlp_tx = RlpEvmTransaction {
                                     rlp: evm_tx
                                         .to_recovered_transaction()
@@ -306,13 +347,13 @@ where
                                 let mut working_set =
                                     working_set_to_discard.checkpoint().to_revertable();
 
-                                match self.stf.apply_soft_confirmation_txs(
+                                let call_response = match self.stf.apply_soft_confirmation_txs(
                                     soft_confirmation_info.clone(),
                                     &txs,
                                     &txs_new,
                                     &mut working_set,
                                 ) {
-                                    Ok(result) => result,
+                                    Ok(call_response) => call_response,
                                     Err(e) => match e {
                                         // Since this is the sequencer, it should never get a soft confirmation error or a hook error
                                         sov_rollup_interface::stf::StateTransitionError::SoftConfirmationError(soft_confirmation_error) => panic!("Soft confirmation error: {:?}", soft_confirmation_error),
@@ -336,8 +377,18 @@ where
                                                }
                                             },
                                             // we configure mempool to never accept blob transactions
-                                            // to mitigate potential bugs in reth-mempool we should look into continue instead of panicking here
-                                            sov_modules_api::SoftConfirmationModuleCallError::EvmTxTypeNotSupported(_) => panic!("got unsupported tx type"),
+                                            // and eip7702 transactions before prague, yet an eip7702
+                                            // transaction can be pooled before the fork boundary and
+                                            // be tried after it; it is dropped from the mempool
+                                            sov_modules_api::SoftConfirmationModuleCallError::EvmTxTypeNotSupported(tx_type) => {
+                                                selection.skip(
+                                                    *evm_tx.hash(),
+                                                    evm_tx.sender(),
+                                                    SkipReason::TxTypeNotSupported(tx_type),
+                                                );
+                                                working_set_to_discard = working_set.revert().to_revertable();
+                                                continue;
+                                            },
                                             // Discard tx if it fails to execute
                                             sov_modules_api::SoftConfirmationModuleCallError::EvmTransactionExecutionError => {
                                                 working_set_to_discard = working_set.revert().to_revertable();
@@ -360,15 +411,41 @@ where
                                     },
                                 };
 
+                                // defer the transaction to a later block instead of
+                                // growing the state diff of this one over the budget
+                                let l1_diff_size = call_response.l1_diff_size;
+                                if !selection.fits_l1_diff_budget(
+                                    *evm_tx.hash(),
+                                    evm_tx.sender(),
+                                    l1_diff_size,
+                                ) {
+                                    // the evm keeps executed transactions in memory,
+                                    // which the working set revert doesn't cover
+                                    self.stf.revert_latest_call();
+                                    working_set_to_discard = working_set.revert().to_revertable();
+                                    continue;
+                                }
+
                                 // if no errors
                                 // we can include the transaction in the block
                                 working_set_to_discard = working_set.checkpoint().to_revertable();
+                                selection.include(
+                                    evm_tx.sender(),
+                                    evm_tx.gas_limit(),
+                                    l1_diff_size,
+                                );
                                 all_txs.push(rlp_tx);
                             }
 
-                            Ok((all_txs, l1_fee_failed_txs))
+                            let l1_diff_size = selection.l1_diff_size();
+                            Ok((
+                                all_txs,
+                                l1_fee_failed_txs,
+                                selection.into_skipped(),
+                                l1_diff_size,
+                            ))
                         }
-                        L2BlockMode::Empty => Ok((vec![], vec![])),
+                        L2BlockMode::Empty => Ok((vec![], vec![], vec![], 0)),
                     }
                 }
                 Err(err) => {
@@ -409,10 +486,12 @@ where
         let pub_key = borsh::to_vec(&self.sov_tx_signer_priv_key.pub_key())
             .map_err(Into::<anyhow::Error>::into)?;
 
-        let deposit_data = self
+        let (deposit_indices, deposit_data): (Vec<u64>, Vec<Vec<u8>>) = self
             .deposit_mempool
             .lock()
-            .fetch_deposits(self.config.deposit_mempool_fetch_limit);
+            .fetch_deposits(self.config.deposit_mempool_fetch_limit)
+            .into_iter()
+            .unzip();
 
         let active_fork_spec = self.fork_manager.active_fork().spec_id;
 
@@ -443,7 +522,9 @@ where
         // Dry running transactions would basically allow for figuring out a list of
         // all transactions that would fit into the current block and the list of transactions
         // which do not have enough balance to pay for the L1 fee.
-        let (txs_to_run, l1_fee_failed_txs) = self
+        // Skipped transactions stay in the mempool for the next blocks, unless their
+        // state diff alone is over the budget of a block.
+        let (txs_to_run, l1_fee_failed_txs, skipped_txs, l1_diff_size) = self
             .dry_run_transactions(
                 evm_txs,
                 &pub_key,
@@ -454,6 +535,16 @@ where
             )
             .await?;
 
+        for skipped_tx in &skipped_txs {
+            SEQUENCER_SKIPPED_TXS
+                .with_label_values(&[skipped_tx.reason.name()])
+                .inc();
+            debug!(
+                "Skipped tx {} from {}: {}",
+                skipped_tx.hash, skipped_tx.sender, skipped_tx.reason
+            );
+        }
+
         let prestate = self
             .storage_manager
             .create_storage_on_l2_height(l2_height)
@@ -579,15 +670,28 @@ where
 
                 let l1_height = da_block.header().height();
                 info!(
-                    "New block #{}, DA #{}, Tx count: #{}",
-                    l2_height, l1_height, evm_txs_count,
+                    "New block #{}, DA #{}, Tx count: #{}, L1 diff size: {}",
+                    l2_height, l1_height, evm_txs_count, l1_diff_size,
                 );
 
+                SEQUENCER_BLOCK_L1_DIFF_SIZE_BYTES.set(l1_diff_size as i64);
+                {
+                    let mut l1_diff_budget = self.l1_diff_budget.lock();
+                    l1_diff_budget.l2_height = U64::from(l2_height);
+                    l1_diff_budget.l1_diff_size = U64::from(l1_diff_size);
+                }
+
                 self.state_root = next_state_root;
                 self.batch_hash = soft_confirmation_hash;
 
                 let mut txs_to_remove = self.db_provider.last_block_tx_hashes()?;
                 txs_to_remove.extend(l1_fee_failed_txs);
+                txs_to_remove.extend(
+                    skipped_txs
+                        .iter()
+                        .filter(|tx| tx.reason.is_permanent())
+                        .map(|tx| tx.hash),
+                );
 
                 self.mempool.remove_transactions(txs_to_remove.clone());
 
@@ -602,6 +706,9 @@ where
                 if let Err(e) = self.ledger_db.remove_mempool_txs(txs) {
                     warn!("Failed to remove txs from mempool: {:?}", e);
                 }
+                if let Err(e) = self.ledger_db.remove_mempool_deposits(deposit_indices) {
+                    warn!("Failed to remove deposits from mempool: {:?}", e);
+                }
 
                 Ok((
                     l2_height,
@@ -637,6 +744,11 @@ where
             }
         }
 
+        if let Err(e) = resume_publication_receipts(self.da_service.clone(), self.ledger_db.clone())
+        {
+            warn!("Sequencer: Failed to resume publication receipts: {:?}", e);
+        }
+
         let (mut last_finalized_block, mut l1_fee_rate) =
             match get_da_block_data(self.da_service.clone()).await {
                 Ok(l1_data) => l1_data,
@@ -778,11 +890,7 @@ where
         }
     }
 
-    fn get_best_transactions(
-        &self,
-    ) -> anyhow::Result<
-        Box<dyn BestTransactions<Item = Arc<ValidPoolTransaction<EthPooledTransaction>>>>,
-    > {
+    fn get_best_transactions(&self) -> anyhow::Result<Box<dyn Iterator<Item = PooledTx> + Send>> {
         let cfg = self.db_provider.cfg();
         let latest_header = self
             .db_provider
@@ -804,7 +912,9 @@ where
             .mempool
             .best_transactions_with_attributes(BestTransactionsAttributes::base_fee(base_fee));
 
-        Ok(best_txs_with_base_fee)
+        Ok(self
+            .ordering_policy
+            .order(best_txs_with_base_fee, cfg.block_gas_limit))
     }
 
     /// Signs batch of messages with sovereign priv key turns them into a sov blob
@@ -927,6 +1037,7 @@ where
             storage: self.storage.clone(),
             ledger: self.ledger_db.clone(),
             test_mode: self.config.test_mode,
+            l1_diff_budget: self.l1_diff_budget.clone(),
         }
     }
 
@@ -942,6 +1053,11 @@ where
     }
 
     pub async fn restore_mempool(&self) -> Result<(), anyhow::Error> {
+        let mempool_deposits = self.ledger_db.get_mempool_deposits()?;
+        self.deposit_mempool
+            .lock()
+            .restore_deposits(mempool_deposits);
+
         let mempool_txs = self.ledger_db.get_mempool_txs()?;
         for (_, tx) in mempool_txs {
             let recovered =
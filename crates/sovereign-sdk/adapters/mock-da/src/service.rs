@@ -10,9 +10,10 @@ use pin_project::pin_project;
 use sha2::Digest;
 use sov_rollup_interface::da::{
     BlobReaderTrait, BlockHeaderTrait, DaData, DaDataBatchProof, DaDataLightClient, DaNamespace,
-    DaSpec, SequencerCommitment, Time,
+    DaPublicationReceipt, DaSpec, SequencerCommitment, Time,
 };
 use sov_rollup_interface::services::da::{DaService, SenderWithNotifier, SlotData};
+use sov_rollup_interface::spec::SpecId;
 use sov_rollup_interface::zk::Proof;
 use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
 use tokio::sync::{broadcast, Mutex as AsyncMutex, MutexGuard as AsyncMutexGuard};
@@ -155,7 +156,7 @@ impl MockDaService {
         blocks.prune_above(height);
 
         for blob in blobs {
-            let da_data = DaData::ZKProof(blob);
+            let da_data = DaData::ZKProof(blob, SpecId::default());
             let blob = borsh::to_vec(&da_data).unwrap();
             self.add_blob(&blocks, blob, Default::default()).unwrap();
         }
@@ -479,7 +480,7 @@ impl DaService for MockDaService {
     #[tracing::instrument(name = "MockDA", level = "debug", skip_all)]
     async fn send_transaction(&self, da_data: DaData) -> Result<Self::TransactionId, Self::Error> {
         let blob = match da_data {
-            DaData::ZKProof(proof) => {
+            DaData::ZKProof(proof, _) => {
                 tracing::debug!("Adding a zkproof");
                 let data = DaDataLightClient::Complete(proof);
                 borsh::to_vec(&data).unwrap()
@@ -490,9 +491,34 @@ impl DaService for MockDaService {
                 borsh::to_vec(&data).unwrap()
             }
         };
+        let tx_id = hash_to_array(&blob);
         let blocks = self.blocks.lock().await;
         let _ = self.add_blob(&blocks, blob, Default::default())?;
-        Ok(MockHash([0; 32]))
+        Ok(MockHash(tx_id))
+    }
+
+    async fn get_publication_receipt(
+        &self,
+        tx_id: [u8; 32],
+    ) -> Result<Option<DaPublicationReceipt>, Self::Error> {
+        let last_finalized_height = self.get_last_finalized_height().await;
+        let blocks = self.blocks.lock().await;
+        // Blobs are their own tx, there is no commit tx or inclusion proof
+        for height in (1..=last_finalized_height).rev() {
+            let Some(block) = blocks.get(height - 1) else {
+                continue;
+            };
+            if block.blobs.iter().any(|blob| blob.hash == tx_id) {
+                return Ok(Some(DaPublicationReceipt {
+                    commit_tx_id: tx_id,
+                    reveal_tx_id: tx_id,
+                    l1_height: height,
+                    l1_block_hash: block.header().hash.0,
+                    inclusion_proof: vec![],
+                }));
+            }
+        }
+        Ok(None)
     }
 
     fn get_send_transaction_queue(
@@ -640,7 +666,7 @@ mod tests {
 
         for i in 0..num_blocks {
             let proof = vec![i as u8; i + 1];
-            let published_blob = DaData::ZKProof(proof.clone());
+            let published_blob = DaData::ZKProof(proof.clone(), SpecId::Genesis);
             let height = (i + 1) as u64;
 
             da.send_transaction(published_blob.clone()).await.unwrap();
@@ -691,7 +717,7 @@ mod tests {
         for (i, blob) in blobs.iter().enumerate() {
             let height = (i + 1) as u64;
             // Send transaction should pass
-            da.send_transaction(DaData::ZKProof(blob.to_owned()))
+            da.send_transaction(DaData::ZKProof(blob.to_owned(), SpecId::Genesis))
                 .await
                 .unwrap();
             let last_finalized_block_response = da.get_last_finalized_block_header().await;
@@ -777,13 +803,13 @@ mod tests {
 
             // 1 -> 2 -> 3
 
-            da.send_transaction(DaData::ZKProof(vec![1, 2, 3, 4]))
+            da.send_transaction(DaData::ZKProof(vec![1, 2, 3, 4], SpecId::Genesis))
                 .await
                 .unwrap();
-            da.send_transaction(DaData::ZKProof(vec![4, 5, 6, 7]))
+            da.send_transaction(DaData::ZKProof(vec![4, 5, 6, 7], SpecId::Genesis))
                 .await
                 .unwrap();
-            da.send_transaction(DaData::ZKProof(vec![8, 9, 0, 1]))
+            da.send_transaction(DaData::ZKProof(vec![8, 9, 0, 1], SpecId::Genesis))
                 .await
                 .unwrap();
 
@@ -834,19 +860,19 @@ mod tests {
             //      \ -> 3.2 -> 4.2
 
             // 1
-            da.send_transaction(DaData::ZKProof(vec![1, 2, 3, 4]))
+            da.send_transaction(DaData::ZKProof(vec![1, 2, 3, 4], SpecId::Genesis))
                 .await
                 .unwrap();
             // 2
-            da.send_transaction(DaData::ZKProof(vec![4, 5, 6, 7]))
+            da.send_transaction(DaData::ZKProof(vec![4, 5, 6, 7], SpecId::Genesis))
                 .await
                 .unwrap();
             // 3.1
-            da.send_transaction(DaData::ZKProof(vec![8, 9, 0, 1]))
+            da.send_transaction(DaData::ZKProof(vec![8, 9, 0, 1], SpecId::Genesis))
                 .await
                 .unwrap();
             // 4.1
-            da.send_transaction(DaData::ZKProof(vec![2, 3, 4, 5]))
+            da.send_transaction(DaData::ZKProof(vec![2, 3, 4, 5], SpecId::Genesis))
                 .await
                 .unwrap();
 
@@ -876,16 +902,16 @@ mod tests {
 
             // 1 -> 2 -> 3 -> 4
 
-            da.send_transaction(DaData::ZKProof(vec![1, 2, 3, 4]))
+            da.send_transaction(DaData::ZKProof(vec![1, 2, 3, 4], SpecId::Genesis))
                 .await
                 .unwrap();
-            da.send_transaction(DaData::ZKProof(vec![4, 5, 6, 7]))
+            da.send_transaction(DaData::ZKProof(vec![4, 5, 6, 7], SpecId::Genesis))
                 .await
                 .unwrap();
-            da.send_transaction(DaData::ZKProof(vec![8, 9, 0, 1]))
+            da.send_transaction(DaData::ZKProof(vec![8, 9, 0, 1], SpecId::Genesis))
                 .await
                 .unwrap();
-            da.send_transaction(DaData::ZKProof(vec![2, 3, 4, 5]))
+            da.send_transaction(DaData::ZKProof(vec![2, 3, 4, 5], SpecId::Genesis))
                 .await
                 .unwrap();
 
@@ -945,13 +971,13 @@ mod tests {
                 assert!(has_planned_fork.is_some());
             }
 
-            da.send_transaction(DaData::ZKProof(vec![1, 2, 3, 4]))
+            da.send_transaction(DaData::ZKProof(vec![1, 2, 3, 4], SpecId::Genesis))
                 .await
                 .unwrap();
-            da.send_transaction(DaData::ZKProof(vec![4, 5, 6, 7]))
+            da.send_transaction(DaData::ZKProof(vec![4, 5, 6, 7], SpecId::Genesis))
                 .await
                 .unwrap();
-            da.send_transaction(DaData::ZKProof(vec![8, 9, 0, 1]))
+            da.send_transaction(DaData::ZKProof(vec![8, 9, 0, 1], SpecId::Genesis))
                 .await
                 .unwrap();
 
@@ -983,19 +1009,19 @@ mod tests {
                 PlannedFork::new(4, 2, vec![vec![13, 13, 13, 13], vec![14, 14, 14, 14]]);
             da.set_planned_fork(planned_fork).await.unwrap();
 
-            da.send_transaction(DaData::ZKProof(vec![1, 1, 1, 1]))
+            da.send_transaction(DaData::ZKProof(vec![1, 1, 1, 1], SpecId::Genesis))
                 .await
                 .unwrap();
-            da.send_transaction(DaData::ZKProof(vec![2, 2, 2, 2]))
+            da.send_transaction(DaData::ZKProof(vec![2, 2, 2, 2], SpecId::Genesis))
                 .await
                 .unwrap();
-            da.send_transaction(DaData::ZKProof(vec![3, 3, 3, 3]))
+            da.send_transaction(DaData::ZKProof(vec![3, 3, 3, 3], SpecId::Genesis))
                 .await
                 .unwrap();
-            da.send_transaction(DaData::ZKProof(vec![4, 4, 4, 4]))
+            da.send_transaction(DaData::ZKProof(vec![4, 4, 4, 4], SpecId::Genesis))
                 .await
                 .unwrap();
-            da.send_transaction(DaData::ZKProof(vec![5, 5, 5, 5]))
+            da.send_transaction(DaData::ZKProof(vec![5, 5, 5, 5], SpecId::Genesis))
                 .await
                 .unwrap();
 
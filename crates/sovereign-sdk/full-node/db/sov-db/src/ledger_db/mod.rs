@@ -3,7 +3,7 @@ use std::sync::{Arc, Mutex};
 
 use serde::de::DeserializeOwned;
 use serde::Serialize;
-use sov_rollup_interface::da::{DaSpec, SequencerCommitment};
+use sov_rollup_interface::da::{DaPublicationReceipt, DaSpec, SequencerCommitment};
 use sov_rollup_interface::fork::{Fork, ForkMigration};
 use sov_rollup_interface::stf::{SoftConfirmationReceipt, StateDiff};
 use sov_rollup_interface::zk::Proof;
@@ -14,17 +14,18 @@ use crate::rocks_db_config::RocksdbConfig;
 #[cfg(test)]
 use crate::schema::tables::TestTableNew;
 use crate::schema::tables::{
-    BatchByNumber, CommitmentsByNumber, ExecutedMigrations, L2GenesisStateRoot, L2RangeByL1Height,
-    L2Witness, LastPrunedBlock, LastSequencerCommitmentSent, LastStateDiff,
-    LightClientProofBySlotNumber, MempoolTxs, PendingProvingSessions,
+    BatchByNumber, BatchProofPublicationReceipts, CommitmentPublicationReceipts,
+    CommitmentsByNumber, ExecutedMigrations, L2GenesisStateRoot, L2RangeByL1Height, L2Witness,
+    LastPrunedBlock, LastSequencerCommitmentSent, LastStateDiff, LightClientProofBySlotNumber,
+    MempoolDeposits, MempoolTxs, PendingProvingSessions, PendingPublications,
     PendingSequencerCommitmentL2Range, ProofsBySlotNumberV2, ProverLastScannedSlot,
     ProverStateDiffs, SlotByHash, SlotByNumber, SoftConfirmationByHash, SoftConfirmationByNumber,
     SoftConfirmationStatus, VerifiedBatchProofsBySlotNumber, LEDGER_TABLES,
 };
 use crate::schema::types::{
     BatchNumber, L2HeightRange, SlotNumber, StoredBatchProof, StoredBatchProofOutput,
-    StoredLightClientProof, StoredLightClientProofOutput, StoredSlot, StoredSoftConfirmation,
-    StoredTransaction, StoredVerifiedProof,
+    StoredLightClientProof, StoredLightClientProofOutput, StoredPendingPublication, StoredSlot,
+    StoredSoftConfirmation, StoredTransaction, StoredVerifiedProof,
 };
 
 /// Implementation of database migrator
@@ -149,6 +150,19 @@ impl LedgerDB {
             _ => Ok(None),
         }
     }
+
+    /// The L2 heights proven by a verified batch proof. The L2 block before the first one
+    /// stays resolvable by hash after it is pruned.
+    fn proven_l2_range(
+        &self,
+        proof_output: &StoredBatchProofOutput,
+    ) -> anyhow::Result<std::ops::RangeInclusive<u64>> {
+        let start = self
+            .db
+            .get::<SoftConfirmationByHash>(&proof_output.prev_soft_confirmation_hash)?
+            .map_or(0, |number| number.0 + 1);
+        Ok(start..=proof_output.last_l2_height)
+    }
 }
 
 impl SharedLedgerOps for LedgerDB {
@@ -469,6 +483,88 @@ impl SharedLedgerOps for LedgerDB {
         Ok(())
     }
 
+    /// Prunes soft confirmations and their related data in the given L2 range
+    #[instrument(level = "trace", skip(self), err)]
+    fn prune_soft_confirmations(
+        &self,
+        range: &std::ops::RangeInclusive<BatchNumber>,
+    ) -> anyhow::Result<()> {
+        let mut schema_batch = SchemaBatch::new();
+        let mut l1_heights = Vec::new();
+
+        let mut iter = self.db.iter::<SoftConfirmationByNumber>()?;
+        iter.seek(range.start())?;
+
+        for item in iter {
+            let (number, soft_confirmation) = item?.into_tuple();
+            if number > *range.end() {
+                break;
+            }
+
+            // The hash to number mapping is kept, so lookups by hash of a pruned
+            // soft confirmation can be told apart from unknown hashes
+            schema_batch.delete::<SoftConfirmationByNumber>(&number)?;
+            schema_batch.delete::<SoftConfirmationStatus>(&number)?;
+            schema_batch.delete::<L2Witness>(&number)?;
+            schema_batch.delete::<ProverStateDiffs>(&number)?;
+
+            if l1_heights.last() != Some(&soft_confirmation.da_slot_height) {
+                l1_heights.push(soft_confirmation.da_slot_height);
+            }
+        }
+
+        // Only drop the L1 to L2 range mapping if the whole range has been pruned,
+        // the L1 block might still be referenced by L2 blocks above the pruning point.
+        for l1_height in l1_heights {
+            let l1_height = SlotNumber(l1_height);
+            if let Some((_, l2_end)) = self.db.get::<L2RangeByL1Height>(&l1_height)? {
+                if l2_end <= *range.end() {
+                    schema_batch.delete::<L2RangeByL1Height>(&l1_height)?;
+                }
+            }
+        }
+
+        // Slots are stored in order, so the ones whose batches are all pruned come first
+        let mut iter = self.db.iter::<SlotByNumber>()?;
+        iter.seek_to_first();
+        for item in iter {
+            let (slot_number, slot) = item?.into_tuple();
+            if slot.batches.end.0 > range.end().0 + 1 {
+                break;
+            }
+            schema_batch.delete::<SlotByNumber>(&slot_number)?;
+        }
+
+        // Commitments land on L1 after the blocks they commit to, so they can't be found
+        // through the L1 heights of the pruned blocks. Their table isn't stored in order,
+        // but it only has an entry per L1 block with commitments.
+        let mut iter = self.db.iter::<CommitmentsByNumber>()?;
+        iter.seek_to_first();
+        for item in iter {
+            let (l1_height, commitments) = item?.into_tuple();
+            if commitments
+                .iter()
+                .all(|commitment| commitment.l2_end_block_number <= range.end().0)
+            {
+                schema_batch.delete::<CommitmentsByNumber>(&l1_height)?;
+            }
+        }
+
+        schema_batch.put::<LastPrunedBlock>(&(), &range.end().0)?;
+        self.db.write_schemas(schema_batch)?;
+
+        Ok(())
+    }
+
+    /// Sums up the SST file sizes of all ledger column families
+    #[instrument(level = "trace", skip(self), err, ret)]
+    fn get_db_size(&self) -> anyhow::Result<u64> {
+        LEDGER_TABLES
+            .iter()
+            .map(|table| self.db.get_property(table, "rocksdb.total-sst-files-size"))
+            .sum()
+    }
+
     /// Gets all executed migrations.
     #[instrument(level = "trace", skip(self), err)]
     fn get_executed_migrations(&self) -> anyhow::Result<Vec<(String, u64)>> {
@@ -487,6 +583,135 @@ impl SharedLedgerOps for LedgerDB {
     fn put_executed_migration(&self, migration: (String, u64)) -> anyhow::Result<()> {
         self.db.put::<ExecutedMigrations>(&migration, &())
     }
+
+    #[instrument(level = "trace", skip(self, receipt), err)]
+    fn put_commitment_publication_receipt(
+        &self,
+        merkle_root: [u8; 32],
+        receipt: &DaPublicationReceipt,
+    ) -> anyhow::Result<()> {
+        self.db
+            .put::<CommitmentPublicationReceipts>(&merkle_root, receipt)
+    }
+
+    #[instrument(level = "trace", skip(self, receipt), err)]
+    fn put_batch_proof_publication_receipt(
+        &self,
+        proof_hash: [u8; 32],
+        receipt: &DaPublicationReceipt,
+    ) -> anyhow::Result<()> {
+        self.db
+            .put::<BatchProofPublicationReceipts>(&proof_hash, receipt)
+    }
+
+    #[instrument(level = "trace", skip(self), err)]
+    fn put_pending_publication(
+        &self,
+        tx_id: [u8; 32],
+        pending: &StoredPendingPublication,
+    ) -> anyhow::Result<()> {
+        self.db.put::<PendingPublications>(&tx_id, pending)
+    }
+
+    #[instrument(level = "trace", skip(self), err)]
+    fn get_pending_publications(
+        &self,
+    ) -> anyhow::Result<Vec<([u8; 32], StoredPendingPublication)>> {
+        let mut iter = self.db.iter::<PendingPublications>()?;
+        iter.seek_to_first();
+
+        let pending = iter
+            .map(|item| item.map(|item| (item.key, item.value)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(pending)
+    }
+
+    #[instrument(level = "trace", skip(self), err)]
+    fn delete_pending_publication(&self, tx_id: [u8; 32]) -> anyhow::Result<()> {
+        self.db.delete::<PendingPublications>(&tx_id)
+    }
+
+    /// Removes the data recorded from orphaned L1 blocks
+    #[instrument(level = "trace", skip(self), err)]
+    fn rollback_l1_blocks(
+        &self,
+        fork_height: u64,
+        orphaned_hashes: &[[u8; 32]],
+    ) -> anyhow::Result<Vec<SequencerCommitment>> {
+        let mut schema_batch = SchemaBatch::new();
+        let mut commitments = Vec::new();
+        let mut orphaned_verified_proofs = Vec::new();
+
+        let last_scanned_l1_height = self
+            .db
+            .get::<ProverLastScannedSlot>(&())?
+            .map(|height| height.0)
+            .unwrap_or_default();
+        for l1_height in (fork_height + 1)..=last_scanned_l1_height {
+            let l1_height = SlotNumber(l1_height);
+            if let Some(slot_commitments) = self.db.get::<CommitmentsByNumber>(&l1_height)? {
+                commitments.extend(slot_commitments);
+            }
+            if let Some(verified_proofs) =
+                self.db.get::<VerifiedBatchProofsBySlotNumber>(&l1_height)?
+            {
+                orphaned_verified_proofs.extend(verified_proofs);
+            }
+            schema_batch.delete::<CommitmentsByNumber>(&l1_height)?;
+            schema_batch.delete::<ProofsBySlotNumberV2>(&l1_height)?;
+            schema_batch.delete::<VerifiedBatchProofsBySlotNumber>(&l1_height)?;
+            schema_batch.delete::<LightClientProofBySlotNumber>(&l1_height)?;
+        }
+
+        for hash in orphaned_hashes {
+            schema_batch.delete::<SlotByHash>(hash)?;
+        }
+
+        // L2 blocks proven only by orphaned proofs are back to being finalized
+        if !orphaned_verified_proofs.is_empty() {
+            let mut remaining_proven_ranges = Vec::new();
+            let mut iter = self.db.iter::<VerifiedBatchProofsBySlotNumber>()?;
+            iter.seek_to_first();
+            for item in iter {
+                let (l1_height, verified_proofs) = item?.into_tuple();
+                if l1_height.0 > fork_height {
+                    continue;
+                }
+                for verified_proof in verified_proofs {
+                    remaining_proven_ranges
+                        .push(self.proven_l2_range(&verified_proof.proof_output)?);
+                }
+            }
+
+            for verified_proof in orphaned_verified_proofs {
+                for l2_height in self.proven_l2_range(&verified_proof.proof_output)? {
+                    if remaining_proven_ranges
+                        .iter()
+                        .any(|range| range.contains(&l2_height))
+                    {
+                        continue;
+                    }
+                    let l2_height = BatchNumber(l2_height);
+                    if self.db.get::<SoftConfirmationStatus>(&l2_height)?
+                        == Some(sov_rollup_interface::rpc::SoftConfirmationStatus::Proven)
+                    {
+                        schema_batch.put::<SoftConfirmationStatus>(
+                            &l2_height,
+                            &sov_rollup_interface::rpc::SoftConfirmationStatus::Finalized,
+                        )?;
+                    }
+                }
+            }
+        }
+
+        if last_scanned_l1_height > fork_height {
+            schema_batch.put::<ProverLastScannedSlot>(&(), &SlotNumber(fork_height))?;
+        }
+        self.db.write_schemas(schema_batch)?;
+
+        Ok(commitments)
+    }
 }
 
 impl LightClientProverLedgerOps for LedgerDB {
@@ -771,6 +996,30 @@ impl SequencerLedgerOps for LedgerDB {
         self.db.write_schemas(schema_batch)?;
         Ok(())
     }
+
+    fn insert_mempool_deposit(&self, index: u64, deposit: Vec<u8>) -> anyhow::Result<()> {
+        self.db.put::<MempoolDeposits>(&index, &deposit)
+    }
+
+    fn remove_mempool_deposits(&self, indices: Vec<u64>) -> anyhow::Result<()> {
+        let mut schema_batch = SchemaBatch::new();
+        for index in indices {
+            schema_batch.delete::<MempoolDeposits>(&index)?;
+        }
+        self.db.write_schemas(schema_batch)?;
+        Ok(())
+    }
+
+    fn get_mempool_deposits(&self) -> anyhow::Result<Vec<(u64, Vec<u8>)>> {
+        let mut iter = self.db.iter::<MempoolDeposits>()?;
+        iter.seek_to_first();
+
+        let deposits = iter
+            .map(|item| item.map(|item| (item.key, item.value)))
+            .collect::<Result<Vec<(u64, Vec<u8>)>, _>>()?;
+
+        Ok(deposits)
+    }
 }
 
 impl NodeLedgerOps for LedgerDB {
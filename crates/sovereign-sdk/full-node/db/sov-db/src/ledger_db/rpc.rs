@@ -1,11 +1,12 @@
 use sov_rollup_interface::rpc::{
-    sequencer_commitment_to_response, BatchProofResponse, LastVerifiedBatchProofResponse,
-    LedgerRpcProvider, SequencerCommitmentResponse, SoftConfirmationIdentifier,
-    SoftConfirmationResponse, VerifiedBatchProofResponse,
+    sequencer_commitment_to_response, BatchProofResponse, DaPublicationReceiptResponse,
+    LastVerifiedBatchProofResponse, LedgerRpcProvider, SequencerCommitmentResponse,
+    SoftConfirmationIdentifier, SoftConfirmationResponse, VerifiedBatchProofResponse,
 };
 
 use crate::schema::tables::{
-    CommitmentsByNumber, SlotByHash, SoftConfirmationByHash, SoftConfirmationByNumber,
+    BatchProofPublicationReceipts, CommitmentPublicationReceipts, CommitmentsByNumber,
+    LastPrunedBlock, SlotByHash, SoftConfirmationByHash, SoftConfirmationByNumber,
     SoftConfirmationStatus, VerifiedBatchProofsBySlotNumber,
 };
 use crate::schema::types::{BatchNumber, SlotNumber};
@@ -25,6 +26,7 @@ impl LedgerRpcProvider for LedgerDB {
         let batch_num = self.resolve_soft_confirmation_identifier(batch_id)?;
         Ok(match batch_num {
             Some(num) => {
+                self.ensure_not_pruned(num.0)?;
                 if let Some(stored_batch) = self.db.get::<SoftConfirmationByNumber>(&num)? {
                     Some(stored_batch.try_into()?)
                 } else {
@@ -82,6 +84,7 @@ impl LedgerRpcProvider for LedgerDB {
             "requested batch range too large. Max: {}",
             MAX_BATCHES_PER_REQUEST
         );
+        self.ensure_not_pruned(start)?;
         let ids: Vec<_> = (start..=end)
             .map(SoftConfirmationIdentifier::Number)
             .collect();
@@ -92,6 +95,8 @@ impl LedgerRpcProvider for LedgerDB {
         &self,
         l2_height: u64,
     ) -> Result<sov_rollup_interface::rpc::SoftConfirmationStatus, anyhow::Error> {
+        self.ensure_not_pruned(l2_height)?;
+
         if self
             .db
             .get::<SoftConfirmationByNumber>(&BatchNumber(l2_height))
@@ -206,6 +211,26 @@ impl LedgerRpcProvider for LedgerDB {
         Ok(None)
     }
 
+    fn get_publication_receipt_by_commitment(
+        &self,
+        merkle_root: [u8; 32],
+    ) -> Result<Option<DaPublicationReceiptResponse>, anyhow::Error> {
+        Ok(self
+            .db
+            .get::<CommitmentPublicationReceipts>(&merkle_root)?
+            .map(DaPublicationReceiptResponse::from))
+    }
+
+    fn get_publication_receipt_by_batch_proof(
+        &self,
+        proof_hash: [u8; 32],
+    ) -> Result<Option<DaPublicationReceiptResponse>, anyhow::Error> {
+        Ok(self
+            .db
+            .get::<BatchProofPublicationReceipts>(&proof_hash)?
+            .map(DaPublicationReceiptResponse::from))
+    }
+
     fn get_head_soft_confirmation_height(&self) -> Result<u64, anyhow::Error> {
         let next_ids = self.get_next_items_numbers();
         Ok(next_ids.soft_confirmation_number.saturating_sub(1))
@@ -222,4 +247,18 @@ impl LedgerDB {
             SoftConfirmationIdentifier::Number(num) => Ok(Some(BatchNumber(*num))),
         }
     }
+
+    /// Returns an error if the soft confirmation at the given height has been pruned.
+    /// Used by lookups by number and by hash alike, as pruning keeps the hash to number mapping.
+    fn ensure_not_pruned(&self, l2_height: u64) -> Result<(), anyhow::Error> {
+        if let Some(last_pruned_l2_height) = self.db.get::<LastPrunedBlock>(&())? {
+            anyhow::ensure!(
+                l2_height > last_pruned_l2_height,
+                "Soft confirmation at height {} has been pruned. Last pruned L2 height: {}",
+                l2_height,
+                last_pruned_l2_height
+            );
+        }
+        Ok(())
+    }
 }
@@ -1,13 +1,23 @@
 use std::sync::OnceLock;
 
 use anyhow::anyhow;
+use sov_rollup_interface::da::{DaPublicationReceipt, SequencerCommitment};
+use sov_rollup_interface::rpc::{LedgerRpcProvider, SoftConfirmationStatus};
 use sov_schema_db::SchemaBatch;
 
 use super::migrations::{LedgerDBMigrator, LedgerMigration, MigrationName, MigrationVersion};
 use super::LedgerDB;
-use crate::ledger_db::{SharedLedgerOps, TestLedgerOps};
+use crate::ledger_db::{
+    BatchProverLedgerOps, NodeLedgerOps, SequencerLedgerOps, SharedLedgerOps, TestLedgerOps,
+};
 use crate::rocks_db_config::RocksdbConfig;
-use crate::schema::tables::TestTableOld;
+use crate::schema::tables::{
+    L2RangeByL1Height, L2Witness, SlotByNumber, SoftConfirmationByHash, TestTableOld,
+};
+use crate::schema::types::{
+    BatchNumber, DbBytes, PublishedData, SlotNumber, StoredBatchProofOutput,
+    StoredPendingPublication, StoredSlot, StoredSoftConfirmation,
+};
 
 pub fn successful_migrations() -> &'static Vec<Box<dyn LedgerMigration + Send + Sync + 'static>> {
     static MIGRATIONS: OnceLock<Vec<Box<dyn LedgerMigration + Send + Sync + 'static>>> =
@@ -128,3 +138,351 @@ fn test_failed_migrations() {
     let executed_migrations = ledger_db.get_executed_migrations().unwrap();
     assert_eq!(executed_migrations.len(), 0);
 }
+
+#[test]
+fn test_prune_soft_confirmations() {
+    let ledger_db_path = tempfile::tempdir().unwrap();
+    let ledger_db =
+        LedgerDB::with_config(&RocksdbConfig::new(ledger_db_path.path(), None, None)).unwrap();
+
+    // Two L2 blocks per L1 block
+    for l2_height in 1..=10u64 {
+        let l1_height = (l2_height + 1) / 2;
+        let soft_confirmation = StoredSoftConfirmation {
+            l2_height,
+            da_slot_height: l1_height,
+            da_slot_hash: [0; 32],
+            da_slot_txs_commitment: [0; 32],
+            hash: [l2_height as u8; 32],
+            prev_hash: [l2_height as u8 - 1; 32],
+            txs: vec![],
+            deposit_data: vec![],
+            state_root: vec![],
+            soft_confirmation_signature: vec![],
+            pub_key: vec![],
+            l1_fee_rate: 0,
+            timestamp: 0,
+        };
+        let mut schema_batch = SchemaBatch::new();
+        ledger_db
+            .put_soft_confirmation(
+                &soft_confirmation,
+                &BatchNumber(l2_height),
+                &mut schema_batch,
+            )
+            .unwrap();
+        ledger_db.db.write_schemas(schema_batch).unwrap();
+        ledger_db
+            .extend_l2_range_of_l1_slot(SlotNumber(l1_height), BatchNumber(l2_height))
+            .unwrap();
+        ledger_db
+            .set_l2_witness(l2_height, &vec![1u8], &vec![2u8])
+            .unwrap();
+    }
+
+    for l1_height in 1..=5u64 {
+        let slot = StoredSlot {
+            hash: [l1_height as u8; 32],
+            extra_data: DbBytes::new(vec![]),
+            batches: BatchNumber(2 * l1_height - 1)..BatchNumber(2 * l1_height + 1),
+        };
+        let mut schema_batch = SchemaBatch::new();
+        ledger_db
+            .put_slot(&slot, &SlotNumber(l1_height), &mut schema_batch)
+            .unwrap();
+        ledger_db.db.write_schemas(schema_batch).unwrap();
+    }
+    for (l1_height, l2_start_block_number, l2_end_block_number) in [(3, 1, 4), (6, 5, 8)] {
+        ledger_db
+            .update_commitments_on_da_slot(
+                l1_height,
+                SequencerCommitment {
+                    merkle_root: [0; 32],
+                    l2_start_block_number,
+                    l2_end_block_number,
+                },
+            )
+            .unwrap();
+    }
+
+    ledger_db
+        .prune_soft_confirmations(&(BatchNumber(1)..=BatchNumber(5)))
+        .unwrap();
+
+    assert_eq!(ledger_db.get_last_pruned_l2_height().unwrap(), Some(5));
+    for l2_height in 1..=5u64 {
+        assert!(SharedLedgerOps::get_soft_confirmation_by_number(
+            &ledger_db,
+            &BatchNumber(l2_height)
+        )
+        .unwrap()
+        .is_none());
+        assert!(ledger_db
+            .db
+            .get::<L2Witness>(&BatchNumber(l2_height))
+            .unwrap()
+            .is_none());
+        // Pruned heights are reported as such over RPC
+        assert!(LedgerRpcProvider::get_soft_confirmation_by_number(&ledger_db, l2_height).is_err());
+        assert!(ledger_db
+            .get_soft_confirmation_by_hash(&[l2_height as u8; 32])
+            .is_err());
+    }
+    assert!(ledger_db.get_soft_confirmations_range(4, 7).is_err());
+    assert_eq!(
+        ledger_db.get_soft_confirmations_range(6, 8).unwrap().len(),
+        3
+    );
+    assert!(ledger_db
+        .get_soft_confirmation_by_hash(&[42u8; 32])
+        .unwrap()
+        .is_none());
+    for l2_height in 6..=10u64 {
+        assert!(
+            LedgerRpcProvider::get_soft_confirmation_by_number(&ledger_db, l2_height)
+                .unwrap()
+                .is_some()
+        );
+        assert!(ledger_db
+            .db
+            .get::<L2Witness>(&BatchNumber(l2_height))
+            .unwrap()
+            .is_some());
+    }
+
+    // L1 heights 1 and 2 are fully pruned, L1 height 3 still has L2 block 6
+    assert!(ledger_db
+        .db
+        .get::<L2RangeByL1Height>(&SlotNumber(2))
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        ledger_db
+            .db
+            .get::<L2RangeByL1Height>(&SlotNumber(3))
+            .unwrap(),
+        Some((BatchNumber(5), BatchNumber(6)))
+    );
+
+    // So are the slots, and the commitment to L2 blocks 1 to 4
+    assert!(ledger_db
+        .db
+        .get::<SlotByNumber>(&SlotNumber(2))
+        .unwrap()
+        .is_none());
+    assert!(ledger_db
+        .db
+        .get::<SlotByNumber>(&SlotNumber(3))
+        .unwrap()
+        .is_some());
+    assert!(ledger_db.get_commitments_on_da_slot(3).unwrap().is_none());
+    assert!(ledger_db.get_commitments_on_da_slot(6).unwrap().is_some());
+}
+
+#[test]
+fn test_mempool_deposits() {
+    let ledger_db_path = tempfile::tempdir().unwrap();
+    let ledger_db =
+        LedgerDB::with_config(&RocksdbConfig::new(ledger_db_path.path(), None, None)).unwrap();
+
+    // Indices above 255 make sure keys are ordered numerically, not by their first byte
+    for index in [2u64, 300, 1] {
+        ledger_db
+            .insert_mempool_deposit(index, vec![index as u8])
+            .unwrap();
+    }
+    assert_eq!(
+        ledger_db.get_mempool_deposits().unwrap(),
+        vec![(1, vec![1]), (2, vec![2]), (300, vec![44])]
+    );
+
+    ledger_db.remove_mempool_deposits(vec![1, 300]).unwrap();
+    assert_eq!(
+        ledger_db.get_mempool_deposits().unwrap(),
+        vec![(2, vec![2])]
+    );
+}
+
+#[test]
+fn test_rollback_l1_blocks() {
+    let ledger_db_path = tempfile::tempdir().unwrap();
+    let ledger_db =
+        LedgerDB::with_config(&RocksdbConfig::new(ledger_db_path.path(), None, None)).unwrap();
+
+    for l1_height in 1..=4u64 {
+        ledger_db
+            .update_commitments_on_da_slot(
+                l1_height,
+                SequencerCommitment {
+                    merkle_root: [l1_height as u8; 32],
+                    l2_start_block_number: l1_height * 10,
+                    l2_end_block_number: l1_height * 10 + 9,
+                },
+            )
+            .unwrap();
+        ledger_db
+            .set_l1_height_of_l1_hash([l1_height as u8; 32], l1_height)
+            .unwrap();
+        ledger_db
+            .set_last_scanned_l1_height(SlotNumber(l1_height))
+            .unwrap();
+    }
+
+    // L2 blocks 10-19 are proven in L1 block 2, and 20-39 in L1 block 3
+    let proof_output = |prev_l2_height: u64, last_l2_height: u64| StoredBatchProofOutput {
+        initial_state_root: vec![],
+        final_state_root: vec![],
+        prev_soft_confirmation_hash: [prev_l2_height as u8; 32],
+        final_soft_confirmation_hash: [last_l2_height as u8; 32],
+        state_diff: Default::default(),
+        da_slot_hash: [0u8; 32],
+        sequencer_commitments_range: (0, 0),
+        sequencer_public_key: vec![],
+        sequencer_da_public_key: vec![],
+        preproven_commitments: vec![],
+        last_l2_height,
+    };
+    for (l1_height, prev_l2_height, last_l2_height) in [(2, 9, 19), (3, 19, 39)] {
+        ledger_db
+            .db
+            .put::<SoftConfirmationByHash>(
+                &[prev_l2_height as u8; 32],
+                &BatchNumber(prev_l2_height),
+            )
+            .unwrap();
+        ledger_db
+            .update_verified_proof_data(
+                l1_height,
+                vec![],
+                proof_output(prev_l2_height, last_l2_height),
+            )
+            .unwrap();
+    }
+    for l2_height in 10..=39 {
+        ledger_db
+            .put_soft_confirmation_status(BatchNumber(l2_height), SoftConfirmationStatus::Proven)
+            .unwrap();
+    }
+
+    let commitments = ledger_db
+        .rollback_l1_blocks(2, &[[3; 32], [4; 32]])
+        .unwrap();
+    assert_eq!(
+        commitments
+            .iter()
+            .map(|commitment| commitment.l2_start_block_number)
+            .collect::<Vec<_>>(),
+        vec![30, 40]
+    );
+
+    assert_eq!(
+        ledger_db.get_last_scanned_l1_height().unwrap(),
+        Some(SlotNumber(2))
+    );
+    assert!(ledger_db.get_commitments_on_da_slot(2).unwrap().is_some());
+    assert!(ledger_db.get_commitments_on_da_slot(3).unwrap().is_none());
+    assert_eq!(
+        ledger_db.get_l1_height_of_l1_hash([2; 32]).unwrap(),
+        Some(2)
+    );
+    assert_eq!(ledger_db.get_l1_height_of_l1_hash([4; 32]).unwrap(), None);
+
+    // Only the blocks proven in the orphaned L1 block are no longer proven
+    assert_eq!(
+        ledger_db
+            .get_soft_confirmation_status(BatchNumber(19))
+            .unwrap(),
+        Some(SoftConfirmationStatus::Proven)
+    );
+    assert_eq!(
+        ledger_db
+            .get_soft_confirmation_status(BatchNumber(20))
+            .unwrap(),
+        Some(SoftConfirmationStatus::Finalized)
+    );
+    assert_eq!(
+        ledger_db
+            .get_last_verified_batch_proof()
+            .unwrap()
+            .unwrap()
+            .height,
+        2
+    );
+
+    // Nothing above the fork is left to roll back
+    assert!(ledger_db.rollback_l1_blocks(2, &[]).unwrap().is_empty());
+}
+
+#[test]
+fn test_publication_receipts() {
+    let ledger_db_path = tempfile::tempdir().unwrap();
+    let ledger_db =
+        LedgerDB::with_config(&RocksdbConfig::new(ledger_db_path.path(), None, None)).unwrap();
+
+    let receipt = DaPublicationReceipt {
+        commit_tx_id: [1; 32],
+        reveal_tx_id: [2; 32],
+        l1_height: 3,
+        l1_block_hash: [4; 32],
+        inclusion_proof: vec![5, 6],
+    };
+    ledger_db
+        .put_commitment_publication_receipt([7; 32], &receipt)
+        .unwrap();
+
+    let response = ledger_db
+        .get_publication_receipt_by_commitment([7; 32])
+        .unwrap()
+        .unwrap();
+    assert_eq!(response.reveal_tx_id, [2; 32]);
+    assert_eq!(response.l1_height, 3);
+    assert_eq!(response.inclusion_proof, vec![5, 6]);
+
+    // Commitments and proofs are indexed separately
+    assert!(ledger_db
+        .get_publication_receipt_by_batch_proof([7; 32])
+        .unwrap()
+        .is_none());
+    ledger_db
+        .put_batch_proof_publication_receipt([7; 32], &receipt)
+        .unwrap();
+    assert!(ledger_db
+        .get_publication_receipt_by_batch_proof([7; 32])
+        .unwrap()
+        .is_some());
+}
+
+#[test]
+fn test_pending_publications() {
+    let ledger_db_path = tempfile::tempdir().unwrap();
+    let pending = StoredPendingPublication {
+        data: PublishedData::SequencerCommitment([7; 32]),
+        sent_at: 1_700_000_000,
+    };
+    {
+        let ledger_db =
+            LedgerDB::with_config(&RocksdbConfig::new(ledger_db_path.path(), None, None)).unwrap();
+        assert!(ledger_db.get_pending_publications().unwrap().is_empty());
+        ledger_db
+            .put_pending_publication([1; 32], &pending)
+            .unwrap();
+        ledger_db
+            .put_pending_publication(
+                [2; 32],
+                &StoredPendingPublication {
+                    data: PublishedData::BatchProof([8; 32]),
+                    sent_at: 1_700_000_060,
+                },
+            )
+            .unwrap();
+        ledger_db.delete_pending_publication([2; 32]).unwrap();
+    }
+
+    // Pending publications are kept over restarts
+    let ledger_db =
+        LedgerDB::with_config(&RocksdbConfig::new(ledger_db_path.path(), None, None)).unwrap();
+    assert_eq!(
+        ledger_db.get_pending_publications().unwrap(),
+        vec![([1; 32], pending)]
+    );
+}
@@ -3,7 +3,7 @@ use std::path::Path;
 use anyhow::Result;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
-use sov_rollup_interface::da::{DaSpec, SequencerCommitment};
+use sov_rollup_interface::da::{DaPublicationReceipt, DaSpec, SequencerCommitment};
 use sov_rollup_interface::stf::{SoftConfirmationReceipt, StateDiff};
 use sov_rollup_interface::zk::Proof;
 use sov_schema_db::SchemaBatch;
@@ -11,7 +11,8 @@ use sov_schema_db::SchemaBatch;
 use super::ItemNumbers;
 use crate::schema::types::{
     BatchNumber, L2HeightRange, SlotNumber, StoredBatchProof, StoredBatchProofOutput,
-    StoredLightClientProof, StoredLightClientProofOutput, StoredSlot, StoredSoftConfirmation,
+    StoredLightClientProof, StoredLightClientProofOutput, StoredPendingPublication, StoredSlot,
+    StoredSoftConfirmation,
 };
 
 /// Shared ledger operations
@@ -131,11 +132,61 @@ pub trait SharedLedgerOps {
     /// Set the last pruned block number
     fn set_last_pruned_l2_height(&self, l2_height: u64) -> Result<()>;
 
+    /// Deletes soft confirmations (and their transactions) in the given range alongside
+    /// their witnesses, state diffs, statuses and the L1 to L2 range mappings, slots and
+    /// sequencer commitments which are fully covered by the range.
+    /// L1 hash to height mappings and the proofs stored by L1 height are kept, so the
+    /// L2 ranges of those proofs may refer to pruned soft confirmations.
+    /// The end of the range is recorded as the last pruned L2 height in the same batch.
+    fn prune_soft_confirmations(&self, range: &std::ops::RangeInclusive<BatchNumber>)
+        -> Result<()>;
+
+    /// Returns the total size of the SST files of all ledger tables in bytes.
+    fn get_db_size(&self) -> Result<u64>;
+
     /// Gets all executed migrations.
     fn get_executed_migrations(&self) -> anyhow::Result<Vec<(String, u64)>>;
 
     /// Put a pending commitment l2 range
     fn put_executed_migration(&self, migration: (String, u64)) -> anyhow::Result<()>;
+
+    /// Stores the DA transaction which published the sequencer commitment with the given merkle root
+    fn put_commitment_publication_receipt(
+        &self,
+        merkle_root: [u8; 32],
+        receipt: &DaPublicationReceipt,
+    ) -> Result<()>;
+
+    /// Stores the DA transaction which published the batch proof with the given hash
+    fn put_batch_proof_publication_receipt(
+        &self,
+        proof_hash: [u8; 32],
+        receipt: &DaPublicationReceipt,
+    ) -> Result<()>;
+
+    /// Stores a DA tx whose publication receipt is waited for
+    fn put_pending_publication(
+        &self,
+        tx_id: [u8; 32],
+        pending: &StoredPendingPublication,
+    ) -> Result<()>;
+
+    /// Gets the DA txs whose publication receipts are waited for, by DA tx id
+    fn get_pending_publications(&self) -> Result<Vec<([u8; 32], StoredPendingPublication)>>;
+
+    /// Deletes a DA tx whose publication receipt is no longer waited for
+    fn delete_pending_publication(&self, tx_id: [u8; 32]) -> Result<()>;
+
+    /// Deletes the sequencer commitments, batch proofs and light client proofs found in the
+    /// scanned L1 blocks above `fork_height` and the heights of the `orphaned_hashes`.
+    /// The last scanned L1 height is set to `fork_height` in the same batch, and L2 blocks only
+    /// proven by the deleted verified batch proofs are set back to finalized.
+    /// Returns the deleted sequencer commitments.
+    fn rollback_l1_blocks(
+        &self,
+        fork_height: u64,
+        orphaned_hashes: &[[u8; 32]],
+    ) -> Result<Vec<SequencerCommitment>>;
 }
 
 /// Node ledger operations
@@ -263,6 +314,15 @@ pub trait SequencerLedgerOps: SharedLedgerOps {
 
     /// Fetch mempool transactions
     fn get_mempool_txs(&self) -> anyhow::Result<Vec<(Vec<u8>, Vec<u8>)>>;
+
+    /// Insert a deposit of the deposit mempool under its arrival index
+    fn insert_mempool_deposit(&self, index: u64, deposit: Vec<u8>) -> anyhow::Result<()>;
+
+    /// Remove deposits from the deposit mempool by their arrival indices
+    fn remove_mempool_deposits(&self, indices: Vec<u64>) -> anyhow::Result<()>;
+
+    /// Fetch deposits of the deposit mempool ordered by their arrival index
+    fn get_mempool_deposits(&self) -> anyhow::Result<Vec<(u64, Vec<u8>)>>;
 }
 
 /// Test ledger operations
@@ -96,6 +96,30 @@ impl<Q: QueryManager> NativeDB<Q> {
     }
 }
 
+/// Deletes every version of the given accessory keys from the database in a single batch.
+///
+/// This operates on the raw [`sov_schema_db::DB`] rather than on snapshots, hence it should
+/// only be used to remove finalized data, e.g. when pruning history which is not needed anymore.
+pub fn prune_accessory_keys(
+    db: &sov_schema_db::DB,
+    keys: impl IntoIterator<Item = AccessoryKey>,
+) -> anyhow::Result<()> {
+    let mut batch = SchemaBatch::default();
+    for key in keys {
+        let mut iter = db.iter::<ModuleAccessoryState>()?;
+        iter.seek(&(key.clone(), 0))?;
+
+        for item in iter {
+            let (found_key, version) = item?.key;
+            if found_key != key {
+                break;
+            }
+            batch.delete::<ModuleAccessoryState>(&(found_key, version))?;
+        }
+    }
+    db.write_schemas(batch)
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::RwLock;
@@ -141,4 +165,45 @@ mod tests {
         let key = b"spam".to_vec();
         assert_eq!(db.get_value_option(&key, 0).unwrap(), None);
     }
+
+    #[test]
+    fn get_after_prune() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let raw_db = NativeDB::<NoopQueryManager>::setup_schema_db(&RocksdbConfig::new(
+            tmpdir.path(),
+            None,
+            None,
+        ))
+        .unwrap();
+
+        let pruned_key = b"pruned".to_vec();
+        let kept_key = b"prunedd".to_vec();
+        let mut batch = SchemaBatch::default();
+        for version in 0..3 {
+            batch
+                .put::<ModuleAccessoryState>(&(pruned_key.clone(), version), &Some(vec![1]))
+                .unwrap();
+            batch
+                .put::<ModuleAccessoryState>(&(kept_key.clone(), version), &Some(vec![2]))
+                .unwrap();
+        }
+        raw_db.write_schemas(batch).unwrap();
+
+        prune_accessory_keys(&raw_db, vec![pruned_key.clone()]).unwrap();
+
+        for version in 0..3 {
+            assert_eq!(
+                raw_db
+                    .get::<ModuleAccessoryState>(&(pruned_key.clone(), version))
+                    .unwrap(),
+                None
+            );
+            assert_eq!(
+                raw_db
+                    .get::<ModuleAccessoryState>(&(kept_key.clone(), version))
+                    .unwrap(),
+                Some(Some(vec![2]))
+            );
+        }
+    }
 }
@@ -25,15 +25,15 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 use jmt::storage::{NibblePath, Node, NodeKey};
 use jmt::Version;
-use sov_rollup_interface::da::SequencerCommitment;
+use sov_rollup_interface::da::{DaPublicationReceipt, SequencerCommitment};
 use sov_rollup_interface::stf::StateDiff;
 use sov_schema_db::schema::{KeyDecoder, KeyEncoder, ValueCodec};
 use sov_schema_db::{CodecError, SeekKeyEncoder};
 
 use super::types::{
     AccessoryKey, AccessoryStateValue, BatchNumber, DbHash, JmtValue, L2HeightRange, SlotNumber,
-    StateKey, StoredBatch, StoredBatchProof, StoredLightClientProof, StoredSlot,
-    StoredSoftConfirmation, StoredVerifiedProof,
+    StateKey, StoredBatch, StoredBatchProof, StoredLightClientProof, StoredPendingPublication,
+    StoredSlot, StoredSoftConfirmation, StoredVerifiedProof,
 };
 
 /// A list of all tables used by the StateDB. These tables store rollup state - meaning
@@ -67,9 +67,13 @@ pub const LEDGER_TABLES: &[&str] = &[
     ProofsBySlotNumberV2::table_name(),
     VerifiedBatchProofsBySlotNumber::table_name(),
     MempoolTxs::table_name(),
+    MempoolDeposits::table_name(),
     PendingProvingSessions::table_name(),
     ProverStateDiffs::table_name(),
     LastPrunedBlock::table_name(),
+    CommitmentPublicationReceipts::table_name(),
+    BatchProofPublicationReceipts::table_name(),
+    PendingPublications::table_name(),
     #[cfg(test)]
     TestTableOld::table_name(),
     #[cfg(test)]
@@ -336,6 +340,11 @@ define_table_with_default_codec!(
     (MempoolTxs) Vec<u8> => Vec<u8>
 );
 
+define_table_with_seek_key_codec!(
+    /// Deposits in the sequencer deposit mempool, keyed by their order of arrival
+    (MempoolDeposits) u64 => Vec<u8>
+);
+
 define_table_with_default_codec!(
     /// L2 height to state diff for prover
     (ProverStateDiffs) BatchNumber => StateDiff
@@ -346,6 +355,21 @@ define_table_with_seek_key_codec!(
     (LastPrunedBlock) () => u64
 );
 
+define_table_with_default_codec!(
+    /// DA transactions which published the sequencer commitments, by merkle root
+    (CommitmentPublicationReceipts) DbHash => DaPublicationReceipt
+);
+
+define_table_with_default_codec!(
+    /// DA transactions which published the batch proofs, by proof hash
+    (BatchProofPublicationReceipts) DbHash => DaPublicationReceipt
+);
+
+define_table_with_default_codec!(
+    /// DA transactions whose publication receipts are not stored yet, by DA tx id
+    (PendingPublications) DbHash => StoredPendingPublication
+);
+
 #[cfg(test)]
 define_table_with_seek_key_codec!(
     /// Test table old
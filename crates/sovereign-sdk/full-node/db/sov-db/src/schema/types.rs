@@ -195,6 +195,24 @@ pub struct StoredVerifiedProof {
     pub proof_output: StoredBatchProofOutput,
 }
 
+/// Data published on the DA layer, whose publication receipt is stored once its DA tx is finalized
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub enum PublishedData {
+    /// A sequencer commitment, by merkle root.
+    SequencerCommitment([u8; 32]),
+    /// A batch proof, by sha256 hash.
+    BatchProof([u8; 32]),
+}
+
+/// The on-disk format of a DA tx whose publication receipt is not stored yet
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct StoredPendingPublication {
+    /// What the DA tx published
+    pub data: PublishedData,
+    /// Unix timestamp in seconds of when the DA tx was sent
+    pub sent_at: u64,
+}
+
 impl From<StoredVerifiedProof> for VerifiedBatchProofResponse {
     fn from(value: StoredVerifiedProof) -> Self {
         Self {
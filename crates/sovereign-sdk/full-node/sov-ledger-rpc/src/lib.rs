@@ -4,8 +4,9 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use reth_primitives::U64;
 use sov_rollup_interface::rpc::{
-    BatchProofResponse, LastVerifiedBatchProofResponse, SequencerCommitmentResponse,
-    SoftConfirmationResponse, SoftConfirmationStatus, VerifiedBatchProofResponse,
+    BatchProofResponse, DaPublicationReceiptResponse, LastVerifiedBatchProofResponse,
+    SequencerCommitmentResponse, SoftConfirmationResponse, SoftConfirmationStatus,
+    VerifiedBatchProofResponse,
 };
 
 #[cfg(feature = "server")]
@@ -136,6 +137,22 @@ pub trait LedgerRpc {
     #[blocking]
     fn get_last_verified_batch_proof(&self) -> RpcResult<Option<LastVerifiedBatchProofResponse>>;
 
+    /// Gets the DA transaction which published the sequencer commitment with the given merkle root.
+    #[method(name = "getPublicationReceiptByCommitment")]
+    #[blocking]
+    fn get_publication_receipt_by_commitment(
+        &self,
+        merkle_root: HexHash,
+    ) -> RpcResult<Option<DaPublicationReceiptResponse>>;
+
+    /// Gets the DA transaction which published the batch proof with the given sha256 hash.
+    #[method(name = "getPublicationReceiptByBatchProof")]
+    #[blocking]
+    fn get_publication_receipt_by_batch_proof(
+        &self,
+        proof_hash: HexHash,
+    ) -> RpcResult<Option<DaPublicationReceiptResponse>>;
+
     /// Get last scanned l1 height
     #[method(name = "getLastScannedL1Height")]
     #[blocking]
@@ -6,9 +6,9 @@ use jsonrpsee::RpcModule;
 use reth_primitives::U64;
 use sov_modules_api::utils::to_jsonrpsee_error_object;
 use sov_rollup_interface::rpc::{
-    BatchProofResponse, LastVerifiedBatchProofResponse, LedgerRpcProvider,
-    SequencerCommitmentResponse, SoftConfirmationResponse, SoftConfirmationStatus,
-    VerifiedBatchProofResponse,
+    BatchProofResponse, DaPublicationReceiptResponse, LastVerifiedBatchProofResponse,
+    LedgerRpcProvider, SequencerCommitmentResponse, SoftConfirmationResponse,
+    SoftConfirmationStatus, VerifiedBatchProofResponse,
 };
 
 use crate::{HexHash, LedgerRpcServer};
@@ -148,6 +148,24 @@ where
             .map_err(to_ledger_rpc_error)
     }
 
+    fn get_publication_receipt_by_commitment(
+        &self,
+        merkle_root: HexHash,
+    ) -> RpcResult<Option<DaPublicationReceiptResponse>> {
+        self.ledger
+            .get_publication_receipt_by_commitment(merkle_root.0)
+            .map_err(to_ledger_rpc_error)
+    }
+
+    fn get_publication_receipt_by_batch_proof(
+        &self,
+        proof_hash: HexHash,
+    ) -> RpcResult<Option<DaPublicationReceiptResponse>> {
+        self.ledger
+            .get_publication_receipt_by_batch_proof(proof_hash.0)
+            .map_err(to_ledger_rpc_error)
+    }
+
     fn get_head_soft_confirmation(&self) -> RpcResult<Option<SoftConfirmationResponse>> {
         self.ledger
             .get_head_soft_confirmation()
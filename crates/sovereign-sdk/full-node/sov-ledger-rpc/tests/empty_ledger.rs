@@ -78,4 +78,14 @@ async fn getters_succeed() {
         .unwrap();
 
     rpc_client.get_last_verified_batch_proof().await.unwrap();
+
+    rpc_client
+        .get_publication_receipt_by_commitment(hash)
+        .await
+        .unwrap();
+
+    rpc_client
+        .get_publication_receipt_by_batch_proof(hash)
+        .await
+        .unwrap();
 }
@@ -37,6 +37,9 @@ pub struct ProverStorageManager<Da: DaSpec> {
 
     state_snapshot_manager: Arc<RwLock<SnapshotManager>>,
     accessory_snapshot_manager: Arc<RwLock<SnapshotManager>>,
+
+    // Shared with the accessory snapshot manager, used for pruning finalized accessory state
+    native_db: Arc<sov_schema_db::DB>,
 }
 
 impl<Da: DaSpec> ProverStorageManager<Da>
@@ -45,10 +48,12 @@ where
 {
     fn with_db_handles(state_db: sov_schema_db::DB, native_db: sov_schema_db::DB) -> Self {
         let snapshot_id_to_parent = Arc::new(RwLock::new(HashMap::new()));
+        let native_db = Arc::new(native_db);
 
-        let state_snapshot_manager = SnapshotManager::new(state_db, snapshot_id_to_parent.clone());
+        let state_snapshot_manager =
+            SnapshotManager::new(Arc::new(state_db), snapshot_id_to_parent.clone());
         let accessory_snapshot_manager =
-            SnapshotManager::new(native_db, snapshot_id_to_parent.clone());
+            SnapshotManager::new(native_db.clone(), snapshot_id_to_parent.clone());
 
         Self {
             chain_forks: Default::default(),
@@ -60,6 +65,7 @@ where
             snapshot_id_to_parent,
             state_snapshot_manager: Arc::new(RwLock::new(state_snapshot_manager)),
             accessory_snapshot_manager: Arc::new(RwLock::new(accessory_snapshot_manager)),
+            native_db,
         }
     }
 
@@ -216,6 +222,13 @@ where
         self.get_storage_with_snapshot_id(snapshot_id)
     }
 
+    /// Returns a handle to the underlying native (accessory) database.
+    /// Writes through this handle bypass snapshots, so it should only be used
+    /// to modify finalized data, e.g. for pruning.
+    pub fn get_native_db_handle(&self) -> Arc<sov_schema_db::DB> {
+        self.native_db.clone()
+    }
+
     pub fn finalize_l2(&mut self, l2_block_height: u64) -> anyhow::Result<()> {
         self.finalize_by_l2_height(l2_block_height)
     }
@@ -15,7 +15,7 @@ use crate::snapshot_manager::DataLocation::Snapshot;
 /// down to DB level
 /// Managed externally by [`crate::ProverStorageManager`]
 pub struct SnapshotManager {
-    db: sov_schema_db::DB,
+    db: Arc<sov_schema_db::DB>,
     snapshots: HashMap<SnapshotId, ReadOnlyDbSnapshot>,
     /// Hierarchical
     to_parent: Arc<RwLock<HashMap<SnapshotId, SnapshotId>>>,
@@ -23,7 +23,7 @@ pub struct SnapshotManager {
 
 impl SnapshotManager {
     pub(crate) fn new(
-        db: sov_schema_db::DB,
+        db: Arc<sov_schema_db::DB>,
         to_parent: Arc<RwLock<HashMap<SnapshotId, SnapshotId>>>,
     ) -> Self {
         Self {
@@ -37,7 +37,7 @@ impl SnapshotManager {
     /// So it only reads from database.
     pub fn orphan(db: sov_schema_db::DB) -> Self {
         Self {
-            db,
+            db: Arc::new(db),
             snapshots: HashMap::new(),
             to_parent: Arc::new(RwLock::new(Default::default())),
         }
@@ -317,7 +317,8 @@ mod tests {
     fn test_empty() {
         let tempdir = tempfile::tempdir().unwrap();
         let db = create_test_db(tempdir.path());
-        let snapshot_manager = SnapshotManager::new(db, Arc::new(RwLock::new(HashMap::new())));
+        let snapshot_manager =
+            SnapshotManager::new(Arc::new(db), Arc::new(RwLock::new(HashMap::new())));
         assert!(snapshot_manager.is_empty());
     }
 
@@ -326,7 +327,7 @@ mod tests {
         let tempdir = tempfile::tempdir().unwrap();
         let db = create_test_db(tempdir.path());
         let to_parent = Arc::new(RwLock::new(HashMap::new()));
-        let mut snapshot_manager = SnapshotManager::new(db, to_parent.clone());
+        let mut snapshot_manager = SnapshotManager::new(Arc::new(db), to_parent.clone());
         let query_manager = Arc::new(RwLock::new(NoopQueryManager));
 
         let snapshot_id = 1;
@@ -344,7 +345,7 @@ mod tests {
         let tempdir = tempfile::tempdir().unwrap();
         let db = create_test_db(tempdir.path());
         let to_parent = Arc::new(RwLock::new(HashMap::new()));
-        let mut snapshot_manager = SnapshotManager::new(db, to_parent.clone());
+        let mut snapshot_manager = SnapshotManager::new(Arc::new(db), to_parent.clone());
         let query_manager = Arc::new(RwLock::new(NoopQueryManager));
 
         let snapshot_id = 1;
@@ -363,7 +364,7 @@ mod tests {
         let tempdir = tempfile::tempdir().unwrap();
         let db = create_test_db(tempdir.path());
         let to_parent = Arc::new(RwLock::new(HashMap::new()));
-        let mut snapshot_manager = SnapshotManager::new(db, to_parent.clone());
+        let mut snapshot_manager = SnapshotManager::new(Arc::new(db), to_parent.clone());
 
         snapshot_manager.commit_snapshot(&1).unwrap();
     }
@@ -375,7 +376,7 @@ mod tests {
         let tempdir = tempfile::tempdir().unwrap();
         let db = create_test_db(tempdir.path());
         let to_parent = Arc::new(RwLock::new(HashMap::new()));
-        let mut snapshot_manager = SnapshotManager::new(db, to_parent.clone());
+        let mut snapshot_manager = SnapshotManager::new(Arc::new(db), to_parent.clone());
 
         snapshot_manager.discard_snapshot(&1);
     }
@@ -385,7 +386,7 @@ mod tests {
         let tempdir = tempfile::tempdir().unwrap();
         let db = create_test_db(tempdir.path());
         let to_parent = Arc::new(RwLock::new(HashMap::new()));
-        let mut snapshot_manager = SnapshotManager::new(db, to_parent.clone());
+        let mut snapshot_manager = SnapshotManager::new(Arc::new(db), to_parent.clone());
         let query_manager = Arc::new(RwLock::new(NoopQueryManager));
 
         let snapshot_id = 1;
@@ -402,7 +403,7 @@ mod tests {
         let tempdir = tempfile::tempdir().unwrap();
         let db = create_test_db(tempdir.path());
         let to_parent = Arc::new(RwLock::new(HashMap::new()));
-        let snapshot_manager = SnapshotManager::new(db, to_parent.clone());
+        let snapshot_manager = SnapshotManager::new(Arc::new(db), to_parent.clone());
         assert_eq!(
             None,
             snapshot_manager.get::<Schema>(1, &TestField(1)).unwrap()
@@ -424,7 +425,7 @@ mod tests {
         db_data.put::<Schema>(&three, &three).unwrap();
         db.write_schemas(db_data).unwrap();
 
-        let mut snapshot_manager = SnapshotManager::new(db, to_parent.clone());
+        let mut snapshot_manager = SnapshotManager::new(Arc::new(db), to_parent.clone());
         let query_manager = Arc::new(RwLock::new(NoopQueryManager));
 
         let db_snapshot = DbSnapshot::new(1, query_manager.clone().into());
@@ -473,7 +474,7 @@ mod tests {
         db_data.put::<Schema>(&f1, &f1).unwrap();
         db.write_schemas(db_data).unwrap();
 
-        let mut snapshot_manager = SnapshotManager::new(db, to_parent.clone());
+        let mut snapshot_manager = SnapshotManager::new(Arc::new(db), to_parent.clone());
         let query_manager = Arc::new(RwLock::new(NoopQueryManager));
 
         // Operations:
@@ -576,7 +577,7 @@ mod tests {
         db_data.put::<Schema>(&f4, &f1).unwrap();
         db.write_schemas(db_data).unwrap();
 
-        let mut snapshot_manager = SnapshotManager::new(db, to_parent.clone());
+        let mut snapshot_manager = SnapshotManager::new(Arc::new(db), to_parent.clone());
         let query_manager = Arc::new(RwLock::new(NoopQueryManager));
 
         // Operations:
@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use sov_rollup_interface::services::da::DaService;
+use sov_rollup_interface::spec::SpecId;
 use sov_rollup_interface::zk::Proof;
 use thiserror::Error;
 
@@ -91,14 +92,16 @@ pub trait ProverService {
     /// Prove added input and assumptions.
     async fn prove(&self, elf: Vec<u8>) -> anyhow::Result<Vec<Proof>>;
 
-    /// Submit proofs to DA.
+    /// Submit proofs, proven under the fork with `spec_id`, to DA.
     async fn submit_proofs(
         &self,
         proofs: Vec<Proof>,
+        spec_id: SpecId,
     ) -> anyhow::Result<Vec<(<Self::DaService as DaService>::TransactionId, Proof)>>;
 
-    /// Recover the ongoing sessions and submit them to DA.
+    /// Recover the ongoing sessions and submit them to DA as proven under the fork with `spec_id`.
     async fn recover_and_submit_proving_sessions(
         &self,
+        spec_id: SpecId,
     ) -> anyhow::Result<Vec<(<Self::DaService as DaService>::TransactionId, Proof)>>;
 }
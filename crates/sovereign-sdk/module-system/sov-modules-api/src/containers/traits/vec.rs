@@ -101,6 +101,13 @@ where
         Some(elem)
     }
 
+    /// Deletes the value at the given index without shifting the remaining
+    /// values or changing the length of the vector. Subsequent gets of the
+    /// index return [`None`].
+    fn delete(&self, index: usize, working_set: &mut W) {
+        self.elems().delete(&index, working_set);
+    }
+
     /// Removes all values from this vector.
     fn clear(&self, working_set: &mut W) {
         let len = self.len_value().remove(working_set).unwrap_or_default();
@@ -42,7 +42,7 @@ pub use sov_modules_macros::MessageCodec;
 ///        _context: &Self::Context,
 ///        _working_set: &mut WorkingSet<C::Storage>,
 ///     ) -> Result<CallResponse, Error> {
-///        Ok(CallResponse {})
+///        Ok(CallResponse::default())
 ///     }
 /// }
 ///
@@ -18,7 +18,10 @@ pub use spec::*;
 
 /// Response type for the `Module::call` method.
 #[derive(Default, Debug)]
-pub struct CallResponse {}
+pub struct CallResponse {
+    /// Estimated size in bytes of the state diff the call adds to the L1 commitment.
+    pub l1_diff_size: u64,
+}
 
 /// The core trait implemented by all modules. This trait defines how a module is initialized at genesis,
 /// and how it handles user transactions (if applicable).
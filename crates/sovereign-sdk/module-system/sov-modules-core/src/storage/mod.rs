@@ -331,6 +331,14 @@ pub trait NativeStorage: Storage {
     /// get the value.
     fn get_with_proof(&self, key: StorageKey) -> StorageProof<Self::Proof>;
 
+    /// Returns the value corresponding to the key or None if key is absent and a proof to
+    /// get the value, at the requested version of the tree.
+    fn get_with_proof_at_version(
+        &self,
+        key: StorageKey,
+        version: Version,
+    ) -> Result<StorageProof<Self::Proof>, anyhow::Error>;
+
     /// Get the root hash of the tree at the requested version
     fn get_root_hash(&self, version: Version) -> Result<Self::Root, anyhow::Error>;
 }
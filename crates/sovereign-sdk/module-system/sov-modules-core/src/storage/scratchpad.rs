@@ -506,6 +506,29 @@ impl<S: Storage> WorkingSet<S> {
         // First inner is `RevertableWriter` and second inner is actually a `Storage` instance
         self.delta.inner.inner.get_with_proof(key)
     }
+
+    /// Fetches given value at the given version and provides a proof of it presence/absence.
+    pub fn get_with_proof_at_version(
+        &mut self,
+        key: StorageKey,
+        version: Version,
+    ) -> Result<StorageProof<<S as Storage>::Proof>, anyhow::Error>
+    where
+        S: NativeStorage,
+    {
+        self.delta
+            .inner
+            .inner
+            .get_with_proof_at_version(key, version)
+    }
+
+    /// Returns the root hash of the underlying storage at the given version.
+    pub fn get_root_hash(&self, version: Version) -> Result<<S as Storage>::Root, anyhow::Error>
+    where
+        S: NativeStorage,
+    {
+        self.delta.inner.inner.get_root_hash(version)
+    }
 }
 
 impl<S: Storage> StateReaderAndWriter for WorkingSet<S> {
@@ -132,7 +132,10 @@ pub trait RollupBlueprint: Sized + Send + Sync {
     ) -> Result<Arc<Self::DaService>, anyhow::Error>;
 
     /// Creates instance of [`BitcoinDaVerifier`]
-    fn create_da_verifier(&self) -> Self::DaVerifier;
+    fn create_da_verifier(
+        &self,
+        rollup_config: &FullNodeConfig<Self::DaConfig>,
+    ) -> Result<Self::DaVerifier, anyhow::Error>;
 
     /// Creates instance of [`ProverService`].
     async fn create_prover_service(
@@ -12,8 +12,8 @@ use sov_modules_api::hooks::{
 };
 use sov_modules_api::transaction::Transaction;
 use sov_modules_api::{
-    native_debug, BasicAddress, BlobReaderTrait, Context, DaSpec, DispatchCall, Genesis, Signature,
-    Spec, StateCheckpoint, UnsignedSoftConfirmation, WorkingSet,
+    native_debug, BasicAddress, BlobReaderTrait, CallResponse, Context, DaSpec, DispatchCall,
+    Genesis, Signature, Spec, StateCheckpoint, UnsignedSoftConfirmation, WorkingSet,
 };
 use sov_rollup_interface::da::DaDataBatchProof;
 use sov_rollup_interface::fork::ForkManager;
@@ -76,6 +76,10 @@ pub trait Runtime<C: Context, Da: DaSpec>:
     fn genesis_config(
         genesis_paths: &Self::GenesisPaths,
     ) -> Result<Self::GenesisConfig, anyhow::Error>;
+
+    /// Discards what modules keep in memory about the latest dispatched call,
+    /// for when the working set it was applied to gets reverted.
+    fn revert_latest_call(&mut self) {}
 }
 
 /// The receipts of all the transactions in a batch.
@@ -167,10 +171,16 @@ where
         txs: &[Vec<u8>],
         txs_new: &[<Self as StateTransitionFunction<Da>>::Transaction],
         batch_workspace: &mut WorkingSet<C::Storage>,
-    ) -> Result<(), StateTransitionError> {
+    ) -> Result<CallResponse, StateTransitionError> {
         self.apply_sov_txs_inner(soft_confirmation_info, txs, txs_new, batch_workspace)
     }
 
+    /// Discards what the runtime keeps in memory about the latest applied call,
+    /// after the working set it was applied to got reverted.
+    pub fn revert_latest_call(&mut self) {
+        self.runtime.revert_latest_call();
+    }
+
     /// End a soft confirmation
     pub fn end_soft_confirmation(
         &mut self,
@@ -3,7 +3,9 @@ use std::marker::PhantomData;
 use borsh::BorshDeserialize;
 use sov_modules_api::hooks::HookSoftConfirmationInfo;
 use sov_modules_api::transaction::Transaction;
-use sov_modules_api::{native_debug, native_error, Context, DaSpec, SpecId, WorkingSet};
+use sov_modules_api::{
+    native_debug, native_error, CallResponse, Context, DaSpec, SpecId, WorkingSet,
+};
 use sov_rollup_interface::soft_confirmation::SignedSoftConfirmation;
 use sov_rollup_interface::stf::{
     SoftConfirmationError, SoftConfirmationHookError, StateTransitionError, StateTransitionFunction,
@@ -51,6 +53,7 @@ where
     }
 
     /// Applies sov txs to the state
+    /// Returns the responses of the calls combined
     #[cfg_attr(feature = "native", instrument(level = "trace", skip_all))]
     pub fn apply_sov_txs_inner(
         &mut self,
@@ -58,10 +61,13 @@ where
         txs: &[Vec<u8>],
         txs_new: &[<Self as StateTransitionFunction<Da>>::Transaction],
         sc_workspace: &mut WorkingSet<C::Storage>,
-    ) -> Result<(), StateTransitionError> {
+    ) -> Result<CallResponse, StateTransitionError> {
+        let mut response = CallResponse::default();
         if soft_confirmation_info.current_spec >= SpecId::Fork1 {
             for tx in txs_new {
-                self.apply_sov_tx_inner(&soft_confirmation_info, tx, sc_workspace)?;
+                let tx_response =
+                    self.apply_sov_tx_inner(&soft_confirmation_info, tx, sc_workspace)?;
+                response.l1_diff_size += tx_response.l1_diff_size;
             }
         } else {
             for raw_tx in txs {
@@ -73,11 +79,13 @@ where
                     )
                 })?;
 
-                self.apply_sov_tx_inner(&soft_confirmation_info, &tx, sc_workspace)?;
+                let tx_response =
+                    self.apply_sov_tx_inner(&soft_confirmation_info, &tx, sc_workspace)?;
+                response.l1_diff_size += tx_response.l1_diff_size;
             }
         };
 
-        Ok(())
+        Ok(response)
     }
 
     fn apply_sov_tx_inner(
@@ -85,7 +93,7 @@ where
         soft_confirmation_info: &HookSoftConfirmationInfo,
         tx: &Transaction<C>,
         sc_workspace: &mut WorkingSet<C::Storage>,
-    ) -> Result<(), StateTransitionError> {
+    ) -> Result<CallResponse, StateTransitionError> {
         tx.verify().map_err(|_| {
             StateTransitionError::SoftConfirmationError(
                 SoftConfirmationError::InvalidSovTxSignature,
@@ -113,7 +121,7 @@ where
             .pre_dispatch_tx_hook(tx, sc_workspace, &hook)
             .map_err(StateTransitionError::HookError)?;
 
-        let _ = self
+        let response = self
             .runtime
             .dispatch_call(msg, sc_workspace, &ctx)
             .map_err(StateTransitionError::ModuleCallError)?;
@@ -122,7 +130,7 @@ where
             .post_dispatch_tx_hook(tx, &ctx, sc_workspace)
             .map_err(StateTransitionError::HookError)?;
 
-        Ok(())
+        Ok(response)
     }
 
     /// Begins the inner processes of applying soft confirmation
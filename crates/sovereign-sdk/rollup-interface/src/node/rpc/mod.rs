@@ -11,7 +11,7 @@ use core::marker::PhantomData;
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 
-use crate::da::SequencerCommitment;
+use crate::da::{DaPublicationReceipt, SequencerCommitment};
 use crate::soft_confirmation::SignedSoftConfirmation;
 use crate::zk::{BatchProofInfo, CumulativeStateDiff};
 
@@ -214,6 +214,38 @@ pub struct LastVerifiedBatchProofResponse {
     pub height: u64,
 }
 
+/// The rpc response of the DA transaction which published a sequencer commitment or batch proof
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DaPublicationReceiptResponse {
+    /// Id of the transaction committing to the data
+    #[serde(with = "hex::serde")]
+    pub commit_tx_id: [u8; 32],
+    /// Id of the transaction revealing the data
+    #[serde(with = "hex::serde")]
+    pub reveal_tx_id: [u8; 32],
+    /// L1 height of the block including the reveal tx
+    pub l1_height: u64,
+    /// L1 hash of the block including the reveal tx
+    #[serde(with = "hex::serde")]
+    pub l1_block_hash: [u8; 32],
+    /// Proof of the inclusion of the reveal tx in the block
+    #[serde(with = "hex::serde")]
+    pub inclusion_proof: Vec<u8>,
+}
+
+impl From<DaPublicationReceipt> for DaPublicationReceiptResponse {
+    fn from(value: DaPublicationReceipt) -> Self {
+        Self {
+            commit_tx_id: value.commit_tx_id,
+            reveal_tx_id: value.reveal_tx_id,
+            l1_height: value.l1_height,
+            l1_block_hash: value.l1_block_hash,
+            inclusion_proof: value.inclusion_proof,
+        }
+    }
+}
+
 /// The ZK proof generated by the [`ZkvmHost::run`] method to be served by rpc.
 pub type ProofRpcResponse = Vec<u8>;
 
@@ -440,6 +472,18 @@ pub trait LedgerRpcProvider {
         &self,
     ) -> Result<Option<LastVerifiedBatchProofResponse>, anyhow::Error>;
 
+    /// Get the DA publication receipt of the sequencer commitment with the given merkle root
+    fn get_publication_receipt_by_commitment(
+        &self,
+        merkle_root: [u8; 32],
+    ) -> Result<Option<DaPublicationReceiptResponse>, anyhow::Error>;
+
+    /// Get the DA publication receipt of the batch proof with the given hash
+    fn get_publication_receipt_by_batch_proof(
+        &self,
+        proof_hash: [u8; 32],
+    ) -> Result<Option<DaPublicationReceiptResponse>, anyhow::Error>;
+
     /// Get head soft confirmation
     fn get_head_soft_confirmation(&self)
         -> Result<Option<SoftConfirmationResponse>, anyhow::Error>;
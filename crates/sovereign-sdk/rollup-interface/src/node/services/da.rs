@@ -9,7 +9,9 @@ use tokio::sync::oneshot::Sender as OneshotSender;
 
 use crate::da::BlockHeaderTrait;
 #[cfg(feature = "native")]
-use crate::da::{DaData, DaNamespace, DaSpec, DaVerifier, SequencerCommitment};
+use crate::da::{
+    DaData, DaNamespace, DaPublicationReceipt, DaSpec, DaVerifier, SequencerCommitment,
+};
 #[cfg(feature = "native")]
 use crate::zk::Proof;
 
@@ -45,7 +47,14 @@ pub trait DaService: Send + Sync + 'static {
     >;
 
     /// A transaction ID, used to identify the transaction in the DA layer.
-    type TransactionId: Send + PartialEq + Eq + PartialOrd + Ord + core::hash::Hash + Into<[u8; 32]>;
+    type TransactionId: Send
+        + Clone
+        + PartialEq
+        + Eq
+        + PartialOrd
+        + Ord
+        + core::hash::Hash
+        + Into<[u8; 32]>;
 
     /// The error type for fallible methods.
     type Error: core::fmt::Debug + Send + Sync + core::fmt::Display;
@@ -119,6 +128,13 @@ pub trait DaService: Send + Sync + 'static {
         unimplemented!()
     }
 
+    /// Returns where the transaction sent with id `tx_id` was published.
+    /// Returns `None` while it is not included in a block.
+    async fn get_publication_receipt(
+        &self,
+        tx_id: [u8; 32],
+    ) -> Result<Option<DaPublicationReceipt>, Self::Error>;
+
     /// Returns fee rate per byte on DA layer.
     async fn get_fee_rate(&self) -> Result<u128, Self::Error>;
 
@@ -33,6 +33,14 @@ mod spec {
         /// 2. EVM cancun upgrade (with no kzg precompile)
         /// 3. Don't use borsh when signing SoftConfirmation's
         Fork1 = 1,
+        /// Second fork activates EVM prague upgrade:
+        /// 1. EIP-7702 set code transactions
+        /// 2. BLS12-381 precompiles
+        /// 3. EIP-2935 historical block hashes in state
+        /// 4. Chunked batch proof DA transactions, read from an L1 activation height
+        /// 5. Zstd compressed DA blobs in a versioned envelope, read from the same L1 height,
+        ///    light client blobs which don't decompress are skipped instead of halting readers
+        Fork2 = 2,
     }
 }
 
@@ -7,6 +7,7 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
+use crate::spec::SpecId;
 use crate::zk::{LightClientCircuitOutput, Proof};
 use crate::BasicAddress;
 
@@ -34,6 +35,21 @@ impl core::cmp::Ord for SequencerCommitment {
     }
 }
 
+/// Where a transaction sent to the DA layer was published.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct DaPublicationReceipt {
+    /// Id of the transaction committing to the data, the reveal tx itself if the DA layer has none
+    pub commit_tx_id: [u8; 32],
+    /// Id of the transaction revealing the data
+    pub reveal_tx_id: [u8; 32],
+    /// DA block height
+    pub l1_height: u64,
+    /// DA block hash
+    pub l1_block_hash: [u8; 32],
+    /// Proof of the inclusion of the reveal tx in the block, encoded as defined by the DA layer
+    pub inclusion_proof: Vec<u8>,
+}
+
 /// UpdatedDaState is the state after verifying and applying a block
 /// on top of the existing DA state.
 #[derive(Debug, Clone, Default)]
@@ -60,8 +76,8 @@ pub struct UpdatedDaState<Spec: DaSpec> {
 pub enum DaData {
     /// A commitment from the sequencer
     SequencerCommitment(SequencerCommitment),
-    /// Or a zk proof and state diff
-    ZKProof(Proof),
+    /// Or a zk proof and state diff, with the spec of the fork it was proven under
+    ZKProof(Proof, SpecId),
 }
 
 /// Data written to DA and read from DA must be the borsh serialization of this enum
@@ -1,7 +1,6 @@
 #![no_main]
-use bitcoin_da::spec::RollupParams;
+use bitcoin_da::network::NetworkProfile;
 use bitcoin_da::verifier::BitcoinVerifier;
-use citrea_primitives::{TO_BATCH_PROOF_PREFIX, TO_LIGHT_CLIENT_PREFIX};
 use citrea_risc0_adapter::guest::Risc0Guest;
 use citrea_stf::runtime::Runtime;
 use citrea_stf::StfVerifier;
@@ -20,10 +19,7 @@ pub fn main() {
 
     let mut stf_verifier = StfVerifier::new(
         stf,
-        BitcoinVerifier::new(RollupParams {
-            to_batch_proof_prefix: TO_BATCH_PROOF_PREFIX.to_vec(),
-            to_light_client_prefix: TO_LIGHT_CLIENT_PREFIX.to_vec(),
-        }),
+        BitcoinVerifier::new(NetworkProfile::guest_rollup_params()),
     );
 
     stf_verifier